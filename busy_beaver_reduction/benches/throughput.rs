@@ -0,0 +1,105 @@
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+
+use busy_beaver_reduction::champions::champions::CHAMPIONS;
+use busy_beaver_reduction::delta::transition_function::TransitionFunction;
+use busy_beaver_reduction::filter::filter_compile::FilterCompile;
+use busy_beaver_reduction::generator::generator_transition_function::{
+    GenerationAlgorithm, GeneratorTransitionFunction,
+};
+use busy_beaver_reduction::turing_machine::turing_machine::TuringMachine;
+
+/// Number of transition functions sent at once by the generator.
+const BATCH_SIZE: usize = 1000;
+/// Number of transition functions in the batch given to the compile filter.
+const COMPILE_BATCH_SIZE: usize = 10_000;
+/// Number of steps the BB(3) champion is executed for, more than it needs to halt.
+const MAX_STEPS: u64 = 100;
+
+/// Generates all the transition functions with `number_of_states` states
+/// and 2 symbols, while another thread receives them.
+///
+/// Returns the number of transition functions generated.
+fn generate(number_of_states: u8) -> usize {
+    let (tx, rx): (
+        Sender<Vec<TransitionFunction>>,
+        Receiver<Vec<TransitionFunction>>,
+    ) = channel();
+
+    let receiver_handle = thread::spawn(move || {
+        return rx
+            .iter()
+            .map(|transition_functions| transition_functions.len())
+            .sum();
+    });
+
+    let mut generator = GeneratorTransitionFunction::new(number_of_states, 2);
+    generator.generate_all_transition_functions(GenerationAlgorithm::Deque, tx, BATCH_SIZE);
+
+    return receiver_handle.join().unwrap();
+}
+
+fn bench_generation(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("generate_all_transition_functions");
+    group.sample_size(10);
+
+    for number_of_states in [2u8, 3] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(number_of_states),
+            &number_of_states,
+            |bencher, &number_of_states| bencher.iter(|| generate(number_of_states)),
+        );
+    }
+
+    group.finish();
+}
+
+fn bench_compile_filter(criterion: &mut Criterion) {
+    // the generation order is deterministic, so the batch is always the same
+    let batch: Vec<TransitionFunction> = GeneratorTransitionFunction::iter(3, 2)
+        .take(COMPILE_BATCH_SIZE)
+        .collect();
+
+    criterion.bench_function("filter_compile", |bencher| {
+        bencher.iter_batched(
+            || FilterCompile::new(3, 2, 2),
+            |mut filter_compile| {
+                batch
+                    .iter()
+                    .filter(|transition_function| filter_compile.filter_all(transition_function))
+                    .count()
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn bench_execute(criterion: &mut Criterion) {
+    let champion = CHAMPIONS
+        .iter()
+        .find(|champion| champion.states == 3)
+        .unwrap();
+    let transition_function =
+        TransitionFunction::from_standard_notation(champion.standard_notation).unwrap();
+
+    criterion.bench_function("execute_bb3_champion", |bencher| {
+        bencher.iter_batched(
+            || TuringMachine::new(transition_function.clone()),
+            |mut turing_machine| {
+                turing_machine.execute(black_box(MAX_STEPS));
+                turing_machine
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_generation,
+    bench_compile_filter,
+    bench_execute
+);
+criterion_main!(benches);
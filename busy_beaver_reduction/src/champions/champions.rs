@@ -0,0 +1,136 @@
+use std::fmt;
+
+use crate::delta::decode_error::DecodeError;
+use crate::delta::parse_error::ParseError;
+use crate::delta::transition_function::TransitionFunction;
+use crate::turing_machine::turing_machine::TuringMachine;
+
+/// A verified Busy Beaver champion: the Turing machine with `states` states
+/// and 2 symbols that writes the most 1s on the tape before halting.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Champion {
+    pub states: u8,
+    pub standard_notation: &'static str,
+    pub score: u64,
+    pub steps: u64,
+}
+
+/// The known champions, used to validate the pipeline end-to-end.
+pub const CHAMPIONS: [Champion; 3] = [
+    Champion {
+        states: 2,
+        standard_notation: "1RB1LB_1LA1RH",
+        score: 4,
+        steps: 6,
+    },
+    Champion {
+        states: 3,
+        standard_notation: "1RB1RH_0RC1RB_1LC1LA",
+        score: 6,
+        steps: 14,
+    },
+    Champion {
+        states: 4,
+        standard_notation: "1RB1LB_1LA0LC_1RH1LD_1RD0RA",
+        score: 13,
+        steps: 107,
+    },
+];
+
+/// Errors that can occur while verifying the known champions.
+#[derive(Debug, PartialEq)]
+pub enum VerifyError {
+    /// There is no known champion with the given number of states.
+    UnknownChampion(u8),
+    /// The standard notation of the champion could not be parsed.
+    Parse(ParseError),
+    /// The encoding of the champion could not be decoded.
+    Decode(DecodeError),
+    /// The champion did not halt with the expected score and steps.
+    Mismatch {
+        champion: Champion,
+        halted: bool,
+        score: u64,
+        steps: u64,
+    },
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::UnknownChampion(states) => {
+                write!(f, "no known champion with {} states", states)
+            }
+            VerifyError::Parse(error) => write!(f, "invalid champion: {}", error),
+            VerifyError::Decode(error) => write!(f, "invalid champion encoding: {}", error),
+            VerifyError::Mismatch {
+                champion,
+                halted,
+                score,
+                steps,
+            } => write!(
+                f,
+                "champion {} expected to halt with score {} in {} steps, but got halted = {}, score {} in {} steps",
+                champion.standard_notation, champion.score, champion.steps, halted, score, steps
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/// Verifies the known champion with `number_of_states` states: decodes
+/// its transition function, runs it and checks that it halts with
+/// the expected score, in the expected number of steps.
+pub fn verify_champions(number_of_states: u8) -> Result<(), VerifyError> {
+    let champion = CHAMPIONS
+        .iter()
+        .find(|champion| champion.states == number_of_states)
+        .ok_or(VerifyError::UnknownChampion(number_of_states))?;
+
+    // go through the encoding, the way the transition
+    // functions are read back from the database
+    let parsed = TransitionFunction::from_standard_notation(champion.standard_notation)
+        .map_err(VerifyError::Parse)?;
+    let mut transition_function =
+        TransitionFunction::new(parsed.number_of_states, parsed.number_of_symbols);
+    transition_function
+        .decode(parsed.encode())
+        .map_err(VerifyError::Decode)?;
+
+    let mut turing_machine = TuringMachine::new(transition_function);
+    turing_machine.execute(TuringMachine::get_default_max_steps(number_of_states));
+
+    if turing_machine.halted == false
+        || turing_machine.score != champion.score
+        || turing_machine.steps != champion.steps
+    {
+        return Err(VerifyError::Mismatch {
+            champion: *champion,
+            halted: turing_machine.halted,
+            score: turing_machine.score,
+            steps: turing_machine.steps,
+        });
+    }
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_known_champions() {
+        for champion in CHAMPIONS.iter() {
+            if let Err(error) = verify_champions(champion.states) {
+                panic!("{}", error);
+            }
+        }
+    }
+
+    #[test]
+    fn verify_unknown_champion() {
+        assert_eq!(verify_champions(9), Err(VerifyError::UnknownChampion(9)));
+    }
+}
@@ -0,0 +1,130 @@
+use std::path::PathBuf;
+
+use clap::builder::RangedU64ValueParser;
+use clap::Parser;
+
+/// Maximum number of states a Turing machine can be generated with.
+pub const MAXIMUM_STATES: u8 = 5;
+/// Maximum number of symbols of the tape alphabet, so each
+/// symbol can be written as a single digit.
+pub const MAXIMUM_SYMBOLS: u8 = 10;
+
+/// Generates, filters and runs all the Turing machines with
+/// the given number of states and symbols, in search of the Busy Beaver.
+#[derive(Parser, Debug)]
+#[command(version, about)]
+pub struct Arguments {
+    /// Number of states of the Turing machines, without the halting state.
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u8).range(1..=MAXIMUM_STATES as i64))]
+    pub states: u8,
+
+    /// Number of symbols of the tape alphabet, including the blank symbol.
+    #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(2..=MAXIMUM_SYMBOLS as i64))]
+    pub symbols: u8,
+
+    /// Number of transition functions sent at once by the generator.
+    #[arg(long, default_value_t = 100, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub batch_size: usize,
+
+    /// Number of Turing machines inserted in the database with a single statement.
+    #[arg(long, default_value_t = 1000, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub database_batch_size: usize,
+
+    /// Number of threads the Turing machines are executed on;
+    /// defaults to the available parallelism.
+    #[arg(long, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub threads: Option<usize>,
+
+    /// Generates, filters and runs the Turing machines
+    /// without accessing the database.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Inserts only the Turing machines that halted in the database.
+    #[arg(long)]
+    pub halting_only: bool,
+
+    /// Writes a JSON summary of the run to the given file,
+    /// once the Turing machines finished running.
+    #[arg(long)]
+    pub report: Option<PathBuf>,
+
+    /// Re-runs the Turing machines from the database that did not halt,
+    /// for at most the given number of steps, updating the ones that halted.
+    #[arg(long, value_parser = RangedU64ValueParser::<u64>::new().range(1..))]
+    pub rerun_holdouts: Option<u64>,
+
+    /// Runs the encodings of transition functions read from the standard
+    /// input, one per line, and prints their results, instead of the generation.
+    #[arg(long)]
+    pub run_stdin: bool,
+
+    /// Runs the known champion with the given number of states
+    /// and checks its score and steps, instead of the generation.
+    #[arg(long)]
+    pub verify_champions: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_defaults() {
+        let arguments = Arguments::try_parse_from(["busy_beaver_reduction"]).unwrap();
+
+        assert_eq!(arguments.states, 3);
+        assert_eq!(arguments.symbols, 2);
+        assert_eq!(arguments.batch_size, 100);
+        assert_eq!(arguments.database_batch_size, 1000);
+        assert_eq!(arguments.threads, None);
+        assert_eq!(arguments.dry_run, false);
+        assert_eq!(arguments.halting_only, false);
+        assert_eq!(arguments.report, None);
+        assert_eq!(arguments.rerun_holdouts, None);
+        assert_eq!(arguments.run_stdin, false);
+    }
+
+    #[test]
+    fn parse_arguments() {
+        let arguments = Arguments::try_parse_from([
+            "busy_beaver_reduction",
+            "--states",
+            "4",
+            "--symbols",
+            "2",
+            "--batch-size",
+            "500",
+            "--database-batch-size",
+            "250",
+            "--threads",
+            "16",
+            "--dry-run",
+            "--halting-only",
+            "--report",
+            "report.json",
+            "--rerun-holdouts",
+            "5000",
+        ])
+        .unwrap();
+
+        assert_eq!(arguments.states, 4);
+        assert_eq!(arguments.symbols, 2);
+        assert_eq!(arguments.batch_size, 500);
+        assert_eq!(arguments.database_batch_size, 250);
+        assert_eq!(arguments.threads, Some(16));
+        assert_eq!(arguments.dry_run, true);
+        assert_eq!(arguments.halting_only, true);
+        assert_eq!(arguments.report, Some(PathBuf::from("report.json")));
+        assert_eq!(arguments.rerun_holdouts, Some(5000));
+    }
+
+    #[test]
+    fn parse_invalid_states() {
+        let error =
+            Arguments::try_parse_from(["busy_beaver_reduction", "--states", "9"]).unwrap_err();
+
+        assert_eq!(error.kind(), clap::error::ErrorKind::ValueValidation);
+        assert!(Arguments::try_parse_from(["busy_beaver_reduction", "--states", "0"]).is_err());
+    }
+}
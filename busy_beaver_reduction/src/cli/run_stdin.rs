@@ -0,0 +1,73 @@
+use std::io::{self, BufRead, Write};
+
+use crate::turing_machine::turing_machine::TuringMachine;
+
+/// Reads the encodings of transition functions, one per line, in the
+/// format of `TransitionFunction::encode`, with `number_of_states` states
+/// and `number_of_symbols` symbols, and runs each of them for the default
+/// maximum number of steps, without generating them or accessing the database.
+///
+/// For every Turing machine executed, a result line is written to `writer`,
+/// with the columns of `turing_machine_csv::CSV_HEADER`.
+///
+/// The lines that can not be decoded are reported to `errors` and skipped,
+/// while the empty lines are ignored.
+pub fn run_encodings<R: BufRead, W: Write, E: Write>(
+    reader: R,
+    writer: &mut W,
+    errors: &mut E,
+    number_of_states: u8,
+    number_of_symbols: u8,
+) -> io::Result<()> {
+    let max_steps = TuringMachine::get_default_max_steps(number_of_states);
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let encoded = line.trim();
+
+        if encoded.is_empty() {
+            continue;
+        }
+
+        match TuringMachine::from_encoded(encoded, number_of_states, number_of_symbols) {
+            Ok(mut turing_machine) => {
+                turing_machine.execute(max_steps);
+                writeln!(writer, "{}", turing_machine.to_csv_row())?;
+            }
+            Err(error) => {
+                writeln!(errors, "Skipped line {}: {}", index + 1, error)?;
+            }
+        }
+    }
+
+    return writer.flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_encodings_two_lines() {
+        let input = "0,0,1,1,1|0,1,1,1,0|1,0,0,1,0|1,1,255,1,1\n\
+            not an encoding\n\
+            \n\
+            0,0,1,0,1|0,1,255,1,1|1,0,0,0,0|1,1,0,0,0\n";
+        let mut output: Vec<u8> = Vec::new();
+        let mut errors: Vec<u8> = Vec::new();
+
+        run_encodings(input.as_bytes(), &mut output, &mut errors, 2, 2).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        // the BB(2) champion
+        assert!(lines[0].starts_with("\"0,0,1,1,1|0,1,1,1,0|1,0,0,1,0|1,1,255,1,1\",2,2,true,6,4,"));
+        assert!(lines[1].starts_with("\"0,0,1,0,1|0,1,255,1,1|1,0,0,0,0|1,1,0,0,0\",2,2,false,"));
+
+        let errors = String::from_utf8(errors).unwrap();
+        assert_eq!(errors.lines().count(), 1);
+        assert!(errors.starts_with("Skipped line 2: "));
+    }
+}
@@ -1,49 +1,99 @@
-use log::{error, info};
-use sqlx::query::Query;
+use log::{error, info, warn};
 use std::env;
-
-use sqlx::mysql::{MySql, MySqlArguments, MySqlPoolOptions, MySqlQueryResult, MySqlRow};
-use sqlx::{Pool, Row};
-
-use crate::delta::transition_function::TransitionFunction;
+use std::fmt::Display;
+use std::future::Future;
+use std::time::Duration;
+
+use crate::database::dialect::Dialect;
+use crate::database::mysql_store::MySqlStore;
+use crate::database::postgres_store::PostgresStore;
+use crate::database::sqlite_store::SqliteStore;
+use crate::database::store::TmStore;
 use crate::turing_machine::turing_machine::TuringMachine;
 
-const MAX_POOL_CONNECTIONS: u32 = 8;
+/// Number of times `DatabaseManager::new` tries to connect to the database.
 const MAX_RETRIES: u8 = 3;
-
+/// Delay before the second attempt to connect, doubled after every attempt.
+const BASE_DELAY: Duration = Duration::from_millis(100);
+/// Database used when no `DATABASE_URL` is configured:
+/// a SQLite database file in the working directory.
+const DEFAULT_CONNECTION_STRING: &str = "sqlite://busy-beaver.db";
+
+/// Manages the `turing_machines` table, using the `TmStore`
+/// selected by the scheme of the `DATABASE_URL`:
+/// - `mysql://` -> `MySqlStore`
+/// - `postgres://` or `postgresql://` -> `PostgresStore`
+/// - `sqlite:` -> `SqliteStore`
 pub struct DatabaseManager {
-    pool: Pool<MySql>,
+    store: Box<dyn TmStore>,
 }
 
 impl DatabaseManager {
     pub async fn new() -> Option<Self> {
-        // counter for the number of times tried to connect
-        // to the database
-        let mut connection_retries: u8 = 0;
+        return DatabaseManager::with_retries(MAX_RETRIES, BASE_DELAY).await;
+    }
 
-        while connection_retries < MAX_RETRIES {
+    /// Connects to the database described by the `DATABASE_URL`, trying
+    /// at most `max_retries` times, with an exponential backoff between
+    /// the attempts: `base_delay`, `2 * base_delay`, `4 * base_delay`, ...
+    ///
+    /// The backoff gives a database that is briefly unavailable,
+    /// e.g. a container that is still starting, the time to come up.
+    pub async fn with_retries(max_retries: u8, base_delay: Duration) -> Option<Self> {
+        let store = DatabaseManager::connect_with_backoff(max_retries, base_delay, || async {
             let connection_string = DatabaseManager::get_connection_string();
-            let pool = DatabaseManager::get_pool(&connection_string).await;
+            DatabaseManager::get_store(&connection_string).await
+        })
+        .await?;
 
-            match pool {
-                Ok(pool) => {
-                    info!("DatabaseManager created successfully!");
-                    return Some(DatabaseManager { pool: pool });
-                }
+        info!("DatabaseManager created successfully!");
+        return Some(DatabaseManager { store: store });
+    }
+
+    /// Calls `connect` until it succeeds, at most `max_retries` times,
+    /// sleeping `base_delay * 2 ^ attempt` after every failed attempt,
+    /// except for the last one.
+    ///
+    /// Returns the connection, or `None` if every attempt failed.
+    async fn connect_with_backoff<T, E, F, Fut>(
+        max_retries: u8,
+        base_delay: Duration,
+        mut connect: F,
+    ) -> Option<T>
+    where
+        E: Display,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        for attempt in 0..max_retries {
+            match connect().await {
+                Ok(connection) => return Some(connection),
                 Err(error) => {
                     error!("DatabaseManager couldn't be created: {}", error);
                 }
             }
 
-            // increase the number of tries
-            connection_retries += 1;
+            if attempt + 1 < max_retries {
+                let delay = base_delay.saturating_mul(1 << attempt.min(31));
+                info!("Retrying to connect to the database in {:?}...", delay);
+                tokio::time::sleep(delay).await;
+            }
         }
 
         return None;
     }
 
+    /// Creates a `DatabaseManager` using the given `TmStore`,
+    /// instead of the one selected by the `DATABASE_URL`.
+    pub fn from_store(store: Box<dyn TmStore>) -> Self {
+        return DatabaseManager { store: store };
+    }
+
     /// Loads and gets the `connection string` to the database,
     /// from the `.env` file configured in the crate.
+    ///
+    /// If it is not configured, the embedded SQLite
+    /// database found at `DEFAULT_CONNECTION_STRING` is used.
     fn get_connection_string() -> String {
         match env::var("DATABASE_URL") {
             Ok(connection_string) => {
@@ -51,47 +101,34 @@ impl DatabaseManager {
             }
 
             Err(error) => {
-                error!(
-                    "While setting the connection string for the database: {}",
-                    error
+                info!(
+                    "Using the default database {}: {}",
+                    DEFAULT_CONNECTION_STRING, error
                 );
-                return "".to_string();
+                return DEFAULT_CONNECTION_STRING.to_string();
             }
         }
     }
 
-    /// Gets the `pool` of connections using the `connection_string`.
-    async fn get_pool(connection_string: &String) -> Result<Pool<MySql>, sqlx::Error> {
-        let pool = MySqlPoolOptions::new()
-            .max_connections(MAX_POOL_CONNECTIONS)
-            .connect(&connection_string)
-            .await?;
-
-        Ok(pool)
-    }
-
-    /// Given a `MySqlRow1 object, that should contain
-    /// an entry from the `turing_machines` table, transform
-    /// it into a TuringMachine object.
-    ///
-    /// Returns the `TuringMachine` obtained.
-    fn mysqlrow_to_turing_machine(&self, row: MySqlRow) -> TuringMachine {
-        // reconstruct the transition function
-        let transition_function_encoded = row.get(1);
-        let number_of_states: i8 = row.get(2);
-        let number_of_symbols: i8 = row.get(3);
-
-        let mut transition_function =
-            TransitionFunction::new(number_of_states as u8, number_of_symbols as u8);
-
-        // decode the transition function
-        transition_function.decode(transition_function_encoded);
-
-        // reconstruct the turing machine
-        let mut turing_machine = TuringMachine::new(transition_function);
-        turing_machine.halted = row.get(4);
-
-        return turing_machine;
+    /// Gets the `TmStore` for the database described by the
+    /// `connection_string`, based on its scheme.
+    async fn get_store(connection_string: &str) -> Result<Box<dyn TmStore>, sqlx::Error> {
+        match Dialect::from_connection_string(connection_string) {
+            Some(Dialect::MySql) => {
+                return Ok(Box::new(MySqlStore::connect(connection_string).await?));
+            }
+            Some(Dialect::Postgres) => {
+                return Ok(Box::new(PostgresStore::connect(connection_string).await?));
+            }
+            Some(Dialect::Sqlite) => {
+                return Ok(Box::new(SqliteStore::connect(connection_string).await?));
+            }
+            None => {
+                return Err(sqlx::Error::Configuration(
+                    "unsupported database scheme in the connection string".into(),
+                ));
+            }
+        }
     }
 
     /// Given a number of states and a number of symbols,
@@ -104,184 +141,464 @@ impl DatabaseManager {
         number_of_states: u8,
         number_of_symbols: u8,
     ) -> Option<Vec<TuringMachine>> {
-        let result: Result<Vec<MySqlRow>, sqlx::Error> = sqlx::query(
-            "
-                SELECT * 
-                FROM turing_machines 
-                WHERE number_of_states = ? 
-                    AND number_of_symbols = ?
-                    AND halted = FALSE",
-        )
-        .bind(number_of_states)
-        .bind(number_of_symbols)
-        .fetch_all(&self.pool)
-        .await;
-
-        match result {
-            Ok(rows) => {
-                let mut turing_machines = Vec::<TuringMachine>::new();
-
-                for row in rows {
-                    // reconstruct the turing machine
-                    // from the mysqlrow
-                    let turing_machine = self.mysqlrow_to_turing_machine(row);
-                    turing_machines.push(turing_machine);
-                }
+        return self
+            .store
+            .select_to_run(number_of_states, number_of_symbols)
+            .await;
+    }
 
-                return Some(turing_machines);
-            }
-            Err(error) => {
-                error!(
-                    "While selecting all turing machines from database: {}",
-                    error
-                );
-                return None;
-            }
-        }
+    /// Given a number of states and a number of symbols, selects at most
+    /// `limit` turing machines that `didn't halt`, with an `id` greater
+    /// than `after_id`, ordered ascending by their `id`.
+    ///
+    /// Unlike `select_turing_machines_to_run`, the table can be streamed
+    /// through in pages, by passing the last `id` of a page as the
+    /// `after_id` of the next one; the first page starts after `0`.
+    ///
+    /// Returns a `Option<Vec<(i32, TuringMachine)>>` with the Turing machines
+    /// of the page, together with their `id`s.
+    pub async fn select_turing_machines_to_run_paged(
+        &mut self,
+        number_of_states: u8,
+        number_of_symbols: u8,
+        after_id: i32,
+        limit: u32,
+    ) -> Option<Vec<(i32, TuringMachine)>> {
+        return self
+            .store
+            .select_to_run_paged(number_of_states, number_of_symbols, after_id, limit)
+            .await;
     }
 
     /// Given a turing machine, selects the turing machine
     /// from the database based on the encoding of the transition
     /// function.
     ///
-    /// Returns the `id` of the entry in the database, `if the entry exists`.
+    /// Returns the `id` of the entry in the database, `if the entry exists`,
+    /// or `None` otherwise; only a failed query is an error.
     pub async fn select_turing_machine_by_delta(
         &mut self,
         turing_machine: &TuringMachine,
-    ) -> Option<i32> {
-        let transition_function_encoded = turing_machine.transition_function.encode();
-
-        let result: Result<MySqlRow, sqlx::Error> = sqlx::query(
-            "
-                SELECT * 
-                FROM turing_machines 
-                WHERE transition_function = ?",
-        )
-        .bind(transition_function_encoded)
-        .fetch_one(&self.pool)
-        .await;
+    ) -> Result<Option<i32>, sqlx::Error> {
+        return self.store.select_by_delta(turing_machine).await;
+    }
 
-        match result {
-            Ok(row) => {
-                return row.get(0);
-            }
-            Err(error) => {
-                error!(
-                    "While selecting a turing machine from database, by the transition function: {}",
-                    error
-                );
-                return None;
-            }
-        }
+    /// Given the `id` of an entry in the database, selects the turing
+    /// machine stored in it, decoded with all of its metrics, e.g. for
+    /// inspecting a single machine.
+    ///
+    /// Returns `None` if there is no such entry or the query failed.
+    pub async fn fetch_turing_machine_by_id(&self, id: i32) -> Option<TuringMachine> {
+        return self.store.select_by_id(id).await;
+    }
+
+    /// Given a number of states and a number of symbols, counts the
+    /// turing machines stored with a transition function that matches
+    /// those numbers, halted or not.
+    ///
+    /// Returns `None` if the query failed.
+    pub async fn count_turing_machines(
+        &self,
+        number_of_states: u8,
+        number_of_symbols: u8,
+    ) -> Option<i64> {
+        return self.store.count(number_of_states, number_of_symbols).await;
+    }
+
+    /// Given a number of states and a number of symbols, selects
+    /// at most `limit` halted turing machines with the highest score.
+    ///
+    /// Returns a `Option<Vec<TuringMachines>>` with all of them,
+    /// ordered descending by their score.
+    pub async fn select_champions(
+        &self,
+        number_of_states: u8,
+        number_of_symbols: u8,
+        limit: u32,
+    ) -> Option<Vec<TuringMachine>> {
+        return self
+            .store
+            .select_champions(number_of_states, number_of_symbols, limit)
+            .await;
+    }
+
+    /// Given a number of states and a number of symbols, selects
+    /// at most `limit` halted turing machines with the most steps.
+    ///
+    /// Returns a `Option<Vec<TuringMachines>>` with all of them,
+    /// ordered descending by their number of steps.
+    pub async fn select_max_steps(
+        &self,
+        number_of_states: u8,
+        number_of_symbols: u8,
+        limit: u32,
+    ) -> Option<Vec<TuringMachine>> {
+        return self
+            .store
+            .select_max_steps(number_of_states, number_of_symbols, limit)
+            .await;
     }
 
     /// Updates the turing machine in the database, if it
     /// actually exists in the database. The check is done
     /// using the `encoding` of the transition function.
     pub async fn update_turing_machine(&self, turing_machine: TuringMachine) {
-        // encode the transition function as a string
-        let transition_function_encoded = turing_machine.transition_function.encode();
-
-        let result: Result<MySqlQueryResult, sqlx::Error> = sqlx::query(
-            "
-            UPDATE turing_machines
-            SET halted = ?,
-            steps = ?,
-            score = ?,
-            time_to_run = ?
-            WHERE transition_function = ?
-        ",
-        )
-        .bind(turing_machine.halted)
-        .bind(turing_machine.steps)
-        .bind(turing_machine.score)
-        .bind(turing_machine.runtime)
-        .bind(transition_function_encoded)
-        .execute(&self.pool)
-        .await;
+        self.store.update(turing_machine).await;
+    }
 
-        match result {
-            Ok(_) => {}
-            Err(error) => {
-                error!("While updating turing machine in the database: {}", error);
+    /// Inserts the given `TuringMachine` into the database.
+    pub async fn insert_turing_machine(&mut self, turing_machine: TuringMachine) {
+        self.store.insert(turing_machine).await;
+    }
+
+    /// Insert the given vector of `TuringMachine`s into
+    /// the `turing machines` table.
+    ///
+    /// A batch insert will be made with all of them. If the
+    /// vector is empty, no query is executed. If the batch insert
+    /// fails, it is retried in smaller batches ( see `store_splitting` ).
+    ///
+    /// Returns the number of Turing machines inserted.
+    pub async fn batch_insert_turing_machines(&mut self, turing_machines: &[TuringMachine]) -> u64 {
+        return self.store_splitting(turing_machines, false).await;
+    }
+
+    /// Inserts the given `TuringMachine` into the database or, if a Turing
+    /// machine with the same encoded transition function already exists,
+    /// updates its metrics, so re-running does not create duplicates.
+    pub async fn insert_or_update_turing_machine(&mut self, turing_machine: TuringMachine) {
+        self.store.insert_or_update(turing_machine).await;
+    }
+
+    /// Inserts or updates the given vector of `TuringMachine`s, with
+    /// a single batch statement. If the vector is empty, no query is executed.
+    /// If the batch statement fails, it is retried in smaller batches
+    /// ( see `store_splitting` ).
+    ///
+    /// Returns the number of Turing machines inserted or updated.
+    pub async fn batch_insert_or_update_turing_machines(
+        &mut self,
+        turing_machines: &[TuringMachine],
+    ) -> u64 {
+        return self.store_splitting(turing_machines, true).await;
+    }
+
+    /// Stores the given vector of `TuringMachine`s with a single batch statement,
+    /// which updates the ones already stored if `update_existing` is set.
+    ///
+    /// A batch that fails, e.g. because the statement has too many placeholders
+    /// for the database, is split in half and each half is retried, down to
+    /// a single Turing machine, so only the ones that cannot be stored are lost.
+    ///
+    /// Returns the number of Turing machines stored.
+    async fn store_splitting(
+        &mut self,
+        turing_machines: &[TuringMachine],
+        update_existing: bool,
+    ) -> u64 {
+        let mut stored: u64 = 0;
+        // the batches left to store, with the next one on top
+        let mut batches: Vec<&[TuringMachine]> = vec![turing_machines];
+
+        while let Some(batch) = batches.pop() {
+            let result = match update_existing {
+                true => self.store.batch_insert_or_update(batch).await,
+                false => self.store.batch_insert(batch).await,
+            };
+
+            match result {
+                Ok(()) => {
+                    stored += batch.len() as u64;
+                }
+                Err(_) if batch.len() > 1 => {
+                    let (first_half, second_half) = batch.split_at(batch.len() / 2);
+
+                    warn!(
+                        "Retrying the batch of {} Turing machines in batches of {} and {}.",
+                        batch.len(),
+                        first_half.len(),
+                        second_half.len()
+                    );
+
+                    batches.push(second_half);
+                    batches.push(first_half);
+                }
+                Err(error) => {
+                    error!(
+                        "Dropping the Turing machine {}, that could not be stored: {}",
+                        batch[0].transition_function.encode(),
+                        error
+                    );
+                }
             }
         }
+
+        return stored;
     }
+}
 
-    /// Inserts the given `TuringMachine` into the database.
-    pub async fn insert_turing_machine(&mut self, turing_machine: TuringMachine) {
-        // get the encoding of the transition function, as a string,
-        // so it is valid for insert in the database
-        let transition_function_encoded = turing_machine.transition_function.encode();
-
-        let result: Result<MySqlQueryResult, sqlx::Error> = sqlx::query("
-            INSERT INTO turing_machines 
-            (transition_function, number_of_states, number_of_symbols, halted, steps, score, time_to_run) 
-            VALUES
-            (?, ?, ?, ?, ?, ?, ?)")
-            .bind(transition_function_encoded)
-            .bind(turing_machine.transition_function.number_of_states)
-            .bind(turing_machine.transition_function.number_of_symbols)
-            .bind(turing_machine.halted)
-            .bind(turing_machine.steps)
-            .bind(turing_machine.score)
-            .bind(turing_machine.runtime)
-            .execute(&self.pool)
-            .await;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::sqlite_store::SqliteStore;
+    use crate::delta::transition_function::TransitionFunction;
+    use crate::generator::generator_transition_function::GeneratorTransitionFunction;
+    use async_trait::async_trait;
+    use std::time::Instant;
+
+    /// Largest batch the `FailingStore` accepts, like a database
+    /// that limits the size of a statement.
+    const MAX_ACCEPTED_BATCH: usize = 500;
+
+    /// `SqliteStore` that fails every batch longer than `MAX_ACCEPTED_BATCH`,
+    /// and every batch that contains the `rejected` transition function.
+    struct FailingStore {
+        store: SqliteStore,
+        rejected: String,
+    }
 
-        match result {
-            Ok(_) => {}
-            Err(error) => {
-                error!("While inserting turing machine in the database: {}", error);
+    impl FailingStore {
+        fn check(&self, turing_machines: &[TuringMachine]) -> Result<(), sqlx::Error> {
+            if turing_machines.len() > MAX_ACCEPTED_BATCH
+                || turing_machines.iter().any(|turing_machine| {
+                    turing_machine.transition_function.encode() == self.rejected
+                })
+            {
+                return Err(sqlx::Error::Protocol("packet too large".to_string()));
             }
+
+            return Ok(());
         }
     }
 
-    /// Using the `pool` of connections, insert the given vector of
-    /// `TuringMachine`s into the `turing machines` table.
-    ///
-    /// A batch insert will be made with all of them.
-    pub async fn batch_insert_turing_machines(&mut self, turing_machines: &[TuringMachine]) {
-        // create and calculate the query statement
-        let mut query_stmt = r#"
-            INSERT INTO turing_machines 
-            (transition_function, number_of_states, number_of_symbols, halted, steps, score, time_to_run) 
-            VALUES
-        "#.to_string();
-
-        for _ in 0..turing_machines.len() - 1 {
-            query_stmt += "(?, ?, ?, ?, ?, ?, ?),";
+    #[async_trait]
+    impl TmStore for FailingStore {
+        async fn insert(&mut self, turing_machine: TuringMachine) {
+            let _ = self.batch_insert(&[turing_machine]).await;
         }
 
-        query_stmt += "(?, ?, ?, ?, ?, ?, ?)";
-
-        // create the query for MySQL
-        let mut query: Query<'_, MySql, MySqlArguments> = sqlx::query(query_stmt.as_str());
-
-        // for each turing machine in the vector,
-        // bind its values to the query
-        for turing_machine in turing_machines {
-            let transition_function_encoded = turing_machine.transition_function.encode();
-
-            // a new query will be created after each
-            // turing machine is added, that will stack them all up
-            query = query
-                .bind(transition_function_encoded)
-                .bind(turing_machine.transition_function.number_of_states)
-                .bind(turing_machine.transition_function.number_of_symbols)
-                .bind(turing_machine.halted)
-                .bind(turing_machine.steps)
-                .bind(turing_machine.score)
-                .bind(turing_machine.runtime);
+        async fn batch_insert(
+            &mut self,
+            turing_machines: &[TuringMachine],
+        ) -> Result<(), sqlx::Error> {
+            self.check(turing_machines)?;
+            return self.store.batch_insert(turing_machines).await;
         }
 
-        let result = query.execute(&self.pool).await;
+        async fn insert_or_update(&mut self, turing_machine: TuringMachine) {
+            let _ = self.batch_insert_or_update(&[turing_machine]).await;
+        }
 
-        match result {
-            Ok(_) => {}
-            Err(error) => {
-                error!("While batch inserting multiple turing machines: {}", error);
-            }
+        async fn batch_insert_or_update(
+            &mut self,
+            turing_machines: &[TuringMachine],
+        ) -> Result<(), sqlx::Error> {
+            self.check(turing_machines)?;
+            return self.store.batch_insert_or_update(turing_machines).await;
+        }
+
+        async fn update(&self, turing_machine: TuringMachine) {
+            self.store.update(turing_machine).await;
+        }
+
+        async fn select_to_run(
+            &mut self,
+            number_of_states: u8,
+            number_of_symbols: u8,
+        ) -> Option<Vec<TuringMachine>> {
+            return self
+                .store
+                .select_to_run(number_of_states, number_of_symbols)
+                .await;
+        }
+
+        async fn select_to_run_paged(
+            &mut self,
+            number_of_states: u8,
+            number_of_symbols: u8,
+            after_id: i32,
+            limit: u32,
+        ) -> Option<Vec<(i32, TuringMachine)>> {
+            return self
+                .store
+                .select_to_run_paged(number_of_states, number_of_symbols, after_id, limit)
+                .await;
+        }
+
+        async fn select_by_delta(
+            &mut self,
+            turing_machine: &TuringMachine,
+        ) -> Result<Option<i32>, sqlx::Error> {
+            return self.store.select_by_delta(turing_machine).await;
+        }
+
+        async fn select_by_id(&self, id: i32) -> Option<TuringMachine> {
+            return self.store.select_by_id(id).await;
+        }
+
+        async fn select_champions(
+            &self,
+            number_of_states: u8,
+            number_of_symbols: u8,
+            limit: u32,
+        ) -> Option<Vec<TuringMachine>> {
+            return self
+                .store
+                .select_champions(number_of_states, number_of_symbols, limit)
+                .await;
         }
+
+        async fn select_max_steps(
+            &self,
+            number_of_states: u8,
+            number_of_symbols: u8,
+            limit: u32,
+        ) -> Option<Vec<TuringMachine>> {
+            return self
+                .store
+                .select_max_steps(number_of_states, number_of_symbols, limit)
+                .await;
+        }
+
+        async fn count(&self, number_of_states: u8, number_of_symbols: u8) -> Option<i64> {
+            return self.store.count(number_of_states, number_of_symbols).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn connect_with_backoff_gives_up() {
+        let base_delay = Duration::from_millis(10);
+        let mut attempts: Vec<Instant> = Vec::new();
+
+        let connection: Option<()> = DatabaseManager::connect_with_backoff(4, base_delay, || {
+            attempts.push(Instant::now());
+            async { Err("database unavailable") }
+        })
+        .await;
+
+        assert!(connection.is_none());
+        assert_eq!(attempts.len(), 4);
+
+        // the delays between the attempts are doubled every time
+        for (attempt, window) in attempts.windows(2).enumerate() {
+            assert!(window[1] - window[0] >= base_delay * (1 << attempt));
+        }
+    }
+
+    #[tokio::test]
+    async fn connect_with_backoff_succeeds() {
+        let mut attempts: u8 = 0;
+
+        let connection = DatabaseManager::connect_with_backoff(3, Duration::ZERO, || {
+            attempts += 1;
+            let attempt = attempts;
+            async move {
+                if attempt < 2 {
+                    return Err("database unavailable");
+                }
+                return Ok(attempt);
+            }
+        })
+        .await;
+
+        assert_eq!(connection, Some(2));
+        assert_eq!(attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn get_store_unsupported_scheme() {
+        let store = DatabaseManager::get_store("oracle://localhost/busy-beaver").await;
+
+        assert!(matches!(store, Err(sqlx::Error::Configuration(_))));
+    }
+
+    #[tokio::test]
+    async fn fetch_turing_machine_by_id() {
+        let store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        let mut database_manager = DatabaseManager::from_store(Box::new(store));
+
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA1RH").unwrap();
+        let mut turing_machine = TuringMachine::new(transition_function);
+        turing_machine.halted = true;
+        turing_machine.steps = 6;
+        turing_machine.score = 4;
+        turing_machine.space = 4;
+
+        database_manager
+            .insert_turing_machine(turing_machine.clone())
+            .await;
+
+        let id = database_manager
+            .select_turing_machine_by_delta(&turing_machine)
+            .await
+            .unwrap()
+            .unwrap();
+        let fetched = database_manager
+            .fetch_turing_machine_by_id(id)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&fetched).unwrap(),
+            serde_json::to_value(&turing_machine).unwrap()
+        );
+        assert!(database_manager
+            .fetch_turing_machine_by_id(id + 1)
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn get_store_sqlite() {
+        let store = DatabaseManager::get_store("sqlite::memory:").await;
+
+        assert!(store.is_ok());
+    }
+
+    #[tokio::test]
+    async fn batch_insert_splits_failed_batch() {
+        let store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        let turing_machines: Vec<TuringMachine> = GeneratorTransitionFunction::iter(3, 2)
+            .take(1200)
+            .map(TuringMachine::new)
+            .collect();
+
+        let mut database_manager = DatabaseManager::from_store(Box::new(FailingStore {
+            store: store.clone(),
+            rejected: String::new(),
+        }));
+
+        // the batch of 1200 and its halves of 600 fail,
+        // while the batches of 300 are all inserted
+        assert_eq!(
+            database_manager
+                .batch_insert_turing_machines(&turing_machines)
+                .await,
+            1200
+        );
+        assert_eq!(store.count(3, 2).await, Some(1200));
+
+        // only the Turing machine that cannot be stored is lost
+        let store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        let mut database_manager = DatabaseManager::from_store(Box::new(FailingStore {
+            store: store.clone(),
+            rejected: turing_machines[700].transition_function.encode(),
+        }));
+
+        assert_eq!(
+            database_manager
+                .batch_insert_or_update_turing_machines(&turing_machines)
+                .await,
+            1199
+        );
+        assert_eq!(store.count(3, 2).await, Some(1199));
+        assert_eq!(
+            database_manager
+                .select_turing_machine_by_delta(&turing_machines[700])
+                .await
+                .unwrap(),
+            None
+        );
     }
 }
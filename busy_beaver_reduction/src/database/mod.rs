@@ -1,2 +1,8 @@
+pub mod dialect;
 pub mod manager;
+pub mod mysql_store;
+pub mod postgres_store;
 pub mod runner;
+pub mod sql_store;
+pub mod sqlite_store;
+pub mod store;
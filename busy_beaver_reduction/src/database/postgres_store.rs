@@ -0,0 +1,38 @@
+use sqlx::postgres::{PgPoolOptions, Postgres};
+
+use crate::database::dialect::Dialect;
+use crate::database::sql_store::{SqlStore, StoreDatabase};
+use crate::database::store::MAX_POOL_CONNECTIONS;
+
+/// `TmStore` backed by a Postgres database.
+pub type PostgresStore = SqlStore<Postgres>;
+
+/// Postgres has no unsigned integer types, so the number of
+/// states and symbols are stored as `smallint`, while the
+/// steps and the score are stored as `bigint`.
+impl StoreDatabase for Postgres {
+    const DIALECT: Dialect = Dialect::Postgres;
+
+    type SmallInt = i16;
+    type BigInt = i64;
+
+    fn small_int(value: u8) -> i16 {
+        return value as i16;
+    }
+
+    fn big_int(value: u64) -> i64 {
+        return value as i64;
+    }
+}
+
+impl PostgresStore {
+    /// Gets the `pool` of connections using the `connection_string`.
+    pub async fn connect(connection_string: &str) -> Result<Self, sqlx::Error> {
+        let pool = PgPoolOptions::new()
+            .max_connections(MAX_POOL_CONNECTIONS)
+            .connect(connection_string)
+            .await?;
+
+        Ok(PostgresStore::with_pool(pool))
+    }
+}
@@ -1,17 +1,62 @@
+use log::info;
 use tokio::sync::mpsc::Receiver;
 
 use super::manager::DatabaseManager;
 use crate::turing_machine::turing_machine::TuringMachine;
 
+/// Default number of Turing machines inserted with a single statement.
+///
+/// Bigger batches need fewer round trips to the database, but build
+/// bigger statements, which are limited by the number of placeholders
+/// the database accepts, and keep more Turing machines in memory.
 const BATCH_SIZE: usize = 1000;
 
 pub struct DatabaseManagerRunner {
     rx_turing_machines: Receiver<TuringMachine>,
+    halting_only: bool,
+    batch_size: usize,
+    database: Option<DatabaseManager>,
+    pub non_halting_skipped: u64,
+    pub persisted: u64,
 }
 
 impl DatabaseManagerRunner {
     pub fn new(rx_turing_machines: Receiver<TuringMachine>) -> Self {
-        DatabaseManagerRunner { rx_turing_machines }
+        DatabaseManagerRunner {
+            rx_turing_machines,
+            halting_only: false,
+            batch_size: BATCH_SIZE,
+            database: None,
+            non_halting_skipped: 0,
+            persisted: 0,
+        }
+    }
+
+    /// Sets whether only the Turing machines that halted are inserted;
+    /// the ones that did not halt are only counted.
+    pub fn set_halting_only(&mut self, halting_only: bool) {
+        self.halting_only = halting_only;
+    }
+
+    /// Sets the number of Turing machines inserted with a single
+    /// statement, instead of `BATCH_SIZE`.
+    pub fn set_batch_size(&mut self, batch_size: usize) {
+        self.batch_size = batch_size.max(1);
+    }
+
+    /// Sets the `DatabaseManager` the Turing machines are stored with,
+    /// instead of connecting to the one configured by the `DATABASE_URL`.
+    pub fn set_database(&mut self, database: DatabaseManager) {
+        self.database = Some(database);
+    }
+
+    /// Gets the `DatabaseManager` that was set, or connects
+    /// to the one configured by the `DATABASE_URL`.
+    async fn get_database(&mut self) -> Option<DatabaseManager> {
+        match self.database.take() {
+            Some(database) => return Some(database),
+            None => return DatabaseManager::new().await,
+        }
     }
 
     /// Listens to the communication channel, which has the TuringMachineRunner
@@ -20,7 +65,7 @@ impl DatabaseManagerRunner {
     ///
     /// Update statements are made individual from the others.
     pub async fn receive_and_update_turing_machines(&mut self) {
-        let database = match DatabaseManager::new().await {
+        let database = match self.get_database().await {
             Some(database) => database,
             None => return,
         };
@@ -29,39 +74,165 @@ impl DatabaseManagerRunner {
         // and then update its entry in the database
         while let Some(turing_machine) = self.rx_turing_machines.recv().await {
             database.update_turing_machine(turing_machine).await;
+            self.persisted += 1;
         }
+
+        info!(
+            "Updated {} Turing machines in the database.",
+            self.persisted
+        );
     }
 
     /// Listens to the communication channel, which has the TuringMachineRunner
     /// on the other side, and for each turing machine received, add it to a
     /// vector of Turing machines.
     ///
-    /// Once the desired batch size is reached, bulks insert them in the database.
+    /// Once the desired batch size is reached, bulks insert them in the database;
+    /// the Turing machines that were already stored get their metrics updated.
     pub async fn receive_and_insert_turing_machines(&mut self) {
-        let mut database = match DatabaseManager::new().await {
+        let mut database = match self.get_database().await {
             Some(database) => database,
             None => return,
         };
+
+        self.insert_turing_machines(&mut database).await;
+    }
+
+    /// Inserts every turing machine received in the given database, in batches.
+    ///
+    /// If only the halting Turing machines are inserted,
+    /// the other ones are counted and dropped.
+    async fn insert_turing_machines(&mut self, database: &mut DatabaseManager) {
         let mut turing_machines: Vec<TuringMachine> = Vec::new();
 
         // wait for every turing machine executed to come
         // and then update its entry in the database
         while let Some(turing_machine) = self.rx_turing_machines.recv().await {
+            if self.halting_only == true && turing_machine.halted == false {
+                self.non_halting_skipped += 1;
+                continue;
+            }
+
             turing_machines.push(turing_machine);
 
-            if turing_machines.len() == BATCH_SIZE {
-                database
-                    .batch_insert_turing_machines(&turing_machines[..])
+            if turing_machines.len() == self.batch_size {
+                self.persisted += database
+                    .batch_insert_or_update_turing_machines(&turing_machines[..])
                     .await;
                 turing_machines = Vec::new();
             }
         }
 
-        // insert the remaining Turing machines
+        // insert the remaining Turing machines, including
+        // the incomplete batch left when the run was stopped
         if turing_machines.len() != 0 {
-            database
-                .batch_insert_turing_machines(&turing_machines[..])
+            self.persisted += database
+                .batch_insert_or_update_turing_machines(&turing_machines[..])
                 .await;
         }
+
+        info!(
+            "Inserted {} Turing machines in the database.",
+            self.persisted
+        );
+
+        if self.halting_only == true {
+            info!(
+                "Skipped inserting {} Turing machines that did not halt.",
+                self.non_halting_skipped
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::sqlite_store::SqliteStore;
+    use crate::delta::transition_function::TransitionFunction;
+
+    #[tokio::test]
+    async fn insert_halting_only() {
+        let (tx_turing_machines, rx_turing_machines) = tokio::sync::mpsc::channel(10);
+        let store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        let mut database = DatabaseManager::from_store(Box::new(store));
+
+        // 10 different Turing machines, out of which the first 3 halted
+        for index in 0..10 {
+            let mut transition_function = TransitionFunction::new(2, 2);
+            transition_function
+                .decode(format!(
+                    "0,0,1,{},{}|0,1,1,{},{}|1,0,0,1,0|1,1,255,1,1",
+                    index & 1,
+                    (index >> 1) & 1,
+                    (index >> 2) & 1,
+                    (index >> 3) & 1
+                ))
+                .unwrap();
+
+            let mut turing_machine = TuringMachine::new(transition_function);
+            turing_machine.halted = index < 3;
+            tx_turing_machines.send(turing_machine).await.unwrap();
+        }
+        drop(tx_turing_machines);
+
+        let mut database_manager_runner = DatabaseManagerRunner::new(rx_turing_machines);
+        database_manager_runner.set_halting_only(true);
+        database_manager_runner
+            .insert_turing_machines(&mut database)
+            .await;
+
+        assert_eq!(database_manager_runner.non_halting_skipped, 7);
+        assert_eq!(database.select_champions(2, 2, 10).await.unwrap().len(), 3);
+        assert_eq!(
+            database
+                .select_turing_machines_to_run(2, 2)
+                .await
+                .unwrap()
+                .len(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn insert_custom_batch_size() {
+        let (tx_turing_machines, rx_turing_machines) = tokio::sync::mpsc::channel(10);
+        let store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        let mut database = DatabaseManager::from_store(Box::new(store));
+
+        // 7 different Turing machines, inserted in batches of 3
+        for index in 0..7 {
+            let mut transition_function = TransitionFunction::new(2, 2);
+            transition_function
+                .decode(format!(
+                    "0,0,1,{},{}|0,1,1,{},1|1,0,0,1,0|1,1,255,1,1",
+                    index & 1,
+                    (index >> 1) & 1,
+                    (index >> 2) & 1
+                ))
+                .unwrap();
+
+            tx_turing_machines
+                .send(TuringMachine::new(transition_function))
+                .await
+                .unwrap();
+        }
+        drop(tx_turing_machines);
+
+        let mut database_manager_runner = DatabaseManagerRunner::new(rx_turing_machines);
+        database_manager_runner.set_batch_size(3);
+        database_manager_runner
+            .insert_turing_machines(&mut database)
+            .await;
+
+        // the last, incomplete, batch is inserted as well
+        assert_eq!(
+            database
+                .select_turing_machines_to_run(2, 2)
+                .await
+                .unwrap()
+                .len(),
+            7
+        );
     }
 }
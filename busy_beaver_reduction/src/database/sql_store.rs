@@ -0,0 +1,501 @@
+use async_trait::async_trait;
+use log::error;
+use sqlx::database::HasArguments;
+use sqlx::query::Query;
+use sqlx::{
+    ColumnIndex, Database, Decode, Encode, Executor, FromRow, IntoArguments, Pool, Row, Type,
+};
+
+use crate::database::dialect::Dialect;
+use crate::database::store::TmStore;
+use crate::delta::transition_function::TransitionFunction;
+use crate::filter::filter_runtime::FilterRuntimeType;
+use crate::turing_machine::turing_machine::TuringMachine;
+
+/// Database the Turing machines can be stored in by a `SqlStore`.
+///
+/// The `turing_machines` table has the same columns in all of them,
+/// but not all of them have unsigned integer types, so each one
+/// chooses the types its small and big integer columns are read as.
+pub trait StoreDatabase: Database {
+    /// Dialect of the queries executed against the database.
+    const DIALECT: Dialect;
+
+    /// Type of the columns storing a number of states or symbols, or a filter type.
+    type SmallInt: for<'q> Encode<'q, Self>
+        + for<'r> Decode<'r, Self>
+        + Type<Self>
+        + TryInto<u8>
+        + Send
+        + 'static;
+
+    /// Type of the columns storing a number of steps or a score.
+    type BigInt: for<'q> Encode<'q, Self>
+        + for<'r> Decode<'r, Self>
+        + Type<Self>
+        + TryInto<u64>
+        + Send
+        + 'static;
+
+    /// Converts a number of states or symbols, or a filter type,
+    /// into the type of the column it is stored in.
+    fn small_int(value: u8) -> Self::SmallInt;
+
+    /// Converts a number of steps or a score into
+    /// the type of the column it is stored in.
+    fn big_int(value: u64) -> Self::BigInt;
+}
+
+/// `TmStore` backed by a SQL database, the same
+/// for all the databases implementing `StoreDatabase`.
+///
+/// The clones share the same pool, and so the same database.
+pub struct SqlStore<DB: Database> {
+    pub(crate) pool: Pool<DB>,
+}
+
+impl<DB: Database> Clone for SqlStore<DB> {
+    fn clone(&self) -> Self {
+        return SqlStore {
+            pool: self.pool.clone(),
+        };
+    }
+}
+
+impl<DB> SqlStore<DB>
+where
+    DB: StoreDatabase,
+    for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    for<'a> &'a str: ColumnIndex<DB::Row>,
+    (i64,): for<'r> FromRow<'r, DB::Row>,
+    Option<DB::SmallInt>: for<'q> Encode<'q, DB>,
+    Option<DB::BigInt>: for<'q> Encode<'q, DB>,
+    String: for<'q> Encode<'q, DB> + for<'r> Decode<'r, DB> + Type<DB>,
+    bool: for<'q> Encode<'q, DB> + for<'r> Decode<'r, DB> + Type<DB>,
+    i32: for<'q> Encode<'q, DB> + for<'r> Decode<'r, DB> + Type<DB>,
+    i64: for<'q> Encode<'q, DB> + for<'r> Decode<'r, DB> + Type<DB>,
+{
+    /// Creates the store over an already configured `pool` of connections.
+    pub(crate) fn with_pool(pool: Pool<DB>) -> Self {
+        return SqlStore { pool: pool };
+    }
+
+    /// Reads the small integer stored in the `column` of the `row`.
+    fn get_small_int(row: &DB::Row, column: &str) -> Result<Option<u8>, sqlx::Error> {
+        let value: Option<DB::SmallInt> = row.try_get(column)?;
+
+        return value
+            .map(|value| {
+                value.try_into().map_err(|_| sqlx::Error::ColumnDecode {
+                    index: column.to_string(),
+                    source: "the value is out of range".into(),
+                })
+            })
+            .transpose();
+    }
+
+    /// Reads the big integer stored in the `column` of the `row`.
+    fn get_big_int(row: &DB::Row, column: &str) -> Result<Option<u64>, sqlx::Error> {
+        let value: Option<DB::BigInt> = row.try_get(column)?;
+
+        return value
+            .map(|value| {
+                value.try_into().map_err(|_| sqlx::Error::ColumnDecode {
+                    index: column.to_string(),
+                    source: "the value is out of range".into(),
+                })
+            })
+            .transpose();
+    }
+
+    /// Given a row, that should contain an entry from the `turing_machines`
+    /// table, transform it into a TuringMachine object, reading the columns by
+    /// their names.
+    ///
+    /// Returns the error of the column that could not be read,
+    /// or of the transition function that could not be decoded.
+    fn get_turing_machine(row: &DB::Row) -> Result<TuringMachine, sqlx::Error> {
+        let missing = |column: &str| sqlx::Error::ColumnDecode {
+            index: column.to_string(),
+            source: "the value is NULL".into(),
+        };
+
+        // reconstruct the transition function
+        let transition_function_encoded: String = row.try_get("transition_function")?;
+        let number_of_states = Self::get_small_int(row, "number_of_states")?
+            .ok_or_else(|| missing("number_of_states"))?;
+        let number_of_symbols = Self::get_small_int(row, "number_of_symbols")?
+            .ok_or_else(|| missing("number_of_symbols"))?;
+
+        let mut transition_function = TransitionFunction::new(number_of_states, number_of_symbols);
+
+        // decode the transition function
+        transition_function
+            .decode(transition_function_encoded)
+            .map_err(|error| sqlx::Error::Decode(Box::new(error)))?;
+
+        // reconstruct the turing machine
+        let mut turing_machine = TuringMachine::new(transition_function);
+        turing_machine.halted = row.try_get("halted")?;
+        turing_machine.steps = Self::get_big_int(row, "steps")?.ok_or_else(|| missing("steps"))?;
+        turing_machine.score = Self::get_big_int(row, "score")?.ok_or_else(|| missing("score"))?;
+        turing_machine.runtime = row.try_get("time_to_run")?;
+        turing_machine.space = row.try_get("space")?;
+        turing_machine.filtered_at_step = Self::get_big_int(row, "filtered_at_step")?;
+        turing_machine.filtered =
+            FilterRuntimeType::transform(Self::get_small_int(row, "filter_type")?);
+
+        return Ok(turing_machine);
+    }
+
+    /// Given a row, that should contain an entry from the `turing_machines`
+    /// table, transform it into a TuringMachine object, together with its `id`.
+    ///
+    /// Returns `None` if the row could not be read, in which
+    /// case the error is logged and the row should be skipped.
+    fn row_to_turing_machine(row: &DB::Row) -> Option<(i32, TuringMachine)> {
+        let result = row.try_get("id").and_then(|id| {
+            Self::get_turing_machine(row).map(|turing_machine| (id, turing_machine))
+        });
+
+        match result {
+            Ok(turing_machine) => {
+                return Some(turing_machine);
+            }
+            Err(error) => {
+                error!(
+                    "While reading a turing machine from database, skipping it: {}",
+                    error
+                );
+                return None;
+            }
+        }
+    }
+
+    /// Selects at most `limit` halted turing machines with the given number
+    /// of states and number of symbols, ordered descending by `order_column`.
+    async fn select_top_halted(
+        &self,
+        order_column: &str,
+        number_of_states: u8,
+        number_of_symbols: u8,
+        limit: u32,
+    ) -> Option<Vec<TuringMachine>> {
+        let result: Result<Vec<DB::Row>, sqlx::Error> =
+            sqlx::query(&DB::DIALECT.select_top_halted_query(order_column))
+                .bind(DB::small_int(number_of_states))
+                .bind(DB::small_int(number_of_symbols))
+                .bind(limit as i64)
+                .fetch_all(&self.pool)
+                .await;
+
+        match result {
+            Ok(rows) => {
+                return Some(
+                    rows.iter()
+                        .filter_map(|row| Self::row_to_turing_machine(row))
+                        .map(|(_, turing_machine)| turing_machine)
+                        .collect(),
+                );
+            }
+            Err(error) => {
+                error!(
+                    "While selecting the top turing machines by {} from database: {}",
+                    order_column, error
+                );
+                return None;
+            }
+        }
+    }
+
+    /// Executes the given insert statement, binding the values of
+    /// all the `turing_machines` to it. If the vector is empty,
+    /// no query is executed.
+    async fn execute_insert(
+        &self,
+        query_stmt: String,
+        turing_machines: &[TuringMachine],
+    ) -> Result<(), sqlx::Error> {
+        // there is nothing to insert, and an empty
+        // VALUES list is not a valid statement
+        if turing_machines.is_empty() {
+            return Ok(());
+        }
+
+        let mut query: Query<'_, DB, <DB as HasArguments<'_>>::Arguments> =
+            sqlx::query(query_stmt.as_str());
+
+        // for each turing machine in the vector,
+        // bind its values to the query
+        for turing_machine in turing_machines {
+            let transition_function_encoded = turing_machine.transition_function.encode();
+
+            // a new query will be created after each
+            // turing machine is added, that will stack them all up
+            query = query
+                .bind(transition_function_encoded)
+                .bind(DB::small_int(
+                    turing_machine.transition_function.number_of_states,
+                ))
+                .bind(DB::small_int(
+                    turing_machine.transition_function.number_of_symbols,
+                ))
+                .bind(turing_machine.halted)
+                .bind(DB::big_int(turing_machine.steps))
+                .bind(DB::big_int(turing_machine.score))
+                .bind(turing_machine.runtime)
+                .bind(turing_machine.space)
+                .bind(turing_machine.filtered_at_step.map(DB::big_int))
+                .bind(turing_machine.filtered.value().map(DB::small_int))
+                .bind(DB::small_int(
+                    turing_machine.transition_function.states_used(),
+                ))
+                .bind(DB::small_int(
+                    turing_machine.transition_function.input_alphabet_size(),
+                ));
+        }
+
+        let result = query.execute(&self.pool).await;
+
+        match result {
+            Ok(_) => {
+                return Ok(());
+            }
+            Err(error) => {
+                error!("While inserting turing machines in the database: {}", error);
+                return Err(error);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<DB> TmStore for SqlStore<DB>
+where
+    DB: StoreDatabase,
+    for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    for<'a> &'a str: ColumnIndex<DB::Row>,
+    (i64,): for<'r> FromRow<'r, DB::Row>,
+    Option<DB::SmallInt>: for<'q> Encode<'q, DB>,
+    Option<DB::BigInt>: for<'q> Encode<'q, DB>,
+    String: for<'q> Encode<'q, DB> + for<'r> Decode<'r, DB> + Type<DB>,
+    bool: for<'q> Encode<'q, DB> + for<'r> Decode<'r, DB> + Type<DB>,
+    i32: for<'q> Encode<'q, DB> + for<'r> Decode<'r, DB> + Type<DB>,
+    i64: for<'q> Encode<'q, DB> + for<'r> Decode<'r, DB> + Type<DB>,
+{
+    async fn select_to_run(
+        &mut self,
+        number_of_states: u8,
+        number_of_symbols: u8,
+    ) -> Option<Vec<TuringMachine>> {
+        let result: Result<Vec<DB::Row>, sqlx::Error> =
+            sqlx::query(&DB::DIALECT.select_to_run_query())
+                .bind(DB::small_int(number_of_states))
+                .bind(DB::small_int(number_of_symbols))
+                .fetch_all(&self.pool)
+                .await;
+
+        match result {
+            Ok(rows) => {
+                let mut turing_machines = Vec::<TuringMachine>::new();
+
+                for row in rows.iter() {
+                    // reconstruct the turing machine from
+                    // the row, skipping the rows that can not be read
+                    if let Some((_, turing_machine)) = Self::row_to_turing_machine(row) {
+                        turing_machines.push(turing_machine);
+                    }
+                }
+
+                return Some(turing_machines);
+            }
+            Err(error) => {
+                error!(
+                    "While selecting all turing machines from database: {}",
+                    error
+                );
+                return None;
+            }
+        }
+    }
+
+    async fn select_to_run_paged(
+        &mut self,
+        number_of_states: u8,
+        number_of_symbols: u8,
+        after_id: i32,
+        limit: u32,
+    ) -> Option<Vec<(i32, TuringMachine)>> {
+        let result: Result<Vec<DB::Row>, sqlx::Error> =
+            sqlx::query(&DB::DIALECT.select_to_run_paged_query())
+                .bind(DB::small_int(number_of_states))
+                .bind(DB::small_int(number_of_symbols))
+                .bind(after_id)
+                .bind(limit as i64)
+                .fetch_all(&self.pool)
+                .await;
+
+        match result {
+            Ok(rows) => {
+                let mut turing_machines = Vec::<(i32, TuringMachine)>::new();
+
+                for row in rows.iter() {
+                    // reconstruct the turing machine from
+                    // the row, skipping the rows that can not be read
+                    if let Some(turing_machine) = Self::row_to_turing_machine(row) {
+                        turing_machines.push(turing_machine);
+                    }
+                }
+
+                return Some(turing_machines);
+            }
+            Err(error) => {
+                error!(
+                    "While selecting a page of turing machines from database: {}",
+                    error
+                );
+                return None;
+            }
+        }
+    }
+
+    async fn select_by_delta(
+        &mut self,
+        turing_machine: &TuringMachine,
+    ) -> Result<Option<i32>, sqlx::Error> {
+        let transition_function_encoded = turing_machine.transition_function.encode();
+
+        let result: Result<Option<i32>, sqlx::Error> =
+            sqlx::query(&DB::DIALECT.select_by_delta_query())
+                .bind(transition_function_encoded)
+                .fetch_optional(&self.pool)
+                .await
+                .and_then(|row| row.map(|row| row.try_get("id")).transpose());
+
+        match result {
+            Ok(id) => {
+                return Ok(id);
+            }
+            Err(error) => {
+                error!(
+                    "While selecting a turing machine from database, by the transition function: {}",
+                    error
+                );
+                return Err(error);
+            }
+        }
+    }
+
+    async fn select_by_id(&self, id: i32) -> Option<TuringMachine> {
+        let result: Result<Option<DB::Row>, sqlx::Error> =
+            sqlx::query(&DB::DIALECT.select_by_id_query())
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await;
+
+        match result {
+            Ok(row) => {
+                return row
+                    .and_then(|row| Self::row_to_turing_machine(&row))
+                    .map(|(_, turing_machine)| turing_machine);
+            }
+            Err(error) => {
+                error!(
+                    "While selecting a turing machine from database, by its id: {}",
+                    error
+                );
+                return None;
+            }
+        }
+    }
+
+    async fn select_champions(
+        &self,
+        number_of_states: u8,
+        number_of_symbols: u8,
+        limit: u32,
+    ) -> Option<Vec<TuringMachine>> {
+        return self
+            .select_top_halted("score", number_of_states, number_of_symbols, limit)
+            .await;
+    }
+
+    async fn select_max_steps(
+        &self,
+        number_of_states: u8,
+        number_of_symbols: u8,
+        limit: u32,
+    ) -> Option<Vec<TuringMachine>> {
+        return self
+            .select_top_halted("steps", number_of_states, number_of_symbols, limit)
+            .await;
+    }
+
+    async fn count(&self, number_of_states: u8, number_of_symbols: u8) -> Option<i64> {
+        let result: Result<i64, sqlx::Error> = sqlx::query_scalar(&DB::DIALECT.count_query())
+            .bind(DB::small_int(number_of_states))
+            .bind(DB::small_int(number_of_symbols))
+            .fetch_one(&self.pool)
+            .await;
+
+        match result {
+            Ok(count) => {
+                return Some(count);
+            }
+            Err(error) => {
+                error!(
+                    "While counting the turing machines from database: {}",
+                    error
+                );
+                return None;
+            }
+        }
+    }
+
+    async fn update(&self, turing_machine: TuringMachine) {
+        // encode the transition function as a string
+        let transition_function_encoded = turing_machine.transition_function.encode();
+
+        let result = sqlx::query(&DB::DIALECT.update_query())
+            .bind(turing_machine.halted)
+            .bind(DB::big_int(turing_machine.steps))
+            .bind(DB::big_int(turing_machine.score))
+            .bind(turing_machine.runtime)
+            .bind(turing_machine.space)
+            .bind(turing_machine.filtered_at_step.map(DB::big_int))
+            .bind(turing_machine.filtered.value().map(DB::small_int))
+            .bind(transition_function_encoded)
+            .execute(&self.pool)
+            .await;
+
+        match result {
+            Ok(_) => {}
+            Err(error) => {
+                error!("While updating turing machine in the database: {}", error);
+            }
+        }
+    }
+
+    async fn insert(&mut self, turing_machine: TuringMachine) {
+        let _ = self.batch_insert(&[turing_machine]).await;
+    }
+
+    async fn batch_insert(&mut self, turing_machines: &[TuringMachine]) -> Result<(), sqlx::Error> {
+        let query_stmt = DB::DIALECT.insert_query(turing_machines.len());
+        return self.execute_insert(query_stmt, turing_machines).await;
+    }
+
+    async fn insert_or_update(&mut self, turing_machine: TuringMachine) {
+        let _ = self.batch_insert_or_update(&[turing_machine]).await;
+    }
+
+    async fn batch_insert_or_update(
+        &mut self,
+        turing_machines: &[TuringMachine],
+    ) -> Result<(), sqlx::Error> {
+        let query_stmt = DB::DIALECT.upsert_query(turing_machines.len());
+        return self.execute_insert(query_stmt, turing_machines).await;
+    }
+}
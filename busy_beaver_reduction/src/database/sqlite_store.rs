@@ -0,0 +1,409 @@
+use std::str::FromStr;
+
+use sqlx::sqlite::{Sqlite, SqliteConnectOptions, SqlitePoolOptions};
+
+use crate::database::dialect::Dialect;
+use crate::database::sql_store::{SqlStore, StoreDatabase};
+
+/// SQLite equivalent of `database/schema.sql`, created
+/// when connecting, since there is no server to initialize it.
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS turing_machines (
+        id integer PRIMARY KEY AUTOINCREMENT,
+        transition_function text NOT NULL UNIQUE,
+        number_of_states integer NOT NULL,
+        number_of_symbols integer NOT NULL,
+        halted boolean NOT NULL,
+        steps integer NOT NULL,
+        score integer NOT NULL,
+        time_to_run integer NOT NULL,
+        space integer NOT NULL,
+        filtered_at_step integer NULL,
+        filter_type integer NULL,
+        states_used integer NOT NULL,
+        input_alphabet_size integer NOT NULL
+    )";
+
+/// `TmStore` backed by an embedded SQLite database,
+/// stored in a file or in memory.
+pub type SqliteStore = SqlStore<Sqlite>;
+
+/// SQLite has no unsigned 64-bit integer type, so the
+/// steps and the score are stored as `integer`.
+impl StoreDatabase for Sqlite {
+    const DIALECT: Dialect = Dialect::Sqlite;
+
+    type SmallInt = i16;
+    type BigInt = i64;
+
+    fn small_int(value: u8) -> i16 {
+        return value as i16;
+    }
+
+    fn big_int(value: u64) -> i64 {
+        return value as i64;
+    }
+}
+
+impl SqliteStore {
+    /// Gets the `pool` of connections using the `connection_string`,
+    /// creating the database file and the `turing_machines` table if
+    /// they do not exist.
+    ///
+    /// EXAMPLE: "sqlite://busy-beaver.db" or "sqlite::memory:"
+    pub async fn connect(connection_string: &str) -> Result<Self, sqlx::Error> {
+        let options = SqliteConnectOptions::from_str(connection_string)?.create_if_missing(true);
+
+        // SQLite allows a single writer at a time, and every connection
+        // to an in-memory database would open a different database,
+        // so a single connection that is never closed is used
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await?;
+
+        sqlx::query(SCHEMA).execute(&pool).await?;
+
+        Ok(SqliteStore::with_pool(pool))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::store::TmStore;
+    use crate::delta::transition_function::TransitionFunction;
+    use crate::filter::filter_runtime::FilterRuntimeType;
+    use crate::turing_machine::turing_machine::TuringMachine;
+    use log::{Level, LevelFilter, Log, Metadata, Record};
+    use std::sync::Mutex;
+    use std::thread::{self, ThreadId};
+
+    /// Levels of the records logged while running the tests, together
+    /// with the thread that logged them, since the tests run in parallel.
+    static LOGGED_LEVELS: Mutex<Vec<(ThreadId, Level)>> = Mutex::new(Vec::new());
+
+    struct CapturingLogger;
+
+    impl Log for CapturingLogger {
+        fn enabled(&self, _metadata: &Metadata) -> bool {
+            return true;
+        }
+
+        fn log(&self, record: &Record) {
+            LOGGED_LEVELS
+                .lock()
+                .unwrap()
+                .push((thread::current().id(), record.level()));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CapturingLogger = CapturingLogger;
+
+    /// Installs the `CapturingLogger`, if it was not already installed.
+    fn capture_logs() {
+        if log::set_logger(&LOGGER).is_ok() {
+            log::set_max_level(LevelFilter::Trace);
+        }
+    }
+
+    /// Gets the levels of the records logged by the current thread.
+    fn get_logged_levels() -> Vec<Level> {
+        return LOGGED_LEVELS
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(thread_id, _)| *thread_id == thread::current().id())
+            .map(|(_, level)| *level)
+            .collect();
+    }
+
+    const TRANSITION_FUNCTIONS: [&str; 5] = [
+        "0,0,1,1,1|0,1,1,1,0|1,0,0,1,0|1,1,255,1,1",
+        "0,0,1,1,0|0,1,1,1,0|1,0,0,1,0|1,1,255,1,1",
+        "0,0,1,0,1|0,1,1,1,0|1,0,0,1,0|1,1,255,1,1",
+        "0,0,1,0,0|0,1,1,1,0|1,0,0,1,0|1,1,255,1,1",
+        "0,0,1,0,0|0,1,1,0,0|1,0,0,1,0|1,1,255,1,1",
+    ];
+
+    fn get_turing_machine(transition_function_encoded: &str) -> TuringMachine {
+        let mut transition_function: TransitionFunction = TransitionFunction::new(2, 2);
+        transition_function
+            .decode(transition_function_encoded.to_string())
+            .unwrap();
+
+        let mut turing_machine = TuringMachine::new(transition_function);
+        turing_machine.steps = 3;
+        turing_machine.score = 2;
+        turing_machine.space = 2;
+        turing_machine.filtered_at_step = Some(3);
+
+        return turing_machine;
+    }
+
+    #[tokio::test]
+    async fn count() {
+        let mut store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        assert_eq!(store.count(2, 2).await, Some(0));
+
+        let mut turing_machines: Vec<TuringMachine> = TRANSITION_FUNCTIONS
+            .iter()
+            .map(|encoded| get_turing_machine(encoded))
+            .collect();
+        // halted Turing machines are counted as well
+        turing_machines[0].halted = true;
+        store.batch_insert(&turing_machines).await.unwrap();
+
+        assert_eq!(
+            store.count(2, 2).await,
+            Some(TRANSITION_FUNCTIONS.len() as i64)
+        );
+        assert_eq!(store.count(3, 2).await, Some(0));
+    }
+
+    #[tokio::test]
+    async fn insert_and_select() {
+        let mut store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        let turing_machine = get_turing_machine(TRANSITION_FUNCTIONS[0]);
+
+        store.insert(turing_machine.clone()).await;
+
+        let turing_machines = store.select_to_run(2, 2).await.unwrap();
+
+        assert_eq!(turing_machines.len(), 1);
+        assert_eq!(
+            turing_machines[0].transition_function,
+            turing_machine.transition_function
+        );
+        assert_eq!(turing_machines[0].halted, false);
+        assert_eq!(turing_machines[0].steps, 3);
+        assert_eq!(turing_machines[0].score, 2);
+        assert_eq!(turing_machines[0].space, 2);
+        assert_eq!(turing_machines[0].filtered_at_step, Some(3));
+        assert_eq!(
+            store.select_by_delta(&turing_machine).await.unwrap(),
+            Some(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn select_to_run_skips_unreadable_rows() {
+        let mut store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        store
+            .insert(get_turing_machine(TRANSITION_FUNCTIONS[0]))
+            .await;
+
+        // a transition function that can not be decoded, and a
+        // number of steps that does not fit into the turing machine
+        sqlx::query(
+            "INSERT INTO turing_machines (transition_function, number_of_states, number_of_symbols, halted, steps, score, time_to_run, space, states_used, input_alphabet_size) \
+            VALUES ('0,0,1', 2, 2, FALSE, 3, 2, 0, 2, 2, 2), (?, 2, 2, FALSE, -1, 2, 0, 2, 2, 2)",
+        )
+        .bind(TRANSITION_FUNCTIONS[1])
+        .execute(&store.pool)
+        .await
+        .unwrap();
+
+        let turing_machines = store.select_to_run(2, 2).await.unwrap();
+
+        assert_eq!(turing_machines.len(), 1);
+        assert_eq!(
+            turing_machines[0].transition_function,
+            get_turing_machine(TRANSITION_FUNCTIONS[0]).transition_function
+        );
+        assert_eq!(
+            store.select_to_run_paged(2, 2, 0, 10).await.unwrap().len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn select_by_delta_not_found() {
+        capture_logs();
+        let mut store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        let turing_machine = get_turing_machine(TRANSITION_FUNCTIONS[0]);
+
+        store
+            .insert(get_turing_machine(TRANSITION_FUNCTIONS[1]))
+            .await;
+
+        // a turing machine that is not stored is not an error
+        assert!(matches!(
+            store.select_by_delta(&turing_machine).await,
+            Ok(None)
+        ));
+        // the tokio tests run on the current thread, so the
+        // records logged by the store are logged by this thread
+        log::trace!("select_by_delta_not_found finished");
+        let logged_levels = get_logged_levels();
+        assert!(logged_levels.contains(&Level::Trace));
+        assert!(logged_levels.contains(&Level::Error) == false);
+    }
+
+    #[tokio::test]
+    async fn select_champions_and_max_steps() {
+        let mut store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        let mut turing_machines = Vec::new();
+
+        // (halted, steps, score)
+        let metrics = [(true, 4, 3), (true, 6, 4), (false, 100, 10), (true, 5, 2)];
+
+        for (transition_function, (halted, steps, score)) in
+            TRANSITION_FUNCTIONS.iter().zip(metrics)
+        {
+            let mut turing_machine = get_turing_machine(transition_function);
+            turing_machine.halted = halted;
+            turing_machine.steps = steps;
+            turing_machine.score = score;
+            turing_machines.push(turing_machine);
+        }
+
+        store.batch_insert(&turing_machines).await.unwrap();
+
+        // only the halted turing machines are considered
+        let champions = store.select_champions(2, 2, 3).await.unwrap();
+        let scores: Vec<u64> = champions.iter().map(|tm| tm.score).collect();
+        assert_eq!(scores, vec![4, 3, 2]);
+
+        let max_steps = store.select_max_steps(2, 2, 2).await.unwrap();
+        let steps: Vec<u64> = max_steps.iter().map(|tm| tm.steps).collect();
+        assert_eq!(steps, vec![6, 5]);
+    }
+
+    #[tokio::test]
+    async fn insert_or_update_twice() {
+        let mut store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        let mut turing_machine = get_turing_machine(TRANSITION_FUNCTIONS[0]);
+
+        store.insert_or_update(turing_machine.clone()).await;
+
+        turing_machine.steps = 1000;
+        turing_machine.score = 7;
+        store
+            .batch_insert_or_update(&[turing_machine.clone()])
+            .await
+            .unwrap();
+
+        // the second insert updated the metrics of the existing row
+        let turing_machines = store.select_to_run(2, 2).await.unwrap();
+
+        assert_eq!(turing_machines.len(), 1);
+        assert_eq!(turing_machines[0].steps, 1000);
+        assert_eq!(turing_machines[0].score, 7);
+    }
+
+    #[tokio::test]
+    async fn update_and_select() {
+        let mut store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        let mut turing_machine = get_turing_machine(TRANSITION_FUNCTIONS[0]);
+
+        store.batch_insert(&[turing_machine.clone()]).await.unwrap();
+
+        // once halted, the turing machine is
+        // not selected to be run anymore
+        turing_machine.halted = true;
+        turing_machine.steps = 6;
+        store.update(turing_machine).await;
+
+        assert_eq!(store.select_to_run(2, 2).await.unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn select_to_run_paged() {
+        let mut store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+
+        for transition_function_encoded in TRANSITION_FUNCTIONS.iter() {
+            store
+                .insert(get_turing_machine(transition_function_encoded))
+                .await;
+        }
+
+        // page through the turing machines, 2 at a time
+        let mut pages: Vec<Vec<i32>> = Vec::new();
+        let mut after_id: i32 = 0;
+
+        loop {
+            let page = store.select_to_run_paged(2, 2, after_id, 2).await.unwrap();
+
+            if page.is_empty() {
+                break;
+            }
+
+            after_id = page.last().unwrap().0;
+            pages.push(page.iter().map(|(id, _)| *id).collect());
+        }
+
+        assert_eq!(pages, vec![vec![1, 2], vec![3, 4], vec![5]]);
+
+        let page = store.select_to_run_paged(2, 2, 2, 2).await.unwrap();
+        assert_eq!(
+            page[0].1.transition_function,
+            get_turing_machine(TRANSITION_FUNCTIONS[2]).transition_function
+        );
+    }
+
+    #[tokio::test]
+    async fn filter_type_round_trip() {
+        let mut store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        let mut turing_machine = get_turing_machine(TRANSITION_FUNCTIONS[0]);
+        turing_machine.filtered = FilterRuntimeType::Cycler;
+
+        store.insert(turing_machine).await;
+        store
+            .insert(get_turing_machine(TRANSITION_FUNCTIONS[1]))
+            .await;
+
+        let turing_machines = store.select_to_run(2, 2).await.unwrap();
+
+        assert_eq!(turing_machines[0].filtered, FilterRuntimeType::Cycler);
+        assert_eq!(turing_machines[1].filtered, FilterRuntimeType::None);
+    }
+
+    #[tokio::test]
+    async fn states_used_stored() {
+        let mut store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        store
+            .insert(get_turing_machine(TRANSITION_FUNCTIONS[0]))
+            .await;
+
+        let states_used: i64 = sqlx::query_scalar("SELECT states_used FROM turing_machines")
+            .fetch_one(&store.pool)
+            .await
+            .unwrap();
+
+        assert_eq!(states_used, 2);
+    }
+
+    #[tokio::test]
+    async fn input_alphabet_round_trip() {
+        let mut store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        let mut transition_function = TransitionFunction::with_input_alphabet(2, 1, 2);
+        transition_function
+            .decode(TRANSITION_FUNCTIONS[0].to_string())
+            .unwrap();
+        let turing_machine = TuringMachine::new(transition_function);
+
+        store.insert(turing_machine.clone()).await;
+
+        let input_alphabet_size: i64 =
+            sqlx::query_scalar("SELECT input_alphabet_size FROM turing_machines")
+                .fetch_one(&store.pool)
+                .await
+                .unwrap();
+        let turing_machines = store.select_to_run(2, 2).await.unwrap();
+
+        assert_eq!(input_alphabet_size, 1);
+        assert_eq!(
+            turing_machines[0].transition_function,
+            turing_machine.transition_function
+        );
+        assert_eq!(
+            turing_machines[0].transition_function.input_alphabet_size(),
+            1
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+
+use crate::turing_machine::turing_machine::TuringMachine;
+
+pub const MAX_POOL_CONNECTIONS: u32 = 8;
+
+/// Storage for the `turing_machines` table, implemented
+/// for each database the Turing machines can be stored in.
+#[async_trait]
+pub trait TmStore: Send + Sync {
+    /// Inserts the given `TuringMachine` into the database.
+    async fn insert(&mut self, turing_machine: TuringMachine);
+
+    /// Inserts the given vector of `TuringMachine`s into the database,
+    /// with a single batch insert. If the vector is empty, no query is executed.
+    ///
+    /// Returns the error of the query, if it failed, in which case none of them is inserted.
+    async fn batch_insert(&mut self, turing_machines: &[TuringMachine]) -> Result<(), sqlx::Error>;
+
+    /// Inserts the given `TuringMachine` into the database, or updates its
+    /// metrics if its encoded transition function is already stored.
+    async fn insert_or_update(&mut self, turing_machine: TuringMachine);
+
+    /// Inserts or updates the given vector of `TuringMachine`s,
+    /// with a single batch statement. If the vector is empty, no query is executed.
+    ///
+    /// Returns the error of the query, if it failed, in which case none of them is stored.
+    async fn batch_insert_or_update(
+        &mut self,
+        turing_machines: &[TuringMachine],
+    ) -> Result<(), sqlx::Error>;
+
+    /// Updates the turing machine in the database, if it
+    /// actually exists in the database. The check is done
+    /// using the `encoding` of the transition function.
+    async fn update(&self, turing_machine: TuringMachine);
+
+    /// Given a number of states and a number of symbols,
+    /// selects all the turing machines with a transtion functions
+    /// that matches those numbers and `didn't halt`.
+    async fn select_to_run(
+        &mut self,
+        number_of_states: u8,
+        number_of_symbols: u8,
+    ) -> Option<Vec<TuringMachine>>;
+
+    /// Given a number of states and a number of symbols, selects at most
+    /// `limit` turing machines that `didn't halt`, with an `id` greater
+    /// than `after_id`, ordered ascending by their `id`.
+    ///
+    /// Returns the Turing machines together with their `id`s.
+    async fn select_to_run_paged(
+        &mut self,
+        number_of_states: u8,
+        number_of_symbols: u8,
+        after_id: i32,
+        limit: u32,
+    ) -> Option<Vec<(i32, TuringMachine)>>;
+
+    /// Given a turing machine, selects the turing machine
+    /// from the database based on the encoding of the transition
+    /// function.
+    ///
+    /// Returns the `id` of the entry in the database, `if the entry exists`,
+    /// or `None` otherwise; only a failed query is an error.
+    async fn select_by_delta(
+        &mut self,
+        turing_machine: &TuringMachine,
+    ) -> Result<Option<i32>, sqlx::Error>;
+
+    /// Given the `id` of an entry in the database, selects
+    /// the turing machine stored in it, with all of its metrics.
+    ///
+    /// Returns `None` if there is no such entry or the query failed.
+    async fn select_by_id(&self, id: i32) -> Option<TuringMachine>;
+
+    /// Given a number of states and a number of symbols, selects
+    /// at most `limit` halted turing machines with the highest score,
+    /// ordered descending by their score.
+    async fn select_champions(
+        &self,
+        number_of_states: u8,
+        number_of_symbols: u8,
+        limit: u32,
+    ) -> Option<Vec<TuringMachine>>;
+
+    /// Given a number of states and a number of symbols, selects
+    /// at most `limit` halted turing machines with the most steps,
+    /// ordered descending by their number of steps.
+    async fn select_max_steps(
+        &self,
+        number_of_states: u8,
+        number_of_symbols: u8,
+        limit: u32,
+    ) -> Option<Vec<TuringMachine>>;
+
+    /// Given a number of states and a number of symbols, counts the
+    /// turing machines stored with a transition function that matches
+    /// those numbers, without selecting them.
+    async fn count(&self, number_of_states: u8, number_of_symbols: u8) -> Option<i64>;
+}
@@ -0,0 +1,42 @@
+use std::fmt;
+
+/// Errors that can occur while decoding the encoding of a
+/// `Transition` or of a `TransitionFunction`.
+#[derive(Debug, PartialEq)]
+pub enum DecodeError {
+    /// The encoding is empty.
+    Empty,
+    /// A transition does not have exactly 5 fields.
+    InvalidFieldCount(usize),
+    /// A field of a transition is not a number from 0 to 255.
+    InvalidField(String),
+    /// The direction of a transition is neither `0` nor `1`.
+    InvalidDirection(u8),
+    /// A state of a transition is neither a state of the
+    /// transition function nor the halting state.
+    InvalidState(u8),
+    /// The size of the input alphabet is either 0
+    /// or larger than the size of the tape alphabet.
+    InvalidInputAlphabet(u8),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Empty => write!(f, "empty encoding"),
+            DecodeError::InvalidFieldCount(count) => {
+                write!(f, "transition has {} fields instead of 5", count)
+            }
+            DecodeError::InvalidField(field) => write!(f, "invalid field '{}'", field),
+            DecodeError::InvalidDirection(direction) => {
+                write!(f, "invalid direction '{}'", direction)
+            }
+            DecodeError::InvalidState(state) => write!(f, "invalid state '{}'", state),
+            DecodeError::InvalidInputAlphabet(size) => {
+                write!(f, "invalid input alphabet size '{}'", size)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
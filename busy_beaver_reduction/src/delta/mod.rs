@@ -1,2 +1,5 @@
+pub mod decode_error;
+pub mod parse_error;
 pub mod transition;
 pub mod transition_function;
+pub mod validation_report;
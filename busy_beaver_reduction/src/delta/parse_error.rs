@@ -0,0 +1,43 @@
+use std::fmt;
+
+/// Errors that can occur while parsing or writing the text
+/// representation of a `TransitionFunction`.
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    /// The text is empty.
+    Empty,
+    /// A row does not have the same number of cells as the first row.
+    InvalidRowLength(usize),
+    /// A cell is not made of `<symbol><direction><state>` or `---`.
+    InvalidCell(String),
+    /// The symbol of a cell is not a digit.
+    InvalidSymbol(char),
+    /// The direction of a cell is neither `L` nor `R`.
+    InvalidDirection(char),
+    /// The state of a cell is neither a letter from `A` to `Z`,
+    /// nor a halting state (`H` / `Z`), or it exceeds the number of states.
+    InvalidState(char),
+    /// The transition function has more states than the notation
+    /// can label ( see `MAXIMUM_NOTATION_STATES` ).
+    TooManyStates(usize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "empty transition function"),
+            ParseError::InvalidRowLength(row) => write!(f, "row {} has an invalid length", row),
+            ParseError::InvalidCell(cell) => write!(f, "invalid cell '{}'", cell),
+            ParseError::InvalidSymbol(symbol) => write!(f, "invalid symbol '{}'", symbol),
+            ParseError::InvalidDirection(direction) => {
+                write!(f, "invalid direction '{}'", direction)
+            }
+            ParseError::InvalidState(state) => write!(f, "invalid state '{}'", state),
+            ParseError::TooManyStates(number_of_states) => {
+                write!(f, "too many states ({}) to be labeled", number_of_states)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
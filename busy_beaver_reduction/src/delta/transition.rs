@@ -1,6 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+use crate::delta::decode_error::DecodeError;
 use crate::turing_machine::direction::Direction;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 pub struct Transition {
     pub from_state: u8,
     pub from_symbol: u8,
@@ -98,17 +101,40 @@ impl Transition {
 
     /// Given an encoding of a `Transition`, decodes it and
     /// reconstructs the `Transition` function within `itself`.
-    pub fn decode(&mut self, transition: String) {
-        let transition: Vec<u8> = transition
-            .split(",")
-            .map(|s| s.parse::<u8>().unwrap())
-            .collect();
+    ///
+    /// If the encoding is not valid, returns the `DecodeError`
+    /// describing why, and `itself` is left unchanged.
+    pub fn decode(&mut self, transition: String) -> Result<(), DecodeError> {
+        if transition.is_empty() {
+            return Err(DecodeError::Empty);
+        }
+
+        let fields: Vec<&str> = transition.split(",").collect();
+
+        if fields.len() != 5 {
+            return Err(DecodeError::InvalidFieldCount(fields.len()));
+        }
+
+        let mut transition: Vec<u8> = Vec::new();
+
+        for field in fields {
+            match field.parse::<u8>() {
+                Ok(value) => transition.push(value),
+                Err(_) => return Err(DecodeError::InvalidField(field.to_string())),
+            }
+        }
+
+        if transition[4] > 1 {
+            return Err(DecodeError::InvalidDirection(transition[4]));
+        }
 
         self.from_state = transition[0];
         self.from_symbol = transition[1];
         self.to_state = transition[2];
         self.to_symbol = transition[3];
         self.direction = Direction::transform(transition[4]);
+
+        return Ok(());
     }
 }
 
@@ -142,7 +168,7 @@ mod tests {
     fn decode() {
         let transition_encoded: String = "0,0,1,1,1".to_string();
         let mut transition: Transition = Transition::new();
-        transition.decode(transition_encoded);
+        transition.decode(transition_encoded).unwrap();
 
         assert_eq!(transition.from_state, 0);
         assert_eq!(transition.from_symbol, 0);
@@ -150,4 +176,26 @@ mod tests {
         assert_eq!(transition.to_symbol, 1);
         assert_eq!(transition.direction, Direction::RIGHT);
     }
+
+    #[test]
+    fn decode_errors() {
+        let mut transition: Transition = Transition::new();
+
+        assert_eq!(transition.decode("".to_string()), Err(DecodeError::Empty));
+        assert_eq!(
+            transition.decode("0,0,1".to_string()),
+            Err(DecodeError::InvalidFieldCount(3))
+        );
+        assert_eq!(
+            transition.decode("0,0,x,1,1".to_string()),
+            Err(DecodeError::InvalidField("x".to_string()))
+        );
+        assert_eq!(
+            transition.decode("0,0,1,1,2".to_string()),
+            Err(DecodeError::InvalidDirection(2))
+        );
+
+        // the transition is left unchanged
+        assert_eq!(transition.to_state, 0);
+    }
 }
@@ -1,13 +1,106 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::sync::OnceLock;
 
+use serde::{Deserialize, Serialize};
+
+use crate::delta::decode_error::DecodeError;
+use crate::delta::parse_error::ParseError;
 use crate::delta::transition::Transition;
+use crate::delta::validation_report::ValidationReport;
 use crate::turing_machine::direction::Direction;
+use crate::turing_machine::special_states::{SpecialStates, LEGACY_HALT_STATE};
+
+/// Cell used in the standard notation for undefined transitions.
+const UNDEFINED_CELL: &str = "---";
+/// Separates the transitions of the encoding from the size of
+/// the input alphabet, when it is not the tape alphabet.
+const INPUT_ALPHABET_SEPARATOR: char = ';';
+/// Maximum number of states the standard notation can label, `A` to `Y`,
+/// since `Z` labels the halting state of the machines with more than 7 states.
+pub const MAXIMUM_NOTATION_STATES: u8 = 25;
 
-#[derive(PartialEq, Clone, Debug)]
+/// Cell of the standard notation, `(to_symbol, direction, to_state)`,
+/// or `None` for an undefined transition ( see `TransitionFunction::cells` ).
+pub type Cell = Option<(u8, Direction, u8)>;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TransitionFunction {
     pub number_of_states: u8,
+    /// size of the tape alphabet, the symbols from 0 to `number_of_symbols - 1`,
+    /// every one of them can be read and written by the transitions
     pub number_of_symbols: u8,
-    pub transitions: HashMap<(u8, u8), (u8, u8, Direction)>,
+    /// size of the input alphabet, the symbols from 0 to `input_alphabet_size - 1`
+    /// the tape can hold before the execution, or `None` if it is the tape alphabet;
+    /// the rest of the symbols are tape-only, and they are only ever written
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    input_alphabet_size: Option<u8>,
+    #[serde(with = "transitions_serde")]
+    transitions: HashMap<(u8, u8), (u8, u8, Direction)>,
+    /// (state, symbol) entries whose transition was overwritten
+    /// by `add_transition`, reported by `validate`
+    #[serde(skip)]
+    pub overwritten: Vec<(u8, u8)>,
+    /// result of `encode`, computed the first time it is called and
+    /// reset when a transition is added or removed; it is kept by clones
+    #[serde(skip)]
+    encoded: OnceLock<String>,
+}
+
+/// Two transition functions are equal if they have the same alphabets and
+/// transitions, regardless of how they were built or whether they were encoded.
+impl PartialEq for TransitionFunction {
+    fn eq(&self, other: &Self) -> bool {
+        return self.number_of_states == other.number_of_states
+            && self.number_of_symbols == other.number_of_symbols
+            && self.input_alphabet_size() == other.input_alphabet_size()
+            && self.transitions == other.transitions;
+    }
+}
+
+/// (De)serializes the `transitions` HashMap as an array of `Transition`s,
+/// because the keys of a JSON object can only be strings.
+mod transitions_serde {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::delta::transition::Transition;
+    use crate::turing_machine::direction::Direction;
+
+    pub fn serialize<S: Serializer>(
+        transitions: &HashMap<(u8, u8), (u8, u8, Direction)>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut transitions: Vec<Transition> = transitions
+            .iter()
+            .map(Transition::get_from_hashmap)
+            .collect();
+
+        // sort the transitions by their key, so the output is deterministic
+        transitions.sort_by_key(|transition| (transition.from_state, transition.from_symbol));
+        transitions.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<(u8, u8), (u8, u8, Direction)>, D::Error> {
+        let transitions: Vec<Transition> = Vec::deserialize(deserializer)?;
+
+        Ok(transitions
+            .into_iter()
+            .map(|transition| {
+                (
+                    (transition.from_state, transition.from_symbol),
+                    (
+                        transition.to_state,
+                        transition.to_symbol,
+                        transition.direction,
+                    ),
+                )
+            })
+            .collect())
+    }
 }
 
 impl TransitionFunction {
@@ -15,8 +108,78 @@ impl TransitionFunction {
         TransitionFunction {
             number_of_states: number_of_states,
             number_of_symbols: number_of_symbols,
+            input_alphabet_size: None,
             transitions: HashMap::new(),
+            overwritten: Vec::new(),
+            encoded: OnceLock::new(),
+        }
+    }
+
+    /// Creates a transition function with `number_of_states` states, whose tape
+    /// alphabet has `tape_alphabet_size` symbols, out of which the first
+    /// `input_alphabet_size` ones form the input alphabet.
+    ///
+    /// The input alphabet contains at least the blank symbol, 0, and it
+    /// is at most the tape alphabet.
+    pub fn with_input_alphabet(
+        number_of_states: u8,
+        input_alphabet_size: u8,
+        tape_alphabet_size: u8,
+    ) -> Self {
+        assert!(
+            input_alphabet_size >= 1 && input_alphabet_size <= tape_alphabet_size,
+            "The input alphabet must contain the blank symbol and be part of the tape alphabet."
+        );
+
+        let mut transition_function = TransitionFunction::new(number_of_states, tape_alphabet_size);
+
+        if input_alphabet_size != tape_alphabet_size {
+            transition_function.input_alphabet_size = Some(input_alphabet_size);
+        }
+
+        return transition_function;
+    }
+
+    /// Gets the number of symbols the tape can hold before the execution.
+    pub fn input_alphabet_size(&self) -> u8 {
+        return self.input_alphabet_size.unwrap_or(self.number_of_symbols);
+    }
+
+    /// Gets the number of symbols the transitions can read and write,
+    /// the input symbols followed by the tape-only ones.
+    pub fn tape_alphabet_size(&self) -> u8 {
+        return self.number_of_symbols;
+    }
+
+    /// Checks if the `symbol` belongs to the tape alphabet, but not to the
+    /// input alphabet, so it can only be on the tape if it was written.
+    pub fn is_tape_only_symbol(&self, symbol: u8) -> bool {
+        return symbol >= self.input_alphabet_size() && symbol < self.number_of_symbols;
+    }
+
+    /// Creates a transition function with `number_of_states` states and
+    /// `number_of_symbols` symbols, adding every one of the `transitions`,
+    /// in order ( see `add_transition` ).
+    pub fn from_transitions(
+        number_of_states: u8,
+        number_of_symbols: u8,
+        transitions: &[Transition],
+    ) -> Self {
+        let mut transition_function = TransitionFunction::new(number_of_states, number_of_symbols);
+
+        for transition in transitions {
+            transition_function.add_transition(*transition);
         }
+
+        return transition_function;
+    }
+
+    /// Gets the transitions, indexed by (`from_state`, `from_symbol`).
+    ///
+    /// They can only be changed with `add_transition` and `remove_transition`,
+    /// so the cached encoding ( see `encode` ) is never stale.
+    pub fn transitions(&self) -> &HashMap<(u8, u8), (u8, u8, Direction)> {
+        return &self.transitions;
     }
 
     /// Given a `Transition`, inserts it into the HashMap,
@@ -25,8 +188,13 @@ impl TransitionFunction {
     /// It means that the transition will be identified
     /// by the current state of the Turing Machine and
     /// the current symbol where the `head` is pointing at.
+    ///
+    /// If the entry already had a transition, it is overwritten,
+    /// and the entry is recorded in `overwritten`.
     pub fn add_transition(&mut self, transition: Transition) {
-        self.transitions.insert(
+        self.encoded.take();
+
+        let previous = self.transitions.insert(
             (transition.from_state, transition.from_symbol),
             (
                 transition.to_state,
@@ -34,11 +202,344 @@ impl TransitionFunction {
                 transition.direction,
             ),
         );
+
+        if previous.is_some() {
+            self.overwritten
+                .push((transition.from_state, transition.from_symbol));
+        }
+    }
+
+    /// Removes the transition indexed by (`from_state`, `from_symbol`),
+    /// if there is one.
+    ///
+    /// The transitions should be removed through this method, instead of
+    /// the `transitions` HashMap, so the cached encoding is reset.
+    pub fn remove_transition(&mut self, from_state: u8, from_symbol: u8) {
+        self.encoded.take();
+        self.transitions.remove(&(from_state, from_symbol));
+    }
+
+    /// Gets the mirror of the transition function, the left-right reflection in
+    /// which every transition, including the halting ones, moves in the opposite
+    /// direction; it behaves in the same way, on a reflected tape.
+    pub fn mirrored(&self) -> TransitionFunction {
+        let mut mirror = TransitionFunction::new(self.number_of_states, self.number_of_symbols);
+        mirror.input_alphabet_size = self.input_alphabet_size;
+
+        for transition in self.transitions.iter() {
+            let mut transition = Transition::get_from_hashmap(transition);
+            transition.direction = transition.direction.opposite();
+
+            mirror.add_transition(transition);
+        }
+
+        return mirror;
+    }
+
+    /// Relabels the states in Tree Normal Form order: the order they are first
+    /// visited from the start state ( see `get_relabeling` ), which keeps the
+    /// start state, so the transition function behaves in the same way.
+    ///
+    /// Two transition functions that only differ in the numbering of their
+    /// states are equal once normalized, including the states that can not be
+    /// reached from the start state, so a machine with any numbering can be
+    /// matched against the generated ones.
+    pub fn normalize(&mut self) {
+        let (relabeling, _) = self.get_relabeling(SpecialStates::StateStart.value());
+        let overwritten: Vec<(u8, u8)> = self
+            .overwritten
+            .iter()
+            .map(|&(state, symbol)| (relabeling[state as usize], symbol))
+            .collect();
+
+        *self = self.relabeled(&relabeling);
+        self.overwritten = overwritten;
+    }
+
+    /// Relabels the states of the transition function in the order they are
+    /// discovered by a breadth first search from `root`, following the
+    /// transitions in the order of the symbols they read.
+    ///
+    /// The states that are not reached are labeled by further searches
+    /// ( see `label_unreached_states` ), so their labels do not depend
+    /// on their numbering either.
+    ///
+    /// Returns the new label of every state, and whether every state was reached.
+    pub fn get_relabeling(&self, root: u8) -> (Vec<u8>, bool) {
+        let mut relabeling: Vec<Option<u8>> = vec![None; self.number_of_states as usize];
+        let next_label = self.label_reached_states(root, &mut relabeling, 0);
+
+        let reached_all = next_label == self.number_of_states;
+
+        if reached_all == false {
+            (relabeling, _) = self.label_unreached_states(relabeling, next_label);
+        }
+
+        return (relabeling.into_iter().flatten().collect(), reached_all);
+    }
+
+    /// Labels the states that are not labeled yet, from `next_label`, by a
+    /// search from the state whose search gives the smallest cells
+    /// ( see `cells` ), until every state is labeled.
+    ///
+    /// When several states give the same cells, e.g. two states that only
+    /// move to each other, every one of them is tried, keeping the labeling
+    /// that gives the smallest cells of the whole transition function.
+    ///
+    /// Returns the labeling, with the cells of the relabeled transition function.
+    fn label_unreached_states(
+        &self,
+        relabeling: Vec<Option<u8>>,
+        next_label: u8,
+    ) -> (Vec<Option<u8>>, Vec<Cell>) {
+        if next_label == self.number_of_states {
+            let cells = self.get_labeled_cells(&relabeling, 0, next_label);
+
+            return (relabeling, cells);
+        }
+
+        let mut candidates: Vec<(Vec<Cell>, Vec<Option<u8>>, u8)> = Vec::new();
+
+        for state in 0..self.number_of_states {
+            if relabeling[state as usize].is_some() {
+                continue;
+            }
+
+            let mut candidate = relabeling.clone();
+            let end_label = self.label_reached_states(state, &mut candidate, next_label);
+            let cells = self.get_labeled_cells(&candidate, next_label, end_label);
+
+            candidates.push((cells, candidate, end_label));
+        }
+
+        let smallest_cells = candidates
+            .iter()
+            .map(|(cells, _, _)| cells)
+            .min()
+            .unwrap()
+            .clone();
+        let mut best: Option<(Vec<Option<u8>>, Vec<Cell>)> = None;
+
+        for (cells, candidate, end_label) in candidates {
+            if cells != smallest_cells {
+                continue;
+            }
+
+            let (candidate, candidate_cells) = self.label_unreached_states(candidate, end_label);
+
+            match &best {
+                Some((_, best_cells)) if *best_cells <= candidate_cells => {}
+                _ => best = Some((candidate, candidate_cells)),
+            }
+        }
+
+        return best.unwrap();
+    }
+
+    /// Labels the states that are not labeled yet, reached by a breadth first
+    /// search from `root`, starting from `next_label`.
+    ///
+    /// Returns the label after the last one given.
+    fn label_reached_states(
+        &self,
+        root: u8,
+        relabeling: &mut [Option<u8>],
+        mut next_label: u8,
+    ) -> u8 {
+        let mut states_to_visit: VecDeque<u8> = VecDeque::new();
+
+        relabeling[root as usize] = Some(next_label);
+        next_label += 1;
+        states_to_visit.push_back(root);
+
+        while let Some(state) = states_to_visit.pop_front() {
+            for symbol in 0..self.number_of_symbols {
+                let Some(&(to_state, _, _)) = self.transitions.get(&(state, symbol)) else {
+                    continue;
+                };
+
+                if to_state == SpecialStates::StateHalt.value()
+                    || relabeling[to_state as usize].is_some()
+                {
+                    continue;
+                }
+
+                relabeling[to_state as usize] = Some(next_label);
+                next_label += 1;
+                states_to_visit.push_back(to_state);
+            }
+        }
+
+        return next_label;
+    }
+
+    /// Gets the cells of the states labeled from `start_label` to `end_label`,
+    /// in the order of their labels, as if the transition function was relabeled.
+    ///
+    /// Every state they move to is already labeled, since it was reached.
+    fn get_labeled_cells(
+        &self,
+        relabeling: &[Option<u8>],
+        start_label: u8,
+        end_label: u8,
+    ) -> Vec<Cell> {
+        let mut states: Vec<u8> = vec![0; (end_label - start_label) as usize];
+
+        for (state, label) in relabeling.iter().enumerate() {
+            if let Some(label) = label {
+                if *label >= start_label && *label < end_label {
+                    states[(*label - start_label) as usize] = state as u8;
+                }
+            }
+        }
+
+        let mut cells: Vec<Cell> = Vec::new();
+
+        for state in states {
+            for symbol in 0..self.number_of_symbols {
+                cells.push(self.transitions.get(&(state, symbol)).map(
+                    |&(to_state, to_symbol, direction)| {
+                        if to_state == SpecialStates::StateHalt.value() {
+                            return (to_symbol, direction, to_state);
+                        }
+
+                        return (to_symbol, direction, relabeling[to_state as usize].unwrap());
+                    },
+                ));
+            }
+        }
+
+        return cells;
+    }
+
+    /// Gets the transition function obtained by replacing every state
+    /// `q` with `relabeling[q]`; the halting state is kept.
+    pub fn relabeled(&self, relabeling: &[u8]) -> TransitionFunction {
+        let relabel_state = |state: u8| -> u8 {
+            if state == SpecialStates::StateHalt.value() {
+                return state;
+            }
+
+            return relabeling[state as usize];
+        };
+
+        let mut relabeled = TransitionFunction::new(self.number_of_states, self.number_of_symbols);
+        relabeled.input_alphabet_size = self.input_alphabet_size;
+
+        for (&(from_state, from_symbol), &(to_state, to_symbol, direction)) in
+            self.transitions.iter()
+        {
+            relabeled.add_transition(Transition::new_params(
+                relabel_state(from_state),
+                from_symbol,
+                relabel_state(to_state),
+                to_symbol,
+                direction,
+            ));
+        }
+
+        return relabeled;
+    }
+
+    /// Counts the distinct states that are actually used: the start state and
+    /// the states that can be reached from it, following the transitions
+    /// for every symbol, without the halting state.
+    ///
+    /// A machine with `number_of_states` states that uses fewer of them
+    /// behaves as one with `states_used` states.
+    pub fn states_used(&self) -> u8 {
+        let mut states_reached: HashSet<u8> = HashSet::new();
+        let mut states_to_visit: Vec<u8> = vec![SpecialStates::StateStart.value()];
+
+        states_reached.insert(SpecialStates::StateStart.value());
+
+        while let Some(state) = states_to_visit.pop() {
+            for symbol in 0..self.number_of_symbols {
+                let Some(transition) = self.transitions.get(&(state, symbol)) else {
+                    continue;
+                };
+
+                if transition.0 == SpecialStates::StateHalt.value() {
+                    continue;
+                }
+
+                if states_reached.insert(transition.0) == true {
+                    states_to_visit.push(transition.0);
+                }
+            }
+        }
+
+        return states_reached.len() as u8;
+    }
+
+    /// Validates that the transition function is deterministic and complete:
+    /// - every `(state, symbol)` entry of the domain, with the states from 0 to
+    /// `number_of_states - 1` and the symbols from 0 to `number_of_symbols - 1`,
+    /// has a transition
+    /// - no transition was overwritten while it was built, e.g. by
+    /// decoding an encoding that contains the same entry twice
+    ///
+    /// Returns the `ValidationReport` listing the entries that are not.
+    pub fn validate(&self) -> ValidationReport {
+        let mut undefined: Vec<(u8, u8)> = Vec::new();
+
+        for state in 0..self.number_of_states {
+            for symbol in 0..self.number_of_symbols {
+                if self.transitions.contains_key(&(state, symbol)) == false {
+                    undefined.push((state, symbol));
+                }
+            }
+        }
+
+        return ValidationReport {
+            undefined: undefined,
+            overwritten: self.overwritten.clone(),
+        };
+    }
+
+    /// Gets how complete the transition function is: the number of
+    /// entries that have a transition out of the `number_of_states *
+    /// number_of_symbols` entries of the domain, from 0.0 to 1.0.
+    ///
+    /// A transition function with an empty domain is complete.
+    pub fn completeness(&self) -> f64 {
+        let domain_size = self.number_of_states as usize * self.number_of_symbols as usize;
+
+        if domain_size == 0 {
+            return 1.0;
+        }
+
+        return self.transitions.len() as f64 / domain_size as f64;
+    }
+
+    /// Checks if the transition function is deterministic and complete,
+    /// in the same way as `validate`, without building the report.
+    pub fn is_deterministic_complete(&self) -> bool {
+        if self.overwritten.is_empty() == false {
+            return false;
+        }
+
+        for state in 0..self.number_of_states {
+            for symbol in 0..self.number_of_symbols {
+                if self.transitions.contains_key(&(state, symbol)) == false {
+                    return false;
+                }
+            }
+        }
+
+        return true;
     }
 
     /// Encodes the `transitions` HashMap by firstly encoding
     /// each entry and making a `Vec<String>>` with the encodings.
-    /// After that, concatenate the vector with "|".
+    /// After that, concatenate the vector with "|". If the input alphabet is not
+    /// the tape alphabet, its size is appended after a ";".
+    ///
+    /// The transitions are sorted by `(from_state, from_symbol)`, so the
+    /// encoding does not depend on the iteration order of the HashMap.
+    ///
+    /// The encoding is computed once and cached, until the
+    /// transitions are changed by `add_transition` or `remove_transition`.
     ///
     /// Returns the resulted `String`.
     ///
@@ -51,31 +552,396 @@ impl TransitionFunction {
     /// String transition_encoding_03 = "1,1,1,0,1";
     ///
     /// transition_function.encode() = "0,0,1,1,0|0,0,1,0,0|1,1,1,0,1"
+    ///
+    /// or, with an input alphabet of 1 symbol out of 2 tape symbols,
+    /// transition_function.encode() = "0,0,1,1,0|0,0,1,0,0|1,1,1,0,1;1"
     pub fn encode(&self) -> String {
-        return self
-            .transitions
-            .iter()
-            .map(|transition| Transition::encode_from_hashmap(transition))
-            .collect::<Vec<String>>()
-            .join("|");
+        let encoded = self.encoded.get_or_init(|| {
+            let mut transitions: Vec<(&(u8, u8), &(u8, u8, Direction))> =
+                self.transitions.iter().collect();
+            transitions.sort_by_key(|(key, _)| **key);
+
+            let encoded = transitions
+                .into_iter()
+                .map(|transition| Transition::encode_from_hashmap(transition))
+                .collect::<Vec<String>>()
+                .join("|");
+
+            match self.input_alphabet_size {
+                Some(input_alphabet_size) => {
+                    format!(
+                        "{}{}{}",
+                        encoded, INPUT_ALPHABET_SEPARATOR, input_alphabet_size
+                    )
+                }
+                None => encoded,
+            }
+        });
+
+        return encoded.clone();
     }
 
     /// Given a `String`, reconstructs the self `TransitionFunction.transitions` by
     /// decoding each transition from `encoded` and adding it back in the HashMap.
-    pub fn decode(&mut self, encoded: String) {
-        let transitions: Vec<String> = encoded.split("|").map(|s| s.to_string()).collect();
+    ///
+    /// If any of the transitions can not be decoded, or uses a state that is neither
+    /// one of the `number_of_states` states nor the halting state, returns the
+    /// `DecodeError` describing why, and none of the transitions are added.
+    ///
+    /// The legacy halting state, `LEGACY_HALT_STATE`, is decoded as the halting state.
+    ///
+    /// The input alphabet is decoded as well, if the encoding holds its size.
+    pub fn decode(&mut self, encoded: String) -> Result<(), DecodeError> {
+        if encoded.is_empty() {
+            return Err(DecodeError::Empty);
+        }
 
-        for transition in transitions {
+        let (encoded, input_alphabet_size) = match encoded.split_once(INPUT_ALPHABET_SEPARATOR) {
+            Some((encoded, input_alphabet_size)) => {
+                let input_alphabet_size: u8 = input_alphabet_size
+                    .parse()
+                    .map_err(|_| DecodeError::InvalidField(input_alphabet_size.to_string()))?;
+
+                if input_alphabet_size == 0 || input_alphabet_size > self.number_of_symbols {
+                    return Err(DecodeError::InvalidInputAlphabet(input_alphabet_size));
+                }
+
+                (encoded, Some(input_alphabet_size))
+            }
+            None => (encoded.as_str(), None),
+        };
+
+        let mut transitions: Vec<Transition> = Vec::new();
+
+        for transition in encoded.split("|") {
             let mut transition_: Transition = Transition::new();
-            transition_.decode(transition);
-            self.add_transition(transition_);
+            transition_.decode(transition.to_string())?;
+
+            // encodings stored before the halting state was moved
+            // to `HALT_STATE` still use the legacy sentinel
+            if transition_.to_state == LEGACY_HALT_STATE
+                && self.number_of_states <= LEGACY_HALT_STATE
+            {
+                transition_.to_state = SpecialStates::StateHalt.value();
+            }
+
+            if transition_.from_state >= self.number_of_states {
+                return Err(DecodeError::InvalidState(transition_.from_state));
+            }
+
+            if transition_.to_state >= self.number_of_states
+                && transition_.to_state != SpecialStates::StateHalt.value()
+            {
+                return Err(DecodeError::InvalidState(transition_.to_state));
+            }
+
+            transitions.push(transition_);
         }
+
+        if let Some(input_alphabet_size) = input_alphabet_size {
+            self.input_alphabet_size =
+                Some(input_alphabet_size).filter(|&size| size != self.number_of_symbols);
+        }
+
+        for transition in transitions {
+            self.add_transition(transition);
+        }
+
+        return Ok(());
+    }
+
+    /// Encodes the transition function in the standard Busy Beaver
+    /// notation used by bbchallenge.org: one row per state, separated by `_`,
+    /// each row containing a `<symbol><direction><state>` cell for every symbol.
+    ///
+    /// States are labeled `A`, `B`, `C`, ..., the halting state is labeled `H`
+    /// ( or `Z`, if `H` is already a state ) and undefined transitions are `---`.
+    ///
+    /// Returns `ParseError::TooManyStates` if the transition function has
+    /// more than `MAXIMUM_NOTATION_STATES` states, which can not be labeled.
+    ///
+    /// EXAMPLE:
+    ///
+    /// The BB(2) champion is encoded as "1RB1LB_1LA1RH".
+    pub fn to_standard_notation(&self) -> Result<String, ParseError> {
+        if self.number_of_states > MAXIMUM_NOTATION_STATES {
+            return Err(ParseError::TooManyStates(self.number_of_states as usize));
+        }
+
+        let halt_label = Self::get_halt_label(self.number_of_states);
+        let mut rows: Vec<String> = Vec::new();
+
+        for state in 0..self.number_of_states {
+            let mut row = String::new();
+
+            for symbol in 0..self.number_of_symbols {
+                match self.transitions.get(&(state, symbol)) {
+                    Some(transition) => {
+                        let to_state = if transition.0 == SpecialStates::StateHalt.value() {
+                            halt_label
+                        } else {
+                            (b'A' + transition.0) as char
+                        };
+                        row.push_str(&format!(
+                            "{}{}{}",
+                            transition.1,
+                            transition.2.label(),
+                            to_state
+                        ));
+                    }
+                    None => row.push_str(UNDEFINED_CELL),
+                }
+            }
+
+            rows.push(row);
+        }
+
+        return Ok(rows.join("_"));
+    }
+
+    /// Gets the cells of the transition function, in the order of the standard
+    /// notation: `(to_symbol, direction, to_state)` for every `(state, symbol)`
+    /// entry, sorted by state and then by symbol, or `None` if it is undefined.
+    ///
+    /// The cells of two transition functions are equal only if they are, and
+    /// they are ordered as their standard notations, for any number of states,
+    /// so they are compared instead of the notations, e.g. to deduplicate them.
+    pub fn cells(&self) -> Vec<Cell> {
+        let mut cells: Vec<Cell> = Vec::new();
+
+        for state in 0..self.number_of_states {
+            for symbol in 0..self.number_of_symbols {
+                cells.push(
+                    self.transitions
+                        .get(&(state, symbol))
+                        .map(|&(to_state, to_symbol, direction)| (to_symbol, direction, to_state)),
+                );
+            }
+        }
+
+        return cells;
+    }
+
+    /// Given a transition function in the standard Busy Beaver notation
+    /// ( see `to_standard_notation` ), reconstructs the `TransitionFunction`.
+    ///
+    /// The number of states is given by the number of rows,
+    /// and the number of symbols by the number of cells in a row.
+    pub fn from_standard_notation(encoded: &str) -> Result<TransitionFunction, ParseError> {
+        let rows: Vec<&str> = encoded.trim().split('_').collect();
+
+        if rows[0].is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        // every cell has exactly 3 characters
+        if rows[0].len() % 3 != 0 {
+            return Err(ParseError::InvalidRowLength(0));
+        }
+
+        if rows.len() > MAXIMUM_NOTATION_STATES as usize {
+            return Err(ParseError::TooManyStates(rows.len()));
+        }
+
+        let number_of_states = rows.len() as u8;
+        let number_of_symbols = (rows[0].len() / 3) as u8;
+        let mut transition_function = TransitionFunction::new(number_of_states, number_of_symbols);
+
+        for (state, row) in rows.iter().enumerate() {
+            if !row.is_ascii() || row.len() != rows[0].len() {
+                return Err(ParseError::InvalidRowLength(state));
+            }
+
+            for symbol in 0..number_of_symbols as usize {
+                let cell = &row[symbol * 3..symbol * 3 + 3];
+
+                if cell == UNDEFINED_CELL {
+                    continue;
+                }
+
+                let (to_symbol, direction, to_state) =
+                    Self::decode_standard_cell(cell, number_of_states)?;
+
+                transition_function.add_transition(Transition {
+                    from_state: state as u8,
+                    from_symbol: symbol as u8,
+                    to_state,
+                    to_symbol,
+                    direction,
+                });
+            }
+        }
+
+        return Ok(transition_function);
+    }
+
+    /// Decodes a single `<symbol><direction><state>` cell
+    /// of the standard notation.
+    fn decode_standard_cell(
+        cell: &str,
+        number_of_states: u8,
+    ) -> Result<(u8, Direction, u8), ParseError> {
+        let characters: Vec<char> = cell.chars().collect();
+
+        if characters.len() != 3 {
+            return Err(ParseError::InvalidCell(cell.to_string()));
+        }
+
+        let to_symbol = match characters[0].to_digit(10) {
+            Some(symbol) => symbol as u8,
+            None => return Err(ParseError::InvalidSymbol(characters[0])),
+        };
+
+        let direction = match characters[1] {
+            'L' => Direction::LEFT,
+            'R' => Direction::RIGHT,
+            other => return Err(ParseError::InvalidDirection(other)),
+        };
+
+        // letters that are not states of the machine
+        // can only be used to mark the halting state
+        let to_state = match characters[2] {
+            'A'..='Z' if (characters[2] as u8 - b'A') < number_of_states => {
+                characters[2] as u8 - b'A'
+            }
+            'H' | 'Z' => SpecialStates::StateHalt.value(),
+            other => return Err(ParseError::InvalidState(other)),
+        };
+
+        return Ok((to_symbol, direction, to_state));
+    }
+
+    /// Exports the transition function as a Graphviz `digraph`, that can be
+    /// rendered with `dot -Tpng`: one node per state and one edge per
+    /// transition, labeled `read/write,direction`.
+    ///
+    /// The start state is drawn bold and transitions into the
+    /// halting state point to a separate `HALT` node.
+    ///
+    /// EXAMPLE:
+    ///
+    /// The transition (0, 0) -> (1, 1, R) is exported as `q0 -> q1 [label="0/1,R"];`
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph TuringMachine {\n    rankdir=LR;\n");
+
+        for state in 0..self.number_of_states {
+            match SpecialStates::transform(state) {
+                SpecialStates::StateStart => {
+                    dot.push_str(&format!("    q{} [shape=circle, style=bold];\n", state))
+                }
+                _ => dot.push_str(&format!("    q{} [shape=circle];\n", state)),
+            }
+        }
+
+        dot.push_str("    HALT [shape=doublecircle];\n");
+
+        // sort the transitions by their key, so the output is deterministic
+        let mut transitions: Vec<(&(u8, u8), &(u8, u8, Direction))> =
+            self.transitions.iter().collect();
+        transitions.sort_by_key(|transition| *transition.0);
+
+        for (key, value) in transitions {
+            let to_node = match SpecialStates::transform(value.0) {
+                SpecialStates::StateHalt => "HALT".to_string(),
+                _ => format!("q{}", value.0),
+            };
+            dot.push_str(&format!(
+                "    q{} -> {} [label=\"{}/{},{}\"];\n",
+                key.0,
+                to_node,
+                key.1,
+                value.1,
+                value.2.label()
+            ));
+        }
+
+        dot.push_str("}\n");
+
+        return dot;
+    }
+
+    /// Gets the label of the halting state in the standard notation:
+    /// `H`, unless `H` is the label of one of the states.
+    fn get_halt_label(number_of_states: u8) -> char {
+        if number_of_states <= b'H' - b'A' {
+            return 'H';
+        }
+
+        return 'Z';
+    }
+}
+
+/// Renders the transition function as a table, with a header row holding
+/// the symbols and a row for every state, whose cells are `write dir next`
+/// ( `---` for the undefined transitions ).
+///
+/// States are labeled as in the standard notation ( see `to_standard_notation` ),
+/// or by their number if there are more than `MAXIMUM_NOTATION_STATES`,
+/// and the start state is marked with `>`.
+///
+/// EXAMPLE:
+///
+/// The BB(2) champion is rendered as:
+///
+/// ```text
+///     |   0   |   1
+/// > A | 1 R B | 1 L B
+///   B | 1 L A | 1 R H
+/// ```
+impl fmt::Display for TransitionFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let halt_label = Self::get_halt_label(self.number_of_states);
+        let state_label = |state: u8| -> String {
+            if self.number_of_states > MAXIMUM_NOTATION_STATES {
+                return state.to_string();
+            }
+
+            return ((b'A' + state) as char).to_string();
+        };
+
+        let header: Vec<String> = (0..self.number_of_symbols)
+            .map(|symbol| format!("{:^5}", symbol))
+            .collect();
+        write!(f, "    | {}", header.join(" | "))?;
+
+        for state in 0..self.number_of_states {
+            let marker = match SpecialStates::transform(state) {
+                SpecialStates::StateStart => '>',
+                _ => ' ',
+            };
+
+            let cells: Vec<String> = (0..self.number_of_symbols)
+                .map(|symbol| match self.transitions.get(&(state, symbol)) {
+                    Some(transition) => {
+                        let to_state = match SpecialStates::transform(transition.0) {
+                            SpecialStates::StateHalt => halt_label.to_string(),
+                            _ => state_label(transition.0),
+                        };
+
+                        format!("{} {} {}", transition.1, transition.2.label(), to_state)
+                    }
+                    None => format!("{:^5}", UNDEFINED_CELL),
+                })
+                .collect();
+
+            write!(
+                f,
+                "\n{} {} | {}",
+                marker,
+                state_label(state),
+                cells.join(" | ")
+            )?;
+        }
+
+        return Ok(());
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::turing_machine::special_states::HALT_STATE;
+    use crate::turing_machine::turing_machine::TuringMachine;
 
     #[test]
     fn encode() {
@@ -101,13 +967,65 @@ mod tests {
 
         let transition_function_encoded = transition_function.encode();
 
-        if transition_function_encoded == "0,0,1,1,1|0,1,1,1,1" {
-            assert_eq!(true, true);
-        } else if transition_function_encoded == "0,1,1,1,1|0,0,1,1,1" {
-            assert_eq!(true, true);
-        } else {
-            assert_eq!(true, false);
+        assert_eq!(transition_function_encoded, "0,0,1,1,1|0,1,1,1,1");
+    }
+
+    #[test]
+    fn from_transitions() {
+        let transitions: Vec<Transition> = vec![
+            Transition::new_params(0, 0, 1, 1, Direction::RIGHT),
+            Transition::new_params(0, 1, HALT_STATE, 1, Direction::RIGHT),
+            Transition::new_params(1, 0, 0, 1, Direction::LEFT),
+            Transition::new_params(1, 1, 1, 0, Direction::LEFT),
+        ];
+
+        let mut transition_function: TransitionFunction = TransitionFunction::new(2, 2);
+        for transition in transitions.iter() {
+            transition_function.add_transition(*transition);
+        }
+
+        let transition_function_from = TransitionFunction::from_transitions(2, 2, &transitions);
+
+        assert!(transition_function_from == transition_function);
+        assert_eq!(
+            transition_function_from.encode(),
+            transition_function.encode()
+        );
+        assert_eq!(
+            TransitionFunction::from_transitions(2, 2, &[])
+                .transitions
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn encode_insertion_order() {
+        let transitions: Vec<Transition> = vec![
+            Transition::new_params(0, 0, 1, 1, Direction::RIGHT),
+            Transition::new_params(0, 1, HALT_STATE, 1, Direction::RIGHT),
+            Transition::new_params(1, 0, 0, 1, Direction::LEFT),
+            Transition::new_params(1, 1, 1, 0, Direction::LEFT),
+        ];
+
+        let mut transition_function_01: TransitionFunction = TransitionFunction::new(2, 2);
+        let mut transition_function_02: TransitionFunction = TransitionFunction::new(2, 2);
+
+        for transition in transitions.iter() {
+            transition_function_01.add_transition(transition.clone());
         }
+        for transition in transitions.iter().rev() {
+            transition_function_02.add_transition(transition.clone());
+        }
+
+        assert_eq!(
+            transition_function_01.encode(),
+            transition_function_02.encode()
+        );
+        assert_eq!(
+            transition_function_01.encode(),
+            "0,0,1,1,1|0,1,255,1,1|1,0,0,1,0|1,1,1,0,0"
+        );
     }
 
     #[test]
@@ -115,7 +1033,9 @@ mod tests {
         let transition_function_encoded = "0,0,0,0,1|0,1,1,0,1|1,1,0,1,0".to_string();
         let mut transition_function: TransitionFunction = TransitionFunction::new(2, 2);
 
-        transition_function.decode(transition_function_encoded);
+        transition_function
+            .decode(transition_function_encoded)
+            .unwrap();
 
         assert_eq!(transition_function.transitions.contains_key(&(0, 0)), true);
         assert_eq!(transition_function.transitions.contains_key(&(0, 1)), true);
@@ -133,4 +1053,489 @@ mod tests {
             Some(&(0 as u8, 1 as u8, Direction::LEFT))
         );
     }
+
+    #[test]
+    fn decode_errors() {
+        let mut transition_function: TransitionFunction = TransitionFunction::new(2, 2);
+
+        assert_eq!(
+            transition_function.decode("".to_string()),
+            Err(DecodeError::Empty)
+        );
+        assert_eq!(
+            transition_function.decode("0,0,1,1,1|0,1,1".to_string()),
+            Err(DecodeError::InvalidFieldCount(3))
+        );
+        assert_eq!(
+            transition_function.decode("0,0,1,1,1|0,1,one,1,1".to_string()),
+            Err(DecodeError::InvalidField("one".to_string()))
+        );
+
+        assert_eq!(
+            transition_function.decode("0,0,1,1,1|0,1,2,1,1".to_string()),
+            Err(DecodeError::InvalidState(2))
+        );
+        assert_eq!(
+            transition_function.decode("0,0,1,1,1|2,1,0,1,1".to_string()),
+            Err(DecodeError::InvalidState(2))
+        );
+
+        // none of the valid transitions were added
+        assert!(transition_function.transitions.is_empty());
+    }
+
+    #[test]
+    fn decode_legacy_halt_state() {
+        // the BB(2) champion, stored with the legacy halting state
+        let mut transition_function: TransitionFunction = TransitionFunction::new(2, 2);
+        transition_function
+            .decode("0,0,1,1,1|0,1,1,1,0|1,0,0,1,0|1,1,101,1,1".to_string())
+            .unwrap();
+
+        assert_eq!(
+            transition_function.to_standard_notation().unwrap(),
+            "1RB1LB_1LA1RH"
+        );
+        assert_eq!(
+            transition_function.encode(),
+            "0,0,1,1,1|0,1,1,1,0|1,0,0,1,0|1,1,255,1,1"
+        );
+    }
+
+    #[test]
+    fn standard_notation_round_trip() {
+        let champion = "1RB1LB_1LA1RH";
+        let transition_function = TransitionFunction::from_standard_notation(champion).unwrap();
+
+        assert_eq!(transition_function.number_of_states, 2);
+        assert_eq!(transition_function.number_of_symbols, 2);
+        assert_eq!(
+            transition_function.transitions.get(&(1, 1)),
+            Some(&(SpecialStates::StateHalt.value(), 1, Direction::RIGHT))
+        );
+        assert_eq!(
+            transition_function.to_standard_notation().unwrap(),
+            champion
+        );
+
+        // the standard notation must round-trip with the crate's own encoding
+        let mut transition_function_decoded: TransitionFunction = TransitionFunction::new(2, 2);
+        transition_function_decoded
+            .decode(transition_function.encode())
+            .unwrap();
+
+        assert_eq!(transition_function_decoded, transition_function);
+        assert_eq!(
+            transition_function_decoded.to_standard_notation().unwrap(),
+            champion
+        );
+    }
+
+    #[test]
+    fn standard_notation_undefined_transitions() {
+        let mut transition_function: TransitionFunction = TransitionFunction::new(2, 2);
+        transition_function.add_transition(Transition::new_params(0, 0, 1, 1, Direction::RIGHT));
+        transition_function.add_transition(Transition::new_params(1, 1, 0, 0, Direction::LEFT));
+
+        assert_eq!(
+            transition_function.to_standard_notation().unwrap(),
+            "1RB---_---0LA"
+        );
+        assert_eq!(
+            TransitionFunction::from_standard_notation("1RB---_---0LA").unwrap(),
+            transition_function
+        );
+    }
+
+    #[test]
+    fn standard_notation_errors() {
+        assert_eq!(
+            TransitionFunction::from_standard_notation(""),
+            Err(ParseError::Empty)
+        );
+        assert_eq!(
+            TransitionFunction::from_standard_notation("1RB1LB_1LA"),
+            Err(ParseError::InvalidRowLength(1))
+        );
+        assert_eq!(
+            TransitionFunction::from_standard_notation("1XB1LB_1LA1RH"),
+            Err(ParseError::InvalidDirection('X'))
+        );
+        assert_eq!(
+            TransitionFunction::from_standard_notation("1RC1LB_1LA1RH"),
+            Err(ParseError::InvalidState('C'))
+        );
+    }
+
+    #[test]
+    fn standard_notation_too_many_states() {
+        // the last state labeled by the notation
+        let mut transition_function = TransitionFunction::new(MAXIMUM_NOTATION_STATES, 1);
+        transition_function.add_transition(Transition::new_params(0, 0, 24, 1, Direction::RIGHT));
+        transition_function.add_transition(Transition::new_params(
+            24,
+            0,
+            HALT_STATE,
+            1,
+            Direction::RIGHT,
+        ));
+
+        let notation = transition_function.to_standard_notation().unwrap();
+        assert!(notation.starts_with("1RY_"));
+        assert!(notation.ends_with("_1RZ"));
+        assert_eq!(
+            TransitionFunction::from_standard_notation(&notation).unwrap(),
+            transition_function
+        );
+
+        // `Z` would label both the 26th state and the halting state
+        let mut transition_function_01 = TransitionFunction::new(26, 1);
+        transition_function_01.add_transition(Transition::new_params(
+            0,
+            0,
+            25,
+            1,
+            Direction::RIGHT,
+        ));
+        let mut transition_function_02 = TransitionFunction::new(26, 1);
+        transition_function_02.add_transition(Transition::new_params(
+            0,
+            0,
+            HALT_STATE,
+            1,
+            Direction::RIGHT,
+        ));
+
+        assert_eq!(
+            transition_function_01.to_standard_notation(),
+            Err(ParseError::TooManyStates(26))
+        );
+        assert_ne!(
+            transition_function_01.cells(),
+            transition_function_02.cells()
+        );
+        assert_eq!(
+            TransitionFunction::from_standard_notation(&vec!["1RA"; 26].join("_")),
+            Err(ParseError::TooManyStates(26))
+        );
+
+        // the states are labeled by their number, without overflowing
+        let mut transition_function = TransitionFunction::new(200, 1);
+        transition_function.add_transition(Transition::new_params(0, 0, 199, 1, Direction::RIGHT));
+
+        assert!(transition_function.to_string().contains("\n> 0 | 1 R 199"));
+        assert_eq!(
+            transition_function.cells()[0],
+            Some((1, Direction::RIGHT, 199))
+        );
+    }
+
+    #[test]
+    fn cells() {
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB---_1LA1RH").unwrap();
+
+        assert_eq!(
+            transition_function.cells(),
+            vec![
+                Some((1, Direction::RIGHT, 1)),
+                None,
+                Some((1, Direction::LEFT, 0)),
+                Some((1, Direction::RIGHT, HALT_STATE)),
+            ]
+        );
+
+        // the cells are ordered as the standard notations
+        let champion = TransitionFunction::from_standard_notation("1RB1LB_1LA1RH").unwrap();
+        let mirror = TransitionFunction::from_standard_notation("1LB1RB_1RA1RH").unwrap();
+
+        assert!(mirror.cells() < champion.cells());
+        assert!(transition_function.cells() < champion.cells());
+    }
+
+    #[test]
+    fn normalize() {
+        let mut transition_function_01 =
+            TransitionFunction::from_standard_notation("1RB1LC_1LA1RH_1RB0LA").unwrap();
+        // the same transition function, with states B and C interchanged
+        let mut transition_function_02 =
+            TransitionFunction::from_standard_notation("1RC1LB_1RC0LA_1LA1RH").unwrap();
+
+        assert_ne!(transition_function_01, transition_function_02);
+
+        transition_function_01.normalize();
+        transition_function_02.normalize();
+
+        assert_eq!(transition_function_01, transition_function_02);
+        assert_eq!(
+            transition_function_02.to_standard_notation().unwrap(),
+            "1RB1LC_1LA1RH_1RB0LA"
+        );
+    }
+
+    #[test]
+    fn normalize_unreachable_states() {
+        // only D is reached from the start state, C moves to E, while
+        // E and F only move to each other, so they can not be told apart
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RD1LH_------_0RE---_0LD1LD_0LE1RF_0LF1RE")
+                .unwrap();
+        let (_, reached_all) =
+            transition_function.get_relabeling(SpecialStates::StateStart.value());
+        assert_eq!(reached_all, false);
+
+        for relabeling in [[0, 1, 2, 3, 5, 4], [0, 5, 4, 2, 3, 1], [0, 3, 1, 4, 2, 5]] {
+            let mut transition_function_01 = transition_function.clone();
+            let mut transition_function_02 = transition_function.relabeled(&relabeling);
+
+            assert_ne!(transition_function_01, transition_function_02);
+
+            transition_function_01.normalize();
+            transition_function_02.normalize();
+
+            assert_eq!(transition_function_01, transition_function_02);
+        }
+
+        // the reached states are labeled first
+        let mut normalized = transition_function.clone();
+        normalized.normalize();
+        assert!(normalized
+            .to_standard_notation()
+            .unwrap()
+            .starts_with("1RB1LH_0LB1LB_"));
+    }
+
+    #[test]
+    fn completeness() {
+        let mut transition_function = TransitionFunction::new(2, 2);
+        assert_eq!(transition_function.completeness(), 0.0);
+
+        transition_function.add_transition(Transition::new_params(0, 0, 1, 1, Direction::RIGHT));
+        transition_function.add_transition(Transition::new_params(1, 1, 0, 1, Direction::LEFT));
+        assert_eq!(transition_function.completeness(), 0.5);
+
+        let champion = TransitionFunction::from_standard_notation("1RB1LB_1LA1RH").unwrap();
+        assert_eq!(champion.completeness(), 1.0);
+        assert_eq!(TransitionFunction::new(0, 0).completeness(), 1.0);
+    }
+
+    #[test]
+    fn with_input_alphabet() {
+        let mut transition_function = TransitionFunction::with_input_alphabet(1, 2, 3);
+        transition_function.add_transition(Transition::new_params(0, 0, 0, 2, Direction::RIGHT));
+
+        assert_eq!(transition_function.input_alphabet_size(), 2);
+        assert_eq!(transition_function.tape_alphabet_size(), 3);
+        assert_eq!(transition_function.is_tape_only_symbol(1), false);
+        assert_eq!(transition_function.is_tape_only_symbol(2), true);
+
+        // the input alphabet is kept by serialization and by the mirror
+        let transition_function_json = serde_json::to_string(&transition_function).unwrap();
+        let transition_function_deserialized: TransitionFunction =
+            serde_json::from_str(&transition_function_json).unwrap();
+
+        assert_eq!(transition_function_deserialized, transition_function);
+        assert_eq!(transition_function.mirrored().input_alphabet_size(), 2);
+
+        // without a separate input alphabet, it is the tape alphabet
+        assert_eq!(TransitionFunction::new(1, 3).input_alphabet_size(), 3);
+        assert_ne!(
+            TransitionFunction::new(1, 3),
+            TransitionFunction::with_input_alphabet(1, 2, 3)
+        );
+
+        // the input alphabet is kept by the encoding as well
+        assert_eq!(transition_function.encode(), "0,0,0,2,1;2");
+
+        let mut transition_function_decoded = TransitionFunction::new(1, 3);
+        transition_function_decoded
+            .decode(transition_function.encode())
+            .unwrap();
+
+        assert_eq!(transition_function_decoded.input_alphabet_size(), 2);
+        assert_eq!(transition_function_decoded, transition_function);
+
+        assert_eq!(
+            TransitionFunction::new(1, 3).decode("0,0,0,2,1;4".to_string()),
+            Err(DecodeError::InvalidInputAlphabet(4))
+        );
+        assert_eq!(
+            TransitionFunction::new(1, 3).decode("0,0,0,2,1;A".to_string()),
+            Err(DecodeError::InvalidField("A".to_string()))
+        );
+    }
+
+    #[test]
+    fn serde_json_round_trip() {
+        let mut transition_function: TransitionFunction = TransitionFunction::new(3, 2);
+        transition_function.add_transition(Transition::new_params(0, 0, 1, 1, Direction::RIGHT));
+        transition_function.add_transition(Transition::new_params(
+            0,
+            1,
+            HALT_STATE,
+            1,
+            Direction::RIGHT,
+        ));
+        transition_function.add_transition(Transition::new_params(1, 0, 2, 0, Direction::RIGHT));
+        transition_function.add_transition(Transition::new_params(1, 1, 1, 1, Direction::RIGHT));
+        transition_function.add_transition(Transition::new_params(2, 0, 2, 1, Direction::LEFT));
+        transition_function.add_transition(Transition::new_params(2, 1, 0, 1, Direction::LEFT));
+
+        let transition_function_json = serde_json::to_string(&transition_function).unwrap();
+        let transition_function_deserialized: TransitionFunction =
+            serde_json::from_str(&transition_function_json).unwrap();
+
+        assert_eq!(transition_function_deserialized, transition_function);
+        assert!(transition_function_json.contains(
+            r#"{"from_state":0,"from_symbol":0,"to_state":1,"to_symbol":1,"direction":"RIGHT"}"#
+        ));
+    }
+
+    #[test]
+    fn to_dot() {
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA1RH").unwrap();
+        let dot = transition_function.to_dot();
+
+        assert!(dot.starts_with("digraph"));
+        assert!(dot.contains("q0 [shape=circle, style=bold];"));
+        assert!(dot.contains("HALT [shape=doublecircle];"));
+        assert!(dot.contains("q0 -> q1 [label=\"0/1,R\"];"));
+        assert!(dot.contains("q1 -> HALT [label=\"1/1,R\"];"));
+    }
+
+    #[test]
+    fn validate_undefined_entry() {
+        let mut transition_function: TransitionFunction = TransitionFunction::new(2, 2);
+        transition_function
+            .decode("0,0,1,1,1|0,1,255,1,1|1,1,0,0,0".to_string())
+            .unwrap();
+
+        let report = transition_function.validate();
+
+        assert_eq!(report.undefined, vec![(1, 0)]);
+        assert_eq!(report.overwritten, vec![]);
+        assert_eq!(report.is_valid(), false);
+    }
+
+    #[test]
+    fn validate_overwritten_entry() {
+        let mut transition_function: TransitionFunction = TransitionFunction::new(2, 2);
+        transition_function
+            .decode("0,0,1,1,1|0,1,255,1,1|1,0,0,1,0|1,1,0,0,0|0,0,1,0,0".to_string())
+            .unwrap();
+
+        let report = transition_function.validate();
+
+        assert_eq!(report.undefined, vec![]);
+        assert_eq!(report.overwritten, vec![(0, 0)]);
+        assert_eq!(report.is_valid(), false);
+
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA1RH").unwrap();
+        assert_eq!(transition_function.validate().is_valid(), true);
+    }
+
+    #[test]
+    fn halt_state_many_states() {
+        let mut transition_function = TransitionFunction::new(102, 2);
+        transition_function.add_transition(Transition::new_params(0, 0, 101, 1, Direction::RIGHT));
+        transition_function.add_transition(Transition::new_params(
+            101,
+            0,
+            HALT_STATE,
+            1,
+            Direction::RIGHT,
+        ));
+
+        // state 101 is a real state, different from the halting state
+        assert!(matches!(
+            SpecialStates::transform(101),
+            SpecialStates::Default
+        ));
+        assert!(matches!(
+            SpecialStates::transform(HALT_STATE),
+            SpecialStates::StateHalt
+        ));
+        assert_eq!(transition_function.states_used(), 2);
+
+        let mut decoded = TransitionFunction::new(102, 2);
+        decoded.decode(transition_function.encode()).unwrap();
+        assert_eq!(decoded.transitions[&(0, 0)].0, 101);
+        assert_eq!(decoded.transitions[&(101, 0)].0, HALT_STATE);
+
+        let mut turing_machine = TuringMachine::new(transition_function);
+        turing_machine.execute(10);
+
+        assert_eq!(turing_machine.halted, true);
+        assert_eq!(turing_machine.steps, 2);
+    }
+
+    #[test]
+    fn mirrored() {
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA1RH").unwrap();
+        let mirror = transition_function.mirrored();
+
+        assert_eq!(mirror.to_standard_notation().unwrap(), "1LB1RB_1RA1LH");
+        for (key, transition) in transition_function.transitions.iter() {
+            assert_eq!(mirror.transitions[key].2, transition.2.opposite());
+        }
+
+        assert_eq!(mirror.mirrored(), transition_function);
+    }
+
+    #[test]
+    fn states_used() {
+        // state D is defined, but it is never entered
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LC_1LA0RC_1RH1LA_1RD0LD").unwrap();
+
+        assert_eq!(transition_function.number_of_states, 4);
+        assert_eq!(transition_function.states_used(), 3);
+
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA0LC_1RH1LD_1RD0RA").unwrap();
+        assert_eq!(transition_function.states_used(), 4);
+    }
+
+    #[test]
+    fn encode_cache_invalidated() {
+        let mut transition_function: TransitionFunction = TransitionFunction::new(2, 2);
+        transition_function.add_transition(Transition::new_params(0, 0, 1, 1, Direction::RIGHT));
+
+        assert_eq!(transition_function.encode(), "0,0,1,1,1");
+
+        // the cached encoding is kept by the clones
+        let transition_function_clone = transition_function.clone();
+        assert_eq!(transition_function_clone.encode(), "0,0,1,1,1");
+
+        transition_function.add_transition(Transition::new_params(1, 0, 0, 1, Direction::LEFT));
+        assert_eq!(transition_function.encode(), "0,0,1,1,1|1,0,0,1,0");
+
+        transition_function.remove_transition(0, 0);
+        assert_eq!(transition_function.encode(), "1,0,0,1,0");
+        assert_eq!(transition_function_clone.encode(), "0,0,1,1,1");
+    }
+
+    #[test]
+    fn display_table() {
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA---").unwrap();
+
+        let table = transition_function.to_string();
+        let lines: Vec<&str> = table.lines().collect();
+
+        // a header row and one line per state
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "    |   0   |   1  ");
+        assert_eq!(lines[1], "> A | 1 R B | 1 L B");
+        assert_eq!(lines[2], "  B | 1 L A |  --- ");
+
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA1RH").unwrap();
+        assert!(transition_function
+            .to_string()
+            .ends_with("  B | 1 L A | 1 R H"));
+    }
 }
@@ -0,0 +1,19 @@
+/// Result of validating a `TransitionFunction`
+/// ( see `TransitionFunction::validate` ).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ValidationReport {
+    /// (state, symbol) entries of the domain without a transition,
+    /// sorted ascending.
+    pub undefined: Vec<(u8, u8)>,
+    /// (state, symbol) entries whose transition was added more than
+    /// once, the last one overwriting the others, in the order detected.
+    pub overwritten: Vec<(u8, u8)>,
+}
+
+impl ValidationReport {
+    /// Checks if the transition function is deterministic
+    /// and complete: no entry is undefined or overwritten.
+    pub fn is_valid(&self) -> bool {
+        return self.undefined.is_empty() && self.overwritten.is_empty();
+    }
+}
@@ -5,7 +5,6 @@ use crate::filter::filter_compile::FilterCompile;
 use crate::turing_machine::direction::Direction;
 
 const DIRECTIONS: [Direction; 2] = [Direction::LEFT, Direction::RIGHT];
-const ALPHABET: [u8; 2] = [0, 1];
 
 pub struct Filter {
     pub tx_filtered_functions: Option<Sender<Vec<TransitionFunction>>>,
@@ -18,13 +17,14 @@ impl Filter {
         tx_filtered_functions: Sender<Vec<TransitionFunction>>,
         rx_unfiltered_functions: Receiver<Vec<TransitionFunction>>,
         number_of_states: u8,
+        number_of_symbols: u8,
     ) -> Self {
         Filter {
             tx_filtered_functions: Some(tx_filtered_functions),
             rx_unfiltered_functions: rx_unfiltered_functions,
             filter_compile: FilterCompile::new(
                 number_of_states as usize,
-                ALPHABET.len(),
+                number_of_symbols as usize,
                 DIRECTIONS.len(),
             ),
         }
@@ -0,0 +1,179 @@
+use crate::turing_machine::direction::Direction;
+use crate::turing_machine::turing_machine::TuringMachine;
+
+/// Number of consecutive bounces on the same side of the tape
+/// needed to decide whether the machine is a bouncer.
+const BOUNCES_TO_CHECK: usize = 4;
+
+pub struct FilterBouncer {
+    // side of the tape where it increased the last time
+    last_increase: Option<Direction>,
+    // leftmost `head_position` reached so far
+    leftmost: i64,
+    // (u64, u8) -> (step, state) of the bounces on the left side
+    bounces_left: Vec<(u64, u8)>,
+    // (u64, u8) -> (step, state) of the bounces on the right side
+    bounces_right: Vec<(u64, u8)>,
+}
+
+impl Default for FilterBouncer {
+    fn default() -> Self {
+        return FilterBouncer::new();
+    }
+}
+
+impl FilterBouncer {
+    pub fn new() -> Self {
+        return FilterBouncer {
+            last_increase: None,
+            leftmost: 0,
+            bounces_left: Vec::new(),
+            bounces_right: Vec::new(),
+        };
+    }
+
+    /// Given the current state of a `TuringMachine`, records its bounces,
+    /// the outermost turning points of the head: the moments when the tape
+    /// increased on one side, after it previously increased on the other side,
+    /// which means the head turned around and swept all the tape.
+    ///
+    /// A bouncer sweeps a region that grows linearly, so the time between
+    /// two consecutive bounces on the same side grows by a constant difference.
+    ///
+    /// If the last `BOUNCES_TO_CHECK` bounces on the same side happened in the
+    /// same state and the gaps between them grow by the same difference,
+    /// it means it is a bouncer and it will loop endlessly.
+    pub fn filter(&mut self, turing_machine: &TuringMachine) -> bool {
+        // if the tape did not increase in the last iteration,
+        // the filter is considered passed
+        if turing_machine.tape_increased == false {
+            return true;
+        }
+
+        // extract the direction in which the tape increased: to the left
+        // if the head reached a new leftmost coordinate, which does not
+        // depend on how the tape is extended
+        let direction;
+        if turing_machine.head_position < self.leftmost {
+            self.leftmost = turing_machine.head_position;
+            direction = Direction::LEFT;
+        } else {
+            direction = Direction::RIGHT;
+        }
+
+        // the head did not turn around since the last increase
+        if self.last_increase == Some(direction) {
+            return true;
+        }
+
+        self.last_increase = Some(direction);
+
+        let bounces = match direction {
+            Direction::LEFT => &mut self.bounces_left,
+            Direction::RIGHT => &mut self.bounces_right,
+        };
+
+        bounces.push((turing_machine.steps, turing_machine.current_state));
+
+        return !Self::check_bouncer(bounces);
+    }
+
+    /// Checks whether the last `BOUNCES_TO_CHECK` bounces on the same side
+    /// happened in the same state, with gaps that grow by a constant difference.
+    fn check_bouncer(bounces: &[(u64, u8)]) -> bool {
+        if bounces.len() < BOUNCES_TO_CHECK {
+            return false;
+        }
+
+        let last_bounces = &bounces[bounces.len() - BOUNCES_TO_CHECK..];
+
+        if last_bounces
+            .iter()
+            .any(|bounce| bounce.1 != last_bounces[0].1)
+        {
+            return false;
+        }
+
+        let gaps: Vec<u64> = last_bounces
+            .windows(2)
+            .map(|bounces| bounces[1].0 - bounces[0].0)
+            .collect();
+        let difference = gaps[1] as i64 - gaps[0] as i64;
+
+        if difference <= 0 {
+            return false;
+        }
+
+        return gaps
+            .windows(2)
+            .all(|gaps| gaps[1] as i64 - gaps[0] as i64 == difference);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::delta::transition_function::TransitionFunction;
+    use crate::filter::filter_runtime::{FilterRuntime, FilterRuntimeConfig, FilterRuntimeType};
+    use crate::turing_machine::turing_machine::TuringMachine;
+
+    use super::FilterBouncer;
+
+    /// 4-state bouncer, sweeping a block of 1s that grows
+    /// on both sides with every bounce.
+    const BOUNCER_MACHINE: &str = "1LC1RC_0RA1RH_0LD1RA_1LB1LD";
+
+    #[test]
+    fn filter_bouncer() {
+        let transition_function =
+            TransitionFunction::from_standard_notation(BOUNCER_MACHINE).unwrap();
+        let mut filter_bouncer: FilterBouncer = FilterBouncer::new();
+
+        // create the turing machines based on the transition function
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        let maximum_steps = 1000;
+
+        turing_machine.make_transition();
+
+        // execute the turing machine until it reaches the maximum
+        // number of steps OR it gets filtered out by the bouncer filter
+        while turing_machine.steps < maximum_steps {
+            if !(filter_bouncer.filter(&turing_machine)) {
+                break;
+            }
+
+            turing_machine.make_transition();
+        }
+
+        assert_eq!(turing_machine.halted, false);
+        assert_ne!(turing_machine.steps, maximum_steps);
+    }
+
+    #[test]
+    fn filter_runtime_bouncer() {
+        let transition_function =
+            TransitionFunction::from_standard_notation(BOUNCER_MACHINE).unwrap();
+        let mut filter_runtime: FilterRuntime = FilterRuntime::new(FilterRuntimeConfig::new());
+
+        // create the turing machines based on the transition function
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        let maximum_steps = 1000;
+        let mut filtered = FilterRuntimeType::None;
+
+        turing_machine.make_transition();
+
+        // none of the runtime filters applied before
+        // the bouncer filter should catch the machine
+        while turing_machine.steps < maximum_steps {
+            filtered = filter_runtime.filter_all(&turing_machine);
+
+            match filtered {
+                FilterRuntimeType::None => {}
+                _ => break,
+            }
+
+            turing_machine.make_transition();
+        }
+
+        assert!(matches!(filtered, FilterRuntimeType::Bouncer));
+    }
+}
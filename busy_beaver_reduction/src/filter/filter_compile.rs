@@ -1,20 +1,61 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::mpsc::Sender;
 
-use regex::Regex;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
-use crate::delta::transition_function::TransitionFunction;
+use crate::delta::transition::Transition;
+use crate::delta::transition_function::{Cell, TransitionFunction};
 use crate::turing_machine::special_states::SpecialStates;
 use log::info;
 
+/// Maximum number of canonical forms kept to deduplicate the transition
+/// functions across batches ( see `filter_existing_canonical_forms` ).
+const CANONICAL_FORMS_CAPACITY: usize = 1 << 22;
+
+/// Number of transition functions filtered by each compile filter,
+/// out of the `received` ones that were filtered in batches.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FilterCompileStats {
+    pub received: i64,
+    pub never_halters: i64,
+    pub never_outputers: i64,
+    pub mirror_reflections: i64,
+    pub unreachable_state_holders: i64,
+    pub undefined_starters: i64,
+    pub halt_placement_duplicates: i64,
+    pub canonical_form_duplicates: i64,
+}
+
+impl FilterCompileStats {
+    /// Gets the number of transition functions filtered by all the filters.
+    pub fn get_filtered(&self) -> i64 {
+        return self.never_halters
+            + self.never_outputers
+            + self.mirror_reflections
+            + self.unreachable_state_holders
+            + self.undefined_starters
+            + self.halt_placement_duplicates
+            + self.canonical_form_duplicates;
+    }
+}
+
 /// Implements filter techniques for `TransitionFunction`s that
 /// have been `fully generated`, a.k.a their domain of definition
 /// is fully completed.
 pub struct FilterCompile {
-    pub turing_machines_templates: Vec<Vec<(Regex, u8, u8)>>,
+    /// whether `filter_batch` filters out the halt placement duplicates,
+    /// on by default ( see `set_halt_placement` )
+    halt_placement: bool,
+    canonical_forms: HashSet<Vec<Cell>>,
     turing_machines_size: i64,
+    received: i64,
     never_halters: i64,
     never_outputers: i64,
+    mirror_reflections: i64,
+    unreachable_state_holders: i64,
+    undefined_starters: i64,
+    halt_placement_duplicates: i64,
+    canonical_form_duplicates: i64,
 }
 
 impl FilterCompile {
@@ -22,57 +63,128 @@ impl FilterCompile {
         let maximum_possibilites_for_entry =
             alphabet_size * directions_size * (number_of_states + 1);
         let maximum_entries = number_of_states * alphabet_size;
-        let turing_machines_size = maximum_possibilites_for_entry.pow(maximum_entries as u32);
+        // saturate instead of overflowing for larger numbers of states
+        let turing_machines_size =
+            (maximum_possibilites_for_entry as i64).saturating_pow(maximum_entries as u32);
 
         return FilterCompile {
-            turing_machines_templates: Vec::new(),
-            turing_machines_size: turing_machines_size as i64,
+            halt_placement: true,
+            canonical_forms: HashSet::new(),
+            turing_machines_size: turing_machines_size,
+            received: 0,
             never_halters: 0,
             never_outputers: 0,
+            mirror_reflections: 0,
+            unreachable_state_holders: 0,
+            undefined_starters: 0,
+            halt_placement_duplicates: 0,
+            canonical_form_duplicates: 0,
         };
     }
 
+    /// Sets whether `filter_batch` filters out the transition functions that
+    /// only differ in their halt placement before the canonical forms
+    /// ( see `filter_halt_placement` ), which it does by default.
+    ///
+    /// The generation in Tree Normal Form ( `GenerationAlgorithm::Tnf` ) does
+    /// not need it, since it never generates these duplicates.
+    pub fn set_halt_placement(&mut self, halt_placement: bool) {
+        self.halt_placement = halt_placement;
+    }
+
     /// Creates a new thread were all the `TransitionFunction` from the `Vec`
     /// will be filtered.
     ///
-    /// Returns the filtered `Vec`.
+    /// Sends the filtered `Vec` through the channel.
     pub fn filter(
         &mut self,
-        mut transition_functions: Vec<TransitionFunction>,
+        transition_functions: Vec<TransitionFunction>,
         tx: Sender<Vec<TransitionFunction>>,
     ) {
-        transition_functions
-            .retain(|transition_function| self.filter_all(transition_function) == true);
-
-        // transition_functions = self.filter_existing_templates(transition_functions);
+        let transition_functions = self.filter_batch(transition_functions);
 
         // send the filtered transition functions
         // through the channel
         tx.send(transition_functions).unwrap();
     }
 
+    /// Filters a batch of `TransitionFunction`s, keeping the ones that passed
+    /// all the filters and that do not behave in the same way as one seen
+    /// before ( see `filter_existing_canonical_forms` ), after the cheaper
+    /// pass over their halt placements, if it is enabled ( see `set_halt_placement` ).
+    ///
+    /// Returns the filtered `Vec`.
+    pub fn filter_batch(
+        &mut self,
+        mut transition_functions: Vec<TransitionFunction>,
+    ) -> Vec<TransitionFunction> {
+        self.received += transition_functions.len() as i64;
+
+        transition_functions
+            .retain(|transition_function| self.filter_all(transition_function) == true);
+
+        if self.halt_placement == true {
+            transition_functions = self.filter_halt_placement(transition_functions);
+        }
+
+        transition_functions = self.filter_existing_canonical_forms(transition_functions);
+
+        return transition_functions;
+    }
+
     /// Applies all filters of the `FilterCompile` struct to the provided
     /// `TransitionFunction` and returns true if they were `all` passed.
     pub fn filter_all(&mut self, transition_function: &TransitionFunction) -> bool {
+        if Self::filter_undefined_start_transition(transition_function) == false {
+            self.undefined_starters += 1;
+            return false;
+        }
+
         if Self::filter_no_symbol_writing(transition_function) == false {
             self.never_outputers += 1;
             return false;
         }
 
+        if Self::filter_no_symbol_writing_from_blank_tape(transition_function) == false {
+            self.never_outputers += 1;
+            return false;
+        }
+
         if Self::filter_no_moves_to_halting_state(transition_function) == false {
             self.never_halters += 1;
             return false;
         }
 
+        if Self::filter_unreachable_states(transition_function) == false {
+            self.unreachable_state_holders += 1;
+            return false;
+        }
+
+        if Self::filter_mirror_symmetry(transition_function) == false {
+            self.mirror_reflections += 1;
+            return false;
+        }
+
         return true;
     }
 
+    /// Check if the transition from the start state, reading
+    /// the blank symbol, is defined.
+    ///
+    /// Otherwise, the machine halts on its very first step, started on
+    /// the blank tape, so running it would only waste a run slot.
+    fn filter_undefined_start_transition(transition_function: &TransitionFunction) -> bool {
+        return transition_function
+            .transitions()
+            .contains_key(&(SpecialStates::StateStart.value(), 0));
+    }
+
     /// Check if there is at least one transition that will
     /// go to the halting state.
     fn filter_no_moves_to_halting_state(transition_function: &TransitionFunction) -> bool {
         // iterate and check for every transition if it goes
         // to the halting state, and if at least one does, the filter is passed
-        for transition in transition_function.transitions.clone() {
+        for transition in transition_function.transitions().clone() {
             let transition_next = transition.1;
             let transition_next_state = transition_next.0;
 
@@ -87,7 +199,7 @@ impl FilterCompile {
     /// Check if there is at least one transition that will
     /// write a `1` symbol on the tape.
     fn filter_no_symbol_writing(transition_function: &TransitionFunction) -> bool {
-        for transition in transition_function.transitions.clone() {
+        for transition in transition_function.transitions().clone() {
             let transition_next = transition.1;
             let transition_next_symbol = transition_next.1;
 
@@ -99,143 +211,259 @@ impl FilterCompile {
         return false;
     }
 
-    /// Filters out Transition Functions that behave in the same way
-    /// with another Transition Function that already exists in the
-    /// `templates` vector.
-    ///
-    /// Two Transition Functions behave in the same way in the case when
-    /// by interchanging some states of one of them, we get the other
-    /// Transition Function.
-    ///
-    /// ### Example
-    /// f: (2, 1) -> (3, 1, R)
-    /// f: (3, 0) -> (2, 1, L)
-    ///
-    /// g: (3, 1) -> (2, 1, R)
-    /// g: (2, 0) -> (3, 1, L)
+    /// Check if the machine writes a symbol other than the blank one
+    /// when it is started on the blank tape, before it halts.
     ///
-    /// If we interchange appearences of states `2` and `3` for transition
-    /// function g, we get f.
-    fn filter_existing_templates(
-        &mut self,
-        mut transition_functions: Vec<TransitionFunction>,
-    ) -> Vec<TransitionFunction> {
-        let mut transition_functions_to_remove: Vec<usize> = Vec::new();
+    /// While only blanks were written, the head always reads a blank,
+    /// so the execution only depends on the current state: it follows
+    /// the transitions for the blank symbol, from the start state, until:
+    /// - a non-blank symbol is written, so the filter is passed
+    /// - a transition is undefined, which halts writing a `1`, so it is passed
+    /// - it halts writing a blank, or a state repeats, in which case
+    /// the machine never writes anything and its score is 0
+    fn filter_no_symbol_writing_from_blank_tape(transition_function: &TransitionFunction) -> bool {
+        let mut states_visited: HashSet<u8> = HashSet::new();
+        let mut state = SpecialStates::StateStart.value();
 
-        for index in 0..transition_functions.len() {
-            let filter = self.filter_against_templates(&transition_functions[index]);
+        while states_visited.insert(state) == true {
+            let Some(transition) = transition_function.transitions().get(&(state, 0)) else {
+                return true;
+            };
 
-            // if the filter was passed, it means it is a new configuration
-            // of transition function, add it to the templates
-            if filter == true {
-                let new_template = FilterCompile::retrieve_template(&transition_functions[index]);
-                self.turing_machines_templates.push(new_template);
+            if transition.1 != 0 {
+                return true;
             }
-            // otheriwse, keep the index in a vector
-            // in order to delete this transition function
-            // after filtering all of them
-            else {
-                transition_functions_to_remove.push(index);
+
+            if transition.0 == SpecialStates::StateHalt.value() {
+                return false;
             }
-        }
 
-        for index in transition_functions_to_remove {
-            transition_functions.remove(index);
+            state = transition.0;
         }
 
-        return transition_functions;
+        return false;
     }
 
-    /// Check whether a transition function already has
-    /// an equivalent template which behaves in the same way
-    fn filter_against_templates(&mut self, transition_function: &TransitionFunction) -> bool {
-        for template in self.turing_machines_templates.iter() {
-            let mut template_matched: bool = true;
-            let mut transition_function_encoded = transition_function.encode();
-            // holds the mapping of the state of the template
-            // to the states of the current transition,
-            // if at any point this mapping is broken, it means it does
-            // not respect the current template
-            let mut states_mapping: HashMap<u8, u8> = HashMap::new();
-
-            for transition_regex in template {
-                // if the current regex  does not match the encoding,
-                // this template cannot be matched
-                if !transition_regex.0.is_match(&transition_function_encoded) {
-                    template_matched = false;
-                    break;
-                }
+    /// Check if every state of the transition function can be
+    /// reached from the start state.
+    ///
+    /// A state that is never entered does not influence the behaviour
+    /// of the machine, so it is equivalent to one with fewer states.
+    /// Every state is entered through all of its symbols, so
+    /// the search follows the transitions for each symbol.
+    fn filter_unreachable_states(transition_function: &TransitionFunction) -> bool {
+        let mut states_reached: HashSet<u8> = HashSet::new();
+        let mut states_to_visit: Vec<u8> = vec![SpecialStates::StateStart.value()];
+
+        states_reached.insert(SpecialStates::StateStart.value());
 
-                // extract the states from the transition
-                let Some(states) = transition_regex.0.captures(&transition_function_encoded) else {
+        while let Some(state) = states_to_visit.pop() {
+            for symbol in 0..transition_function.number_of_symbols {
+                let Some(transition) = transition_function.transitions().get(&(state, symbol))
+                else {
                     continue;
                 };
-                let from_state = states[1].as_bytes()[0];
-                let to_state = states[2].as_bytes()[0];
-
-                // check if the states from the template exist in the
-                // states mapping; if they do, check if they are in correlance
-                // with the mapping
-                // check for from state
-                if states_mapping.contains_key(&transition_regex.1) {
-                    let state_mapped = states_mapping.get(&transition_regex.1).unwrap();
-
-                    if *state_mapped != from_state {
-                        template_matched = false;
-                        break;
-                    }
-                } else {
-                    states_mapping.insert(transition_regex.1, from_state);
-                }
 
-                // check for to state
-                if states_mapping.contains_key(&transition_regex.2) {
-                    let state_mapped = states_mapping.get(&transition_regex.2).unwrap();
+                // the halting state does not need to be reached
+                if transition.0 == SpecialStates::StateHalt.value() {
+                    continue;
+                }
 
-                    if *state_mapped != to_state {
-                        template_matched = false;
-                        break;
-                    }
-                } else {
-                    states_mapping.insert(transition_regex.2, to_state);
+                if states_reached.insert(transition.0) == true {
+                    states_to_visit.push(transition.0);
                 }
+            }
+        }
+
+        return states_reached.len() == transition_function.number_of_states as usize;
+    }
+
+    /// Check if the transition function is the canonical one out of
+    /// itself and its mirror, the transition function obtained by
+    /// interchanging LEFT and RIGHT in every transition.
+    ///
+    /// The mirror behaves in the same way, on a reflected tape,
+    /// so only the one with the lexicographically smaller
+    /// standard notation passes the filter, comparing their cells
+    /// ( see `TransitionFunction::cells` ).
+    fn filter_mirror_symmetry(transition_function: &TransitionFunction) -> bool {
+        let mirror = Self::get_mirror(transition_function);
+
+        return transition_function.cells() <= mirror.cells();
+    }
+
+    /// Gets the mirror of the transition function, in which
+    /// every transition moves in the opposite direction.
+    ///
+    /// The transitions into the halting state keep their direction,
+    /// because the last move does not change the behaviour, and
+    /// they are always generated moving to the right.
+    fn get_mirror(transition_function: &TransitionFunction) -> TransitionFunction {
+        let mut mirror = TransitionFunction::with_input_alphabet(
+            transition_function.number_of_states,
+            transition_function.input_alphabet_size(),
+            transition_function.tape_alphabet_size(),
+        );
 
-                // after using the regex for extracting information
-                // about a transition from the transition function, delete
-                // the transition from the encoding to prevent it from being
-                // picked up again by an identical regex
-                transition_function_encoded = transition_regex
-                    .0
-                    .replace_all(transition_function_encoded.as_str(), "")
-                    .into_owned();
+        for transition in transition_function.transitions().iter() {
+            let mut transition = Transition::get_from_hashmap(transition);
+
+            if transition.to_state != SpecialStates::StateHalt.value() {
+                transition.direction = transition.direction.opposite();
             }
 
-            // if the template matched, it means it did not
-            // pass the filter, return false
-            if template_matched == true {
-                return false;
+            mirror.add_transition(transition);
+        }
+
+        return mirror;
+    }
+
+    /// Filters out Transition Functions that differ from another Transition
+    /// Function of the same batch only in which state carries the halting
+    /// transition, as a cheaper pass before the canonical forms
+    /// ( see `filter_existing_canonical_forms` ).
+    ///
+    /// ### Example
+    /// f: 1RB1LC_1LA1RH_1RB0LA
+    ///
+    /// g: 1RC1LB_1RC0LA_1LA1RH
+    ///
+    /// The halting transition is carried by `B` in f and by `C` in g,
+    /// and interchanging `B` and `C` in g gives f.
+    ///
+    /// Both of them have the halting transition moved to the last state
+    /// ( see `get_halt_placement_form` ), a single interchange of two states,
+    /// instead of a search through all of them, so only some of the duplicates
+    /// are found, e.g. all of them for 3 states, and the rest are left to the
+    /// canonical forms, which keep the forms of the previous batches.
+    ///
+    /// Only the forms of the current batch are kept, so
+    /// the memory is bounded by the size of the batch.
+    pub fn filter_halt_placement(
+        &mut self,
+        transition_functions: Vec<TransitionFunction>,
+    ) -> Vec<TransitionFunction> {
+        let mut halt_placements: HashSet<Vec<Cell>> = HashSet::new();
+        let mut filtered: Vec<TransitionFunction> = Vec::with_capacity(transition_functions.len());
+
+        // keep only the first transition function of every form
+        for transition_function in transition_functions {
+            if halt_placements.insert(Self::get_halt_placement_form(&transition_function)) == true {
+                filtered.push(transition_function);
+            } else {
+                self.halt_placement_duplicates += 1;
             }
         }
 
-        return true;
+        return filtered;
     }
 
-    /// Retrieve a regex for each transition in a transition function,
-    /// that will extract `from state` and `to state` from another
-    /// transition function that is possible to behave in the same way.
-    fn retrieve_template(transition_function: &TransitionFunction) -> Vec<(Regex, u8, u8)> {
-        let mut template: Vec<(Regex, u8, u8)> = Vec::new();
+    /// Gets the cells of the transition function in which the first state
+    /// carrying a halting transition is interchanged with the last state.
+    ///
+    /// The start state is never interchanged, since the execution begins in it,
+    /// so the behaviour is the same; if it is the only one carrying a halting
+    /// transition, the cells of the transition function are returned.
+    fn get_halt_placement_form(transition_function: &TransitionFunction) -> Vec<Cell> {
+        let number_of_states = transition_function.number_of_states;
+        let halt_carrier = (1..number_of_states).find(|&state| {
+            (0..transition_function.number_of_symbols).any(|symbol| {
+                transition_function
+                    .transitions()
+                    .get(&(state, symbol))
+                    .is_some_and(|transition| transition.0 == SpecialStates::StateHalt.value())
+            })
+        });
+
+        let Some(halt_carrier) = halt_carrier else {
+            return transition_function.cells();
+        };
+
+        let mut relabeling: Vec<u8> = (0..number_of_states).collect();
+        relabeling.swap(halt_carrier as usize, number_of_states as usize - 1);
+
+        return transition_function.relabeled(&relabeling).cells();
+    }
+
+    /// Filters out Transition Functions that behave in the same way
+    /// with another Transition Function that was already seen.
+    ///
+    /// Two Transition Functions behave in the same way in the case when
+    /// by interchanging some states of one of them, other than the start
+    /// state, we get the other Transition Function.
+    ///
+    /// ### Example
+    /// f: (2, 1) -> (3, 1, R)
+    /// f: (3, 0) -> (2, 1, L)
+    ///
+    /// g: (3, 1) -> (2, 1, R)
+    /// g: (2, 0) -> (3, 1, L)
+    ///
+    /// If we interchange appearences of states `2` and `3` for transition
+    /// function g, we get f, so both of them have the same canonical
+    /// form ( see `get_canonical_form` ).
+    ///
+    /// The canonical forms do not depend on each other, so they
+    /// are computed in parallel, and only the deduplication is sequential.
+    ///
+    /// At most `CANONICAL_FORMS_CAPACITY` canonical forms are kept, after which
+    /// they are forgotten, so a duplicate of a transition function from an earlier
+    /// batch may pass, but a transition function is never filtered wrongly.
+    pub fn filter_existing_canonical_forms(
+        &mut self,
+        transition_functions: Vec<TransitionFunction>,
+    ) -> Vec<TransitionFunction> {
+        let canonical_forms: Vec<Vec<Cell>> = transition_functions
+            .par_iter()
+            .map(Self::get_canonical_form)
+            .collect();
+
+        let mut filtered: Vec<TransitionFunction> = Vec::with_capacity(transition_functions.len());
 
-        for (key, value) in &transition_function.transitions {
-            let transition_regex = Regex::new(
-                format!(r"(\d),{},(\d),{},{}", key.1, value.1, value.2.value()).as_str(),
-            )
-            .unwrap();
-            // add the pair (regex, from state, to state) into the list
-            template.push((transition_regex, key.0, value.0));
+        // keep only the first transition function of every canonical form,
+        // including the ones seen in the previous batches
+        for (transition_function, canonical_form) in
+            transition_functions.into_iter().zip(canonical_forms)
+        {
+            if self.canonical_forms.len() >= CANONICAL_FORMS_CAPACITY {
+                self.canonical_forms.clear();
+            }
+
+            if self.canonical_forms.insert(canonical_form) == true {
+                filtered.push(transition_function);
+            } else {
+                self.canonical_form_duplicates += 1;
+            }
         }
 
-        return template;
+        return filtered;
+    }
+
+    /// Gets the canonical form of a transition function: its cells
+    /// ( see `TransitionFunction::cells` ), relabeled in the order its
+    /// states are discovered from the start state ( see `TransitionFunction::get_relabeling` ).
+    ///
+    /// The start state is never relabeled, since the execution begins in it,
+    /// so two transition functions have the same canonical form if and only
+    /// if they behave in the same way, for any number of states.
+    fn get_canonical_form(transition_function: &TransitionFunction) -> Vec<Cell> {
+        let (relabeling, _) = transition_function.get_relabeling(SpecialStates::StateStart.value());
+
+        return transition_function.relabeled(&relabeling).cells();
+    }
+
+    /// Gets the number of transition functions filtered by each filter so far.
+    pub fn statistics(&self) -> FilterCompileStats {
+        return FilterCompileStats {
+            received: self.received,
+            never_halters: self.never_halters,
+            never_outputers: self.never_outputers,
+            mirror_reflections: self.mirror_reflections,
+            unreachable_state_holders: self.unreachable_state_holders,
+            undefined_starters: self.undefined_starters,
+            halt_placement_duplicates: self.halt_placement_duplicates,
+            canonical_form_duplicates: self.canonical_form_duplicates,
+        };
     }
 
     /// Display the number of Turing machines that was filtered
@@ -246,7 +474,33 @@ impl FilterCompile {
         let never_outpuers_percentage =
             self.never_outputers as f64 * 100.0 / self.turing_machines_size as f64;
 
-        let total = never_halters_percentage + never_outpuers_percentage;
+        let mirror_reflections_percentage =
+            self.mirror_reflections as f64 * 100.0 / self.turing_machines_size as f64;
+
+        let unreachable_state_holders_percentage =
+            self.unreachable_state_holders as f64 * 100.0 / self.turing_machines_size as f64;
+
+        let undefined_starters_percentage =
+            self.undefined_starters as f64 * 100.0 / self.turing_machines_size as f64;
+
+        let halt_placement_duplicates_percentage =
+            self.halt_placement_duplicates as f64 * 100.0 / self.turing_machines_size as f64;
+
+        let canonical_form_duplicates_percentage =
+            self.canonical_form_duplicates as f64 * 100.0 / self.turing_machines_size as f64;
+
+        let total = never_halters_percentage
+            + never_outpuers_percentage
+            + mirror_reflections_percentage
+            + unreachable_state_holders_percentage
+            + undefined_starters_percentage
+            + halt_placement_duplicates_percentage
+            + canonical_form_duplicates_percentage;
+
+        info!(
+            "Filtered a total of undefined starters: {:.2}%",
+            undefined_starters_percentage
+        );
 
         info!(
             "Filtered a total of never halters: {:.2}%",
@@ -258,6 +512,26 @@ impl FilterCompile {
             never_outpuers_percentage
         );
 
+        info!(
+            "Filtered a total of unreachable state holders: {:.2}%",
+            unreachable_state_holders_percentage
+        );
+
+        info!(
+            "Filtered a total of mirror reflections: {:.2}%",
+            mirror_reflections_percentage
+        );
+
+        info!(
+            "Filtered a total of halt placement duplicates: {:.2}%",
+            halt_placement_duplicates_percentage
+        );
+
+        info!(
+            "Filtered a total of canonical form duplicates: {:.2}%",
+            canonical_form_duplicates_percentage
+        );
+
         info!(
             "Filtered a total of {:.2}% Turing machines with compile filters.",
             total
@@ -268,27 +542,49 @@ impl FilterCompile {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::turing_machine::special_states::HALT_STATE;
     use crate::{delta::transition::Transition, turing_machine::direction::Direction};
 
     #[test]
-    fn filter_no_moves_to_halting_state() {
-        let mut transition_function: TransitionFunction = TransitionFunction::new(2, 2);
-
-        transition_function.add_transition(Transition {
-            from_state: 0,
-            from_symbol: 0,
-            to_state: 1,
-            to_symbol: 0,
-            direction: Direction::RIGHT,
-        });
+    fn filter_undefined_start_transition() {
+        // every transition is defined, except for (0, 0)
+        let transition_function = TransitionFunction::from_transitions(
+            2,
+            2,
+            &[
+                Transition::new_params(0, 1, 1, 1, Direction::RIGHT),
+                Transition::new_params(1, 0, 0, 1, Direction::RIGHT),
+                Transition::new_params(1, 1, HALT_STATE, 1, Direction::RIGHT),
+            ],
+        );
 
-        transition_function.add_transition(Transition {
-            from_state: 1,
-            from_symbol: 0,
-            to_state: 0,
-            to_symbol: 0,
-            direction: Direction::LEFT,
-        });
+        assert_eq!(
+            FilterCompile::filter_undefined_start_transition(&transition_function),
+            false
+        );
+
+        let mut filter_compile = FilterCompile::new(2, 2, 2);
+        assert_eq!(filter_compile.filter_all(&transition_function), false);
+        assert_eq!(filter_compile.undefined_starters, 1);
+
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA1RH").unwrap();
+        assert_eq!(
+            FilterCompile::filter_undefined_start_transition(&transition_function),
+            true
+        );
+    }
+
+    #[test]
+    fn filter_no_moves_to_halting_state() {
+        let transition_function = TransitionFunction::from_transitions(
+            2,
+            2,
+            &[
+                Transition::new_params(0, 0, 1, 0, Direction::RIGHT),
+                Transition::new_params(1, 0, 0, 0, Direction::LEFT),
+            ],
+        );
 
         let filter_result = FilterCompile::filter_no_moves_to_halting_state(&transition_function);
         assert_eq!(filter_result, false);
@@ -296,54 +592,208 @@ mod tests {
 
     #[test]
     fn filter_no_symbol_writing() {
-        let mut transition_function: TransitionFunction = TransitionFunction::new(2, 2);
-
-        transition_function.add_transition(Transition {
-            from_state: 0,
-            from_symbol: 0,
-            to_state: 1,
-            to_symbol: 0,
-            direction: Direction::RIGHT,
-        });
-
-        transition_function.add_transition(Transition {
-            from_state: 1,
-            from_symbol: 0,
-            to_state: 0,
-            to_symbol: 0,
-            direction: Direction::RIGHT,
-        });
+        let transition_function = TransitionFunction::from_transitions(
+            2,
+            2,
+            &[
+                Transition::new_params(0, 0, 1, 0, Direction::RIGHT),
+                Transition::new_params(1, 0, 0, 0, Direction::RIGHT),
+            ],
+        );
 
         let filter_result = FilterCompile::filter_no_symbol_writing(&transition_function);
         assert_eq!(filter_result, false);
     }
 
     #[test]
-    fn filter_against_templates() {
+    fn filter_no_symbol_writing_from_blank_tape() {
+        // the transitions writing a 1 are only used when reading a 1,
+        // so from the blank tape it halts after 2 steps with a score of 0
+        let transition_function =
+            TransitionFunction::from_standard_notation("0RB1RH_0RH1LA").unwrap();
+
+        assert_eq!(
+            FilterCompile::filter_no_symbol_writing(&transition_function),
+            true
+        );
+        assert_eq!(
+            FilterCompile::filter_no_symbol_writing_from_blank_tape(&transition_function),
+            false
+        );
+
+        // cycles through the states, only writing blanks
+        let transition_function =
+            TransitionFunction::from_standard_notation("0RB1RH_0LA1LA").unwrap();
+        assert_eq!(
+            FilterCompile::filter_no_symbol_writing_from_blank_tape(&transition_function),
+            false
+        );
+
+        let transition_function =
+            TransitionFunction::from_standard_notation("0RB1RH_1LA0RH").unwrap();
+        assert_eq!(
+            FilterCompile::filter_no_symbol_writing_from_blank_tape(&transition_function),
+            true
+        );
+    }
+
+    #[test]
+    fn filter_unreachable_states() {
+        // state C is only entered from itself
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA1RH_1RC0LC").unwrap();
+
+        assert_eq!(
+            FilterCompile::filter_unreachable_states(&transition_function),
+            false
+        );
+
+        let mut filter_compile = FilterCompile::new(3, 2, 2);
+        assert_eq!(filter_compile.filter_all(&transition_function), false);
+        assert_eq!(filter_compile.unreachable_state_holders, 1);
+
+        // state C is entered only after reading a 1 in state B
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA1RC_1RH0LC").unwrap();
+
+        assert_eq!(
+            FilterCompile::filter_unreachable_states(&transition_function),
+            true
+        );
+    }
+
+    #[test]
+    fn filter_halt_placement() {
+        // the halting transition is carried by B, respectively by C
+        let transition_function_01 =
+            TransitionFunction::from_standard_notation("1RB1LC_1LA1RH_1RB0LA").unwrap();
+        let transition_function_02 =
+            TransitionFunction::from_standard_notation("1RC1LB_1RC0LA_1LA1RH").unwrap();
+        // the same halt placement, but a different transition
+        let transition_function_03 =
+            TransitionFunction::from_standard_notation("1RB1LC_1LA1RH_1RB1LA").unwrap();
+
+        let mut filter_compile = FilterCompile::new(3, 2, 2);
+        let filtered = filter_compile.filter_halt_placement(vec![
+            transition_function_01.clone(),
+            transition_function_02.clone(),
+        ]);
+
+        assert_eq!(filtered.len(), 1);
+        assert!(filtered[0] == transition_function_01);
+        assert_eq!(filter_compile.halt_placement_duplicates, 1);
+
+        // only the forms of the current batch are kept
+        let filtered = filter_compile
+            .filter_halt_placement(vec![transition_function_02, transition_function_03.clone()]);
+
+        assert_eq!(filtered.len(), 2);
+        assert!(filtered[1] == transition_function_03);
+        assert_eq!(filter_compile.halt_placement_duplicates, 1);
+    }
+
+    #[test]
+    fn filter_batch_halt_placement() {
+        // the halting transition is carried by B, respectively by C
+        let transition_functions = vec![
+            TransitionFunction::from_standard_notation("1LB1RC_1RA1RH_1LB0RA").unwrap(),
+            TransitionFunction::from_standard_notation("1LC1RB_1LC0RA_1RA1RH").unwrap(),
+        ];
+
+        // filtered by the halt placement, before the canonical forms
+        let mut filter_compile = FilterCompile::new(3, 2, 2);
+        let filtered = filter_compile.filter_batch(transition_functions.clone());
+
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filter_compile.halt_placement_duplicates, 1);
+        assert_eq!(filter_compile.canonical_form_duplicates, 0);
+
+        // in another batch, it is filtered by the canonical forms
+        let filtered = filter_compile.filter_batch(vec![transition_functions[1].clone()]);
+
+        assert_eq!(filtered.len(), 0);
+        assert_eq!(filter_compile.canonical_form_duplicates, 1);
+
+        let mut filter_compile = FilterCompile::new(3, 2, 2);
+        filter_compile.set_halt_placement(false);
+        let filtered = filter_compile.filter_batch(transition_functions);
+
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filter_compile.halt_placement_duplicates, 0);
+        assert_eq!(filter_compile.canonical_form_duplicates, 1);
+
+        // with more states, interchanging B and C is only found by the canonical forms
+        let transition_function =
+            TransitionFunction::from_standard_notation("1LB1RC_1RA1LH_1LD0RA_0RC1LA").unwrap();
+        let transition_functions = vec![
+            transition_function.clone(),
+            transition_function.relabeled(&[0, 2, 1, 3]),
+        ];
+
+        assert_ne!(
+            FilterCompile::get_halt_placement_form(&transition_functions[0]),
+            FilterCompile::get_halt_placement_form(&transition_functions[1])
+        );
+
+        let mut filter_compile = FilterCompile::new(4, 2, 2);
+        let filtered = filter_compile.filter_batch(transition_functions);
+
+        assert_eq!(filtered, vec![transition_function]);
+        assert_eq!(filter_compile.halt_placement_duplicates, 0);
+        assert_eq!(filter_compile.canonical_form_duplicates, 1);
+    }
+
+    #[test]
+    fn filter_mirror_symmetry() {
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA1RH").unwrap();
+        let mirror = TransitionFunction::from_standard_notation("1LB1RB_1RA1RH").unwrap();
+
+        assert_eq!(FilterCompile::get_mirror(&transition_function), mirror);
+        assert_eq!(FilterCompile::get_mirror(&mirror), transition_function);
+
+        let transition_functions = vec![transition_function, mirror.clone()];
+        let mut filter_compile = FilterCompile::new(2, 2, 2);
+        let transition_functions_filtered: Vec<TransitionFunction> = transition_functions
+            .into_iter()
+            .filter(|transition_function| filter_compile.filter_all(transition_function))
+            .collect();
+
+        // only the one with the smaller standard notation survives
+        assert_eq!(transition_functions_filtered, vec![mirror]);
+        assert_eq!(filter_compile.mirror_reflections, 1);
+    }
+
+    #[test]
+    fn filter_existing_canonical_forms() {
         let mut transition_function_01: TransitionFunction = TransitionFunction::new(3, 3);
         let mut transition_function_02: TransitionFunction = TransitionFunction::new(3, 3);
         let mut transition_function_03: TransitionFunction = TransitionFunction::new(3, 3);
         let mut transition_function_04: TransitionFunction = TransitionFunction::new(3, 3);
 
         // initiate transition function 1
+        transition_function_01.add_transition(Transition::new_params(0, 0, 1, 1, Direction::RIGHT));
         transition_function_01.add_transition(Transition::new_params(1, 1, 2, 1, Direction::RIGHT));
         transition_function_01.add_transition(Transition::new_params(1, 0, 0, 1, Direction::LEFT));
         transition_function_01.add_transition(Transition::new_params(2, 1, 1, 1, Direction::LEFT));
         transition_function_01.add_transition(Transition::new_params(2, 0, 2, 0, Direction::RIGHT));
 
         // initiate transition function 2
+        transition_function_02.add_transition(Transition::new_params(0, 0, 2, 1, Direction::RIGHT));
         transition_function_02.add_transition(Transition::new_params(2, 1, 1, 1, Direction::RIGHT));
         transition_function_02.add_transition(Transition::new_params(2, 0, 0, 1, Direction::LEFT));
         transition_function_02.add_transition(Transition::new_params(1, 1, 2, 1, Direction::LEFT));
         transition_function_02.add_transition(Transition::new_params(1, 0, 1, 0, Direction::RIGHT));
 
         // initiate transition function 3
+        transition_function_03.add_transition(Transition::new_params(0, 0, 2, 1, Direction::RIGHT));
         transition_function_03.add_transition(Transition::new_params(2, 1, 1, 1, Direction::RIGHT));
         transition_function_03.add_transition(Transition::new_params(2, 0, 0, 1, Direction::LEFT));
         transition_function_03.add_transition(Transition::new_params(1, 1, 2, 1, Direction::LEFT));
         transition_function_03.add_transition(Transition::new_params(1, 0, 1, 0, Direction::LEFT));
 
         // initiate transition function 4
+        transition_function_04.add_transition(Transition::new_params(0, 0, 2, 1, Direction::RIGHT));
         transition_function_04.add_transition(Transition::new_params(2, 1, 1, 1, Direction::RIGHT));
         transition_function_04.add_transition(Transition::new_params(2, 0, 0, 0, Direction::LEFT));
         transition_function_04.add_transition(Transition::new_params(1, 1, 2, 1, Direction::LEFT));
@@ -357,7 +807,7 @@ mod tests {
         ];
         let mut filter_compile = FilterCompile::new(3, 3, 2);
         let transition_functions_filtered =
-            filter_compile.filter_existing_templates(transition_functions);
+            filter_compile.filter_existing_canonical_forms(transition_functions);
 
         assert_eq!(
             transition_functions_filtered.contains(&transition_function_01),
@@ -376,4 +826,283 @@ mod tests {
             true
         );
     }
+
+    #[test]
+    fn filter_existing_canonical_forms_parallel() {
+        let mut transition_function_01: TransitionFunction = TransitionFunction::new(3, 3);
+        let mut transition_function_02: TransitionFunction = TransitionFunction::new(3, 3);
+        let mut transition_function_03: TransitionFunction = TransitionFunction::new(3, 3);
+        let mut transition_function_04: TransitionFunction = TransitionFunction::new(3, 3);
+
+        // the same transition functions as in `filter_existing_canonical_forms`
+        transition_function_01.add_transition(Transition::new_params(0, 0, 1, 1, Direction::RIGHT));
+        transition_function_01.add_transition(Transition::new_params(1, 1, 2, 1, Direction::RIGHT));
+        transition_function_01.add_transition(Transition::new_params(1, 0, 0, 1, Direction::LEFT));
+        transition_function_01.add_transition(Transition::new_params(2, 1, 1, 1, Direction::LEFT));
+        transition_function_01.add_transition(Transition::new_params(2, 0, 2, 0, Direction::RIGHT));
+
+        transition_function_02.add_transition(Transition::new_params(0, 0, 2, 1, Direction::RIGHT));
+        transition_function_02.add_transition(Transition::new_params(2, 1, 1, 1, Direction::RIGHT));
+        transition_function_02.add_transition(Transition::new_params(2, 0, 0, 1, Direction::LEFT));
+        transition_function_02.add_transition(Transition::new_params(1, 1, 2, 1, Direction::LEFT));
+        transition_function_02.add_transition(Transition::new_params(1, 0, 1, 0, Direction::RIGHT));
+
+        transition_function_03.add_transition(Transition::new_params(0, 0, 2, 1, Direction::RIGHT));
+        transition_function_03.add_transition(Transition::new_params(2, 1, 1, 1, Direction::RIGHT));
+        transition_function_03.add_transition(Transition::new_params(2, 0, 0, 1, Direction::LEFT));
+        transition_function_03.add_transition(Transition::new_params(1, 1, 2, 1, Direction::LEFT));
+        transition_function_03.add_transition(Transition::new_params(1, 0, 1, 0, Direction::LEFT));
+
+        transition_function_04.add_transition(Transition::new_params(0, 0, 2, 1, Direction::RIGHT));
+        transition_function_04.add_transition(Transition::new_params(2, 1, 1, 1, Direction::RIGHT));
+        transition_function_04.add_transition(Transition::new_params(2, 0, 0, 0, Direction::LEFT));
+        transition_function_04.add_transition(Transition::new_params(1, 1, 2, 1, Direction::LEFT));
+        transition_function_04.add_transition(Transition::new_params(1, 0, 1, 0, Direction::RIGHT));
+
+        let transition_functions: Vec<TransitionFunction> = vec![
+            transition_function_01,
+            transition_function_02,
+            transition_function_03,
+            transition_function_04,
+        ];
+
+        // deduplicate sequentially, one transition function at a time
+        let mut canonical_forms: HashSet<Vec<Cell>> = HashSet::new();
+        let transition_functions_sequential: Vec<TransitionFunction> = transition_functions
+            .iter()
+            .filter(|transition_function| {
+                canonical_forms.insert(FilterCompile::get_canonical_form(transition_function))
+            })
+            .cloned()
+            .collect();
+
+        let mut filter_compile_parallel = FilterCompile::new(3, 3, 2);
+        let transition_functions_parallel =
+            filter_compile_parallel.filter_existing_canonical_forms(transition_functions);
+
+        assert_eq!(transition_functions_parallel.len(), 3);
+        assert_eq!(
+            transition_functions_parallel,
+            transition_functions_sequential
+        );
+        assert_eq!(filter_compile_parallel.canonical_form_duplicates, 1);
+    }
+
+    #[test]
+    fn filter_existing_canonical_forms_multiple_duplicates() {
+        let mut transition_function_01: TransitionFunction = TransitionFunction::new(3, 3);
+        let mut transition_function_02: TransitionFunction = TransitionFunction::new(3, 3);
+        let mut transition_function_03: TransitionFunction = TransitionFunction::new(3, 3);
+        let mut transition_function_04: TransitionFunction = TransitionFunction::new(3, 3);
+        let mut transition_function_05: TransitionFunction = TransitionFunction::new(3, 3);
+
+        // initiate transition function 1
+        transition_function_01.add_transition(Transition::new_params(0, 0, 1, 1, Direction::RIGHT));
+        transition_function_01.add_transition(Transition::new_params(1, 1, 2, 1, Direction::RIGHT));
+        transition_function_01.add_transition(Transition::new_params(1, 0, 0, 1, Direction::LEFT));
+        transition_function_01.add_transition(Transition::new_params(2, 1, 1, 1, Direction::LEFT));
+        transition_function_01.add_transition(Transition::new_params(2, 0, 2, 0, Direction::RIGHT));
+
+        // initiate transition function 2, equal to 1 with states 1 and 2 interchanged
+        transition_function_02.add_transition(Transition::new_params(0, 0, 2, 1, Direction::RIGHT));
+        transition_function_02.add_transition(Transition::new_params(2, 1, 1, 1, Direction::RIGHT));
+        transition_function_02.add_transition(Transition::new_params(2, 0, 0, 1, Direction::LEFT));
+        transition_function_02.add_transition(Transition::new_params(1, 1, 2, 1, Direction::LEFT));
+        transition_function_02.add_transition(Transition::new_params(1, 0, 1, 0, Direction::RIGHT));
+
+        // initiate transition function 3, equal to 1 with states 0 and 1 interchanged,
+        // which starts in another state, so it does not behave in the same way
+        transition_function_03.add_transition(Transition::new_params(1, 0, 0, 1, Direction::RIGHT));
+        transition_function_03.add_transition(Transition::new_params(0, 1, 2, 1, Direction::RIGHT));
+        transition_function_03.add_transition(Transition::new_params(0, 0, 1, 1, Direction::LEFT));
+        transition_function_03.add_transition(Transition::new_params(2, 1, 0, 1, Direction::LEFT));
+        transition_function_03.add_transition(Transition::new_params(2, 0, 2, 0, Direction::RIGHT));
+
+        // initiate transition function 4
+        transition_function_04.add_transition(Transition::new_params(0, 0, 2, 1, Direction::RIGHT));
+        transition_function_04.add_transition(Transition::new_params(2, 1, 1, 1, Direction::RIGHT));
+        transition_function_04.add_transition(Transition::new_params(2, 0, 0, 1, Direction::LEFT));
+        transition_function_04.add_transition(Transition::new_params(1, 1, 2, 1, Direction::LEFT));
+        transition_function_04.add_transition(Transition::new_params(1, 0, 1, 0, Direction::LEFT));
+
+        // initiate transition function 5
+        transition_function_05.add_transition(Transition::new_params(0, 0, 2, 1, Direction::RIGHT));
+        transition_function_05.add_transition(Transition::new_params(2, 1, 1, 1, Direction::RIGHT));
+        transition_function_05.add_transition(Transition::new_params(2, 0, 0, 0, Direction::LEFT));
+        transition_function_05.add_transition(Transition::new_params(1, 1, 2, 1, Direction::LEFT));
+        transition_function_05.add_transition(Transition::new_params(1, 0, 1, 0, Direction::RIGHT));
+
+        let transition_functions: Vec<TransitionFunction> = vec![
+            transition_function_01.clone(),
+            transition_function_02.clone(),
+            transition_function_03.clone(),
+            transition_function_04.clone(),
+            transition_function_05.clone(),
+        ];
+        let mut filter_compile = FilterCompile::new(3, 3, 2);
+        let transition_functions_filtered =
+            filter_compile.filter_existing_canonical_forms(transition_functions);
+
+        assert_eq!(
+            transition_functions_filtered,
+            vec![
+                transition_function_01,
+                transition_function_03,
+                transition_function_04,
+                transition_function_05
+            ]
+        );
+    }
+
+    #[test]
+    fn filter_existing_canonical_forms_start_state() {
+        // the BB(2) champion, halting after 6 steps with a score of 4
+        let transition_function_01 =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA1RH").unwrap();
+        // the same transition function with states A and B interchanged,
+        // halting after 3 steps with a score of 2
+        let transition_function_02 =
+            TransitionFunction::from_standard_notation("1LB1RH_1RA1LA").unwrap();
+
+        assert_ne!(
+            FilterCompile::get_canonical_form(&transition_function_01),
+            FilterCompile::get_canonical_form(&transition_function_02)
+        );
+
+        let mut filter_compile = FilterCompile::new(2, 2, 2);
+        let transition_functions_filtered = filter_compile.filter_existing_canonical_forms(vec![
+            transition_function_01.clone(),
+            transition_function_02.clone(),
+        ]);
+
+        assert_eq!(
+            transition_functions_filtered,
+            vec![transition_function_01, transition_function_02]
+        );
+    }
+
+    #[test]
+    fn filter_existing_canonical_forms_multi_digit_states() {
+        // a cycle through all the 11 states, halting from the last one
+        let mut transition_function_01: TransitionFunction = TransitionFunction::new(11, 2);
+        for state in 0..10 {
+            transition_function_01.add_transition(Transition::new_params(
+                state,
+                0,
+                state + 1,
+                1,
+                Direction::RIGHT,
+            ));
+            transition_function_01.add_transition(Transition::new_params(
+                state,
+                1,
+                0,
+                0,
+                Direction::LEFT,
+            ));
+        }
+        transition_function_01.add_transition(Transition::new_params(
+            10,
+            0,
+            0,
+            1,
+            Direction::RIGHT,
+        ));
+        transition_function_01.add_transition(Transition::new_params(
+            10,
+            1,
+            SpecialStates::StateHalt.value(),
+            1,
+            Direction::RIGHT,
+        ));
+
+        // the same transition function, with states 1 and 10 interchanged
+        let relabeling: Vec<u8> = vec![0, 10, 2, 3, 4, 5, 6, 7, 8, 9, 1];
+        let transition_function_02 = transition_function_01.relabeled(&relabeling);
+
+        // the same transition function, writing 0 before halting
+        let mut transition_function_03 = transition_function_01.clone();
+        transition_function_03.add_transition(Transition::new_params(
+            10,
+            1,
+            SpecialStates::StateHalt.value(),
+            0,
+            Direction::RIGHT,
+        ));
+
+        assert_ne!(transition_function_01, transition_function_02);
+        assert_eq!(
+            FilterCompile::get_canonical_form(&transition_function_01),
+            FilterCompile::get_canonical_form(&transition_function_02)
+        );
+
+        let mut filter_compile = FilterCompile::new(11, 2, 2);
+        let transition_functions_filtered = filter_compile.filter_existing_canonical_forms(vec![
+            transition_function_01.clone(),
+            transition_function_02,
+            transition_function_03.clone(),
+        ]);
+
+        assert_eq!(
+            transition_functions_filtered,
+            vec![transition_function_01, transition_function_03]
+        );
+    }
+
+    #[test]
+    fn filter_existing_canonical_forms_halting_state() {
+        let halt = SpecialStates::StateHalt.value();
+        let mut transition_function_01: TransitionFunction = TransitionFunction::new(3, 2);
+        let mut transition_function_02: TransitionFunction = TransitionFunction::new(3, 2);
+        let mut transition_function_03: TransitionFunction = TransitionFunction::new(3, 2);
+
+        // initiate transition function 1, halting from state 2
+        transition_function_01.add_transition(Transition::new_params(0, 0, 1, 1, Direction::RIGHT));
+        transition_function_01.add_transition(Transition::new_params(0, 1, 2, 1, Direction::LEFT));
+        transition_function_01.add_transition(Transition::new_params(1, 0, 0, 1, Direction::LEFT));
+        transition_function_01.add_transition(Transition::new_params(
+            2,
+            1,
+            halt,
+            1,
+            Direction::RIGHT,
+        ));
+
+        // initiate transition function 2, equal to 1 with states 1 and 2 interchanged
+        transition_function_02.add_transition(Transition::new_params(0, 0, 2, 1, Direction::RIGHT));
+        transition_function_02.add_transition(Transition::new_params(0, 1, 1, 1, Direction::LEFT));
+        transition_function_02.add_transition(Transition::new_params(2, 0, 0, 1, Direction::LEFT));
+        transition_function_02.add_transition(Transition::new_params(
+            1,
+            1,
+            halt,
+            1,
+            Direction::RIGHT,
+        ));
+
+        // initiate transition function 3, equal to 1 but moving to state 1 instead of halting
+        transition_function_03.add_transition(Transition::new_params(0, 0, 1, 1, Direction::RIGHT));
+        transition_function_03.add_transition(Transition::new_params(0, 1, 2, 1, Direction::LEFT));
+        transition_function_03.add_transition(Transition::new_params(1, 0, 0, 1, Direction::LEFT));
+        transition_function_03.add_transition(Transition::new_params(2, 1, 1, 1, Direction::RIGHT));
+
+        // the halting state is never relabeled
+        let relabeled = transition_function_01.relabeled(&[2, 0, 1]);
+        assert_eq!(
+            relabeled.transitions().get(&(1, 1)),
+            Some(&(halt, 1, Direction::RIGHT))
+        );
+
+        let mut filter_compile = FilterCompile::new(3, 2, 2);
+        let transition_functions_filtered = filter_compile.filter_existing_canonical_forms(vec![
+            transition_function_01.clone(),
+            transition_function_02,
+            transition_function_03.clone(),
+        ]);
+
+        assert_eq!(
+            transition_functions_filtered,
+            vec![transition_function_01, transition_function_03]
+        );
+    }
 }
@@ -0,0 +1,253 @@
+use std::collections::HashSet;
+
+use crate::delta::transition_function::TransitionFunction;
+use crate::turing_machine::direction::Direction;
+use crate::turing_machine::special_states::SpecialStates;
+use crate::turing_machine::turing_machine::TuringMachine;
+
+/// Number of steps the Turing machine has to run before the proof is
+/// attempted, so the cheaper filters get the chance to identify it first.
+const CTL_START_STEP: u64 = 1000;
+/// Largest length of the tape windows tried while building the proof.
+const MAXIMUM_DEPTH: usize = 4;
+/// Number of local configurations after which the proof is given up.
+const MAXIMUM_CONFIGURATIONS: usize = 10_000;
+
+/// Configuration of the Turing machine as seen around the head:
+/// (state, left window, head symbol, right window), where both
+/// windows are ordered starting from the cell closest to the head.
+type LocalConfiguration = (u8, Vec<u8>, u8, Vec<u8>);
+
+/// Closed Tape Language found for a Turing machine: a set of configurations
+/// that contains the initial one, is closed under the transitions
+/// and in which the halting transition is never applicable.
+///
+/// The tape language is regular, described by the windows of `depth` cells:
+/// a tape belongs to it if every `depth` consecutive cells on each side
+/// of the head form one of the `left_windows`, respectively `right_windows`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CtlProof {
+    pub depth: usize,
+    pub left_windows: HashSet<Vec<u8>>,
+    pub right_windows: HashSet<Vec<u8>>,
+    pub configurations: HashSet<LocalConfiguration>,
+}
+
+pub struct FilterCtl {
+    attempted: bool,
+}
+
+impl Default for FilterCtl {
+    fn default() -> Self {
+        return FilterCtl::new();
+    }
+}
+
+impl FilterCtl {
+    pub fn new() -> Self {
+        return FilterCtl { attempted: false };
+    }
+
+    /// Given the current state of a `TuringMachine`, tries to prove that it
+    /// never halts by building a Closed Tape Language for it ( see `prove` ).
+    ///
+    /// The proof does not depend on the current configuration, so it is
+    /// attempted only once, after the machine made `CTL_START_STEP` steps.
+    /// Being the most expensive filter, it is the last resort for the
+    /// machines that were not identified by the other filters.
+    pub fn filter(&mut self, turing_machine: &TuringMachine) -> bool {
+        if self.attempted == true || turing_machine.steps < CTL_START_STEP {
+            return true;
+        }
+
+        self.attempted = true;
+
+        return Self::prove(
+            &turing_machine.transition_function,
+            turing_machine.blank_symbol,
+        )
+        .is_none();
+    }
+
+    /// Tries to build a Closed Tape Language for the transition function,
+    /// with windows of 1 up to `MAXIMUM_DEPTH` cells ( see `prove_with_depth` ).
+    ///
+    /// Returns the first proof found, or `None` if it gave up.
+    pub fn prove(transition_function: &TransitionFunction, blank_symbol: u8) -> Option<CtlProof> {
+        for depth in 1..=MAXIMUM_DEPTH {
+            if let Some(proof) = Self::prove_with_depth(transition_function, blank_symbol, depth) {
+                return Some(proof);
+            }
+        }
+
+        return None;
+    }
+
+    /// Tries to build a Closed Tape Language in which each side of the tape
+    /// is described by the windows of `depth` cells that can appear on it.
+    ///
+    /// Starting from the blank tape, the local configurations are expanded
+    /// until a fixed point is reached:
+    /// - moving the head pushes the written symbol on one side, whose new
+    /// window is recorded as one that can appear on that side
+    /// - the window on the other side loses its first cell, and it is completed
+    /// with every recorded window that continues it
+    ///
+    /// Every window of consecutive cells on a side was, at some point, the window
+    /// next to the head, so the reachable configurations are all described.
+    /// If the halting transition is never applicable to them, or no transition
+    /// is missing, the machine never halts.
+    ///
+    /// Returns the proof, or `None` if a halting transition was reached
+    /// or there were too many configurations.
+    pub fn prove_with_depth(
+        transition_function: &TransitionFunction,
+        blank_symbol: u8,
+        depth: usize,
+    ) -> Option<CtlProof> {
+        let blank_window: Vec<u8> = vec![blank_symbol; depth];
+
+        let mut proof = CtlProof {
+            depth: depth,
+            left_windows: HashSet::from([blank_window.clone()]),
+            right_windows: HashSet::from([blank_window.clone()]),
+            configurations: HashSet::from([(
+                SpecialStates::StateStart.value(),
+                blank_window.clone(),
+                blank_symbol,
+                blank_window,
+            )]),
+        };
+
+        let mut changed = true;
+
+        while changed == true {
+            changed = false;
+
+            let configurations: Vec<LocalConfiguration> =
+                proof.configurations.iter().cloned().collect();
+
+            for (state, left, symbol, right) in configurations {
+                let (next_state, written, direction) =
+                    match transition_function.transitions().get(&(state, symbol)) {
+                        Some(&transition) => transition,
+                        None => return None,
+                    };
+
+                if next_state == SpecialStates::StateHalt.value() {
+                    return None;
+                }
+
+                // the side the head moves away from gets the written symbol
+                // pushed, while the side it moves towards loses its first cell
+                let (pushed, popped, pushed_windows, popped_windows) = match direction {
+                    Direction::LEFT => {
+                        (&right, &left, &mut proof.right_windows, &proof.left_windows)
+                    }
+                    Direction::RIGHT => {
+                        (&left, &right, &mut proof.left_windows, &proof.right_windows)
+                    }
+                };
+
+                let mut pushed_window: Vec<u8> = vec![written];
+                pushed_window.extend_from_slice(&pushed[..depth - 1]);
+                changed |= pushed_windows.insert(pushed_window.clone());
+
+                let continuations: Vec<Vec<u8>> = popped_windows
+                    .iter()
+                    .filter(|window| window[..depth - 1] == popped[1..])
+                    .cloned()
+                    .collect();
+
+                for continuation in continuations {
+                    let next_configuration = match direction {
+                        Direction::LEFT => {
+                            (next_state, continuation, popped[0], pushed_window.clone())
+                        }
+                        Direction::RIGHT => {
+                            (next_state, pushed_window.clone(), popped[0], continuation)
+                        }
+                    };
+
+                    changed |= proof.configurations.insert(next_configuration);
+                }
+
+                if proof.configurations.len() > MAXIMUM_CONFIGURATIONS {
+                    return None;
+                }
+            }
+        }
+
+        return Some(proof);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::filter_runtime::{FilterRuntime, FilterRuntimeConfig, FilterRuntimeType};
+
+    /// 3-state holdout, whose halting transition from state C reading a 1
+    /// is never applied: it does not halt, recur, cycle or bounce, so
+    /// the cheaper filters do not identify it, while CTL proves it using
+    /// windows of 3 cells ( the windows of 1 or 2 cells are not enough ).
+    const CTL_HOLDOUT: &str = "1RB0LC_0LA0RA_1LA1RH";
+
+    #[test]
+    fn prove_halting_machine() {
+        // BB(2) champion, that halts after 6 steps
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA1RH").unwrap();
+
+        assert!(FilterCtl::prove(&transition_function, 0).is_none());
+    }
+
+    #[test]
+    fn prove_ctl_holdout() {
+        let transition_function = TransitionFunction::from_standard_notation(CTL_HOLDOUT).unwrap();
+
+        assert!(FilterCtl::prove_with_depth(&transition_function, 0, 2).is_none());
+
+        let proof = FilterCtl::prove(&transition_function, 0).unwrap();
+        assert_eq!(proof.depth, 3);
+        assert!(proof.configurations.iter().all(|configuration| {
+            let transition = transition_function.transitions()[&(configuration.0, configuration.2)];
+            transition.0 != SpecialStates::StateHalt.value()
+        }));
+    }
+
+    #[test]
+    fn filter_ctl_holdout() {
+        let transition_function = TransitionFunction::from_standard_notation(CTL_HOLDOUT).unwrap();
+
+        // the cheaper filters do not identify the holdout
+        let mut filter_runtime = FilterRuntime::new(FilterRuntimeConfig::with_filters(vec![
+            FilterRuntimeType::ShortEscapee,
+            FilterRuntimeType::LongEscapee,
+            FilterRuntimeType::Cycler,
+            FilterRuntimeType::TranslatedCycler,
+            FilterRuntimeType::Recurrence,
+            FilterRuntimeType::Bouncer,
+        ]));
+        let mut turing_machine = TuringMachine::new(transition_function.clone());
+
+        turing_machine.make_transition();
+
+        while turing_machine.steps < 10 * CTL_START_STEP {
+            assert_eq!(
+                filter_runtime.filter_all(&turing_machine),
+                FilterRuntimeType::None
+            );
+            turing_machine.make_transition();
+        }
+
+        assert_eq!(turing_machine.halted, false);
+
+        // while the default configuration ends with the CTL proof
+        let mut turing_machine = TuringMachine::new(transition_function);
+        turing_machine.execute(10 * CTL_START_STEP);
+
+        assert_eq!(turing_machine.filtered, FilterRuntimeType::Ctl);
+        assert_eq!(turing_machine.filtered_at_step, Some(CTL_START_STEP));
+    }
+}
@@ -1,13 +1,18 @@
+use std::collections::HashMap;
+
 use crate::turing_machine::turing_machine::TuringMachine;
 
 pub struct FilterCyclers {
-    history: Vec<(String, usize, u8)>,
+    // (String, i64, u8) -> encoding of the configuration,
+    // Vec<Option<(i64, Vec<u8>)>> -> written regions of the tapes with that
+    // encoding, kept only once the encoding was repeated
+    history: HashMap<(String, i64, u8), Vec<Option<(i64, Vec<u8>)>>>,
 }
 
 impl FilterCyclers {
     pub fn new() -> Self {
         return FilterCyclers {
-            history: Vec::new(),
+            history: HashMap::new(),
         };
     }
 
@@ -19,20 +24,34 @@ impl FilterCyclers {
     /// `(<hashed_tape>, <head_position>, <current logical state>)`.
     ///
     /// If the tuple was seen in the past, it means it will loop endlessly.
+    ///
+    /// Because the tape is hashed, two different tapes could have the same
+    /// hash, so the written region of the tape is kept once its tuple is
+    /// repeated, and the configuration is only considered repeated when
+    /// the written region is repeated as well, to rule out a collision.
+    ///
+    /// Only the hash is kept for the tuples seen once, so the memory grows
+    /// with the number of steps, and not with the length of the tape; a
+    /// cycle is found after it is completed twice, instead of once.
     pub fn filter(&mut self, turing_machine: &TuringMachine) -> bool {
         let turing_machine_encoded = turing_machine.encode();
 
-        // if the history of computation already
-        // contains the current state of the turing machine, it
-        // means it is a repetition
-        if self.history.contains(&turing_machine_encoded) {
+        let Some(tapes) = self.history.get_mut(&turing_machine_encoded) else {
+            self.history.insert(turing_machine_encoded, Vec::new());
+            return true;
+        };
+
+        let written_region = turing_machine.get_written_region();
+
+        // if the history already contained the same tape, it
+        // means it is a repetition and the filter is not passed
+        if tapes.contains(&written_region) {
             return false;
         }
 
-        // add the current state to the history of computation
-        self.history.push(turing_machine_encoded);
+        // otherwise, add the current tape to the history of computation
+        tapes.push(written_region);
 
-        // the filtered is passed
         return true;
     }
 }
@@ -48,20 +67,12 @@ mod tests {
 
     #[test]
     fn filter_cycler() {
-        let mut transition_function: TransitionFunction = TransitionFunction::new(5, 2);
+        // moves back and forth between the first two cells forever,
+        // repeating the same configuration every 2 steps
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1RB_0LA---").unwrap();
         let mut filter_cyclers: FilterCyclers = FilterCyclers::new();
 
-        transition_function.add_transition(Transition::new_params(0, 0, 1, 1, Direction::RIGHT));
-        transition_function.add_transition(Transition::new_params(0, 1, 101, 1, Direction::LEFT));
-        transition_function.add_transition(Transition::new_params(1, 0, 2, 0, Direction::RIGHT));
-        transition_function.add_transition(Transition::new_params(1, 1, 0, 0, Direction::LEFT));
-        transition_function.add_transition(Transition::new_params(2, 0, 3, 1, Direction::LEFT));
-        transition_function.add_transition(Transition::new_params(2, 1, 0, 0, Direction::LEFT));
-        transition_function.add_transition(Transition::new_params(3, 0, 1, 1, Direction::LEFT));
-        transition_function.add_transition(Transition::new_params(3, 1, 1, 1, Direction::RIGHT));
-        transition_function.add_transition(Transition::new_params(4, 0, 1, 2, Direction::LEFT));
-        transition_function.add_transition(Transition::new_params(4, 1, 1, 2, Direction::RIGHT));
-
         // create the turing machines based on the transition function
         let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
         let maximum_steps = 1000;
@@ -69,7 +80,7 @@ mod tests {
         turing_machine.make_transition();
 
         // execute the turing machine until it reaches the maximum
-        // number of steps OR it gets filtered out by the escapees filter
+        // number of steps OR it gets filtered out by the cyclers filter
         while turing_machine.steps < maximum_steps {
             if !(filter_cyclers.filter(&turing_machine)) {
                 break;
@@ -78,6 +89,64 @@ mod tests {
             turing_machine.make_transition();
         }
 
-        assert_ne!(turing_machine.steps, maximum_steps);
+        assert_eq!(turing_machine.halted, false);
+        // the repetition of the configuration is caught once
+        // the cycle is completed for the second time
+        assert_eq!(turing_machine.steps, 5);
+    }
+
+    #[test]
+    fn filter_cycler_hash_collision() {
+        let mut transition_function: TransitionFunction = TransitionFunction::new(1, 2);
+        transition_function.add_transition(Transition::new_params(0, 0, 0, 1, Direction::RIGHT));
+
+        let mut filter_cyclers: FilterCyclers = FilterCyclers::new();
+
+        // two turing machines with the same head position
+        // and state, but different tapes
+        let mut turing_machine_01: TuringMachine = TuringMachine::new(transition_function.clone());
+        turing_machine_01.tape[0] = 1;
+
+        let mut turing_machine_02: TuringMachine = TuringMachine::new(transition_function);
+        turing_machine_02.tape[0] = 0;
+
+        assert!(filter_cyclers.filter(&turing_machine_01));
+        // the tape is only kept once the configuration is repeated
+        assert!(filter_cyclers.filter(&turing_machine_01));
+
+        // simulate a collision, the tape of the second turing
+        // machine having the same hash as the one of the first
+        let tapes = filter_cyclers
+            .history
+            .remove(&turing_machine_01.encode())
+            .unwrap();
+        filter_cyclers
+            .history
+            .insert(turing_machine_02.encode(), tapes);
+
+        // the tapes are different, so the configuration is not repeated
+        assert!(filter_cyclers.filter(&turing_machine_02));
+        assert!(!filter_cyclers.filter(&turing_machine_02));
+    }
+
+    #[test]
+    fn filter_cycler_keeps_only_repeated_tapes() {
+        // moves to the right forever, never repeating a configuration
+        let mut transition_function: TransitionFunction = TransitionFunction::new(1, 2);
+        transition_function.add_transition(Transition::new_params(0, 0, 0, 1, Direction::RIGHT));
+
+        let mut filter_cyclers: FilterCyclers = FilterCyclers::new();
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+
+        for _ in 0..100 {
+            assert!(filter_cyclers.filter(&turing_machine));
+            turing_machine.make_transition();
+        }
+
+        assert_eq!(filter_cyclers.history.len(), 100);
+        assert!(filter_cyclers
+            .history
+            .values()
+            .all(|tapes| tapes.is_empty()));
     }
 }
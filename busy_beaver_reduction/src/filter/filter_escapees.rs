@@ -1,7 +1,9 @@
 use crate::turing_machine::turing_machine::TuringMachine;
 
 pub struct FilterEscapees {
-    counter: u8,
+    /// number of consecutive steps that increased the tape, wider than the
+    /// number of states, so it can exceed it without wrapping around
+    counter: u32,
 }
 
 impl FilterEscapees {
@@ -29,9 +31,9 @@ impl FilterEscapees {
             return true;
         }
 
-        self.counter += 1;
+        self.counter = self.counter.saturating_add(1);
 
-        return self.counter <= turing_machine.transition_function.number_of_states;
+        return self.counter <= turing_machine.transition_function.number_of_states as u32;
     }
 
     /// Given the current state of a `TuringMachine`, verify if
@@ -52,15 +54,15 @@ impl FilterEscapees {
             return true;
         }
 
-        let possible_transition = turing_machine.transition_function.transitions.get(&(
+        let possible_transition = turing_machine.transition_function.transitions().get(&(
             turing_machine.current_state,
-            turing_machine.tape[turing_machine.head_position],
+            turing_machine.get_head_symbol(),
         ));
 
         match possible_transition {
             Some(transition) => {
                 return !(turing_machine.current_state == transition.0
-                    && turing_machine.tape[turing_machine.head_position] == transition.1
+                    && turing_machine.get_head_symbol() == transition.1
                     && transition.1 == 0);
             }
             None => {
@@ -134,4 +136,28 @@ mod tests {
 
         assert_ne!(turing_machine.steps, maximum_steps);
     }
+
+    #[test]
+    fn filter_long_escapees_maximum_states() {
+        // only the start state is used, out of the maximum number of states,
+        // so the counter goes past u8::MAX before the machine is filtered
+        let mut transition_function: TransitionFunction = TransitionFunction::new(u8::MAX, 2);
+        let mut filter_escapees: FilterEscapees = FilterEscapees::new();
+
+        transition_function.add_transition(Transition::new_params(0, 0, 0, 1, Direction::RIGHT));
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+
+        // the tape increases at every step, and the threshold
+        // is respected until the counter exceeds the number of states
+        while turing_machine.steps < 1000 {
+            if filter_escapees.filter_long_escapees(&turing_machine) == false {
+                break;
+            }
+
+            turing_machine.make_transition();
+        }
+
+        assert_eq!(turing_machine.steps, u8::MAX as u64 + 1);
+    }
 }
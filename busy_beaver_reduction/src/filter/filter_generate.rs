@@ -3,6 +3,33 @@ use crate::turing_machine::direction::Direction;
 use crate::turing_machine::special_states::SpecialStates;
 use log::info;
 
+/// Number of Turing machines filtered by each generation filter,
+/// out of the `turing_machines_size` Turing machines possible.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FilterGenerateStats {
+    pub halting_skippers: u128,
+    pub start_state_loopers: u128,
+    pub neighbour_state_loopers: u128,
+    pub naive_beavers: u128,
+    pub turing_machines_size: u128,
+}
+
+impl FilterGenerateStats {
+    /// Gets the percentage of the Turing machines that `filtered` represents.
+    pub fn get_percentage(&self, filtered: u128) -> f64 {
+        return filtered as f64 * 100.0 / self.turing_machines_size as f64;
+    }
+
+    /// Gets the number of Turing machines filtered by all the filters.
+    pub fn get_filtered(&self) -> u128 {
+        return self
+            .halting_skippers
+            .saturating_add(self.start_state_loopers)
+            .saturating_add(self.neighbour_state_loopers)
+            .saturating_add(self.naive_beavers);
+    }
+}
+
 /// Implements filter techniques for `TransitionFunction`s that
 /// have been `partially generated`.
 ///
@@ -10,11 +37,12 @@ use log::info;
 /// transition functions, to reduce the number of functions
 /// that need to be generated.
 pub struct FilterGenerate {
-    halting_skippers: i64,
-    start_state_loopers: i64,
-    neighbour_state_loopers: i64,
-    naive_beavers: i64,
-    turing_machines_size: i64,
+    halting_skippers: u128,
+    start_state_loopers: u128,
+    neighbour_state_loopers: u128,
+    naive_beavers: u128,
+    turing_machines_size: u128,
+    number_of_states: usize,
     maximum_entries: usize,
     maximum_possibilies_for_entry: usize,
 }
@@ -31,26 +59,46 @@ impl FilterGenerate {
         // in the current representation being reduced by the halting skippers
         let maximum_possibilies_for_entry = number_of_states * alphabet_size * directions_size + 1;
 
-        let original_turing_machines_size =
-            (original_maximum_possibilites_for_entry).pow(maximum_entries as u32);
+        // the number of Turing machines grows exponentially with the
+        // number of states, so saturate instead of overflowing
+        let original_turing_machines_size = (original_maximum_possibilites_for_entry as u128)
+            .saturating_pow(maximum_entries as u32);
         let filtered_turing_machines_size =
-            (maximum_possibilies_for_entry).pow(maximum_entries as u32);
+            (maximum_possibilies_for_entry as u128).saturating_pow(maximum_entries as u32);
 
         // compute how many Turing machines were filtered using
         // the halting skippers filter technique
-        let halting_skippers = original_turing_machines_size - filtered_turing_machines_size;
+        let halting_skippers =
+            original_turing_machines_size.saturating_sub(filtered_turing_machines_size);
 
         return FilterGenerate {
-            halting_skippers: halting_skippers as i64,
+            halting_skippers: halting_skippers,
             start_state_loopers: 0,
             neighbour_state_loopers: 0,
             naive_beavers: 0,
-            turing_machines_size: original_turing_machines_size as i64,
+            turing_machines_size: original_turing_machines_size,
+            number_of_states: number_of_states,
             maximum_entries,
             maximum_possibilies_for_entry,
         };
     }
 
+    /// Adds the counters of the Turing machines filtered during the generation
+    /// by `other` to the ones of this filter, when the generation
+    /// was split between multiple filters.
+    ///
+    /// The halting skippers are not counted during the generation,
+    /// so they are not added.
+    pub fn merge(&mut self, other: &FilterGenerate) {
+        self.start_state_loopers = self
+            .start_state_loopers
+            .saturating_add(other.start_state_loopers);
+        self.neighbour_state_loopers = self
+            .neighbour_state_loopers
+            .saturating_add(other.neighbour_state_loopers);
+        self.naive_beavers = self.naive_beavers.saturating_add(other.naive_beavers);
+    }
+
     /// Given a transition function, calculates how many
     /// transition functions were filtered by stopping generating
     /// from its state onward.
@@ -60,31 +108,40 @@ impl FilterGenerate {
     pub fn get_transition_function_filtered(
         &self,
         transition_function: &TransitionFunction,
-    ) -> i64 {
-        let entries_left_to_complete = self.maximum_entries - transition_function.transitions.len();
-        let transition_functions_filtered = self
-            .maximum_possibilies_for_entry
-            .pow(entries_left_to_complete as u32);
+    ) -> u128 {
+        let entries_left_to_complete =
+            self.maximum_entries - transition_function.transitions().len();
+        let transition_functions_filtered = (self.maximum_possibilies_for_entry as u128)
+            .saturating_pow(entries_left_to_complete as u32);
 
-        return transition_functions_filtered as i64;
+        return transition_functions_filtered;
     }
 
     /// Applies all filters of the `FilterGenerate` struct to the provided
     /// `TransitionFunction` and returns true if they were `all` passed.
     pub fn filter_all(&mut self, transition_function: &TransitionFunction) -> bool {
         if Self::filter_start_state_moves_into_loop(transition_function) == false {
-            self.start_state_loopers += self.get_transition_function_filtered(transition_function);
+            self.start_state_loopers = self
+                .start_state_loopers
+                .saturating_add(self.get_transition_function_filtered(transition_function));
             return false;
         }
 
         if Self::filter_moves_into_neighbour_loop(transition_function) == false {
-            self.neighbour_state_loopers +=
-                self.get_transition_function_filtered(transition_function);
+            self.neighbour_state_loopers = self
+                .neighbour_state_loopers
+                .saturating_add(self.get_transition_function_filtered(transition_function));
             return false;
         }
 
-        if Self::filter_moves_to_halting_state(transition_function) == false {
-            self.naive_beavers += self.get_transition_function_filtered(transition_function);
+        // with a single state, the Turing machine that halts right away
+        // is the champion, the others never halt or halt without writing
+        if self.number_of_states > 1
+            && Self::filter_moves_to_halting_state(transition_function) == false
+        {
+            self.naive_beavers = self
+                .naive_beavers
+                .saturating_add(self.get_transition_function_filtered(transition_function));
             return false;
         }
 
@@ -94,10 +151,14 @@ impl FilterGenerate {
     /// Checks whether the start state of the transition function
     /// provided will run into a self loop, moving infinitely to
     /// the right / left and writing 0s on the tape (self loops).
+    ///
+    /// The head only reads blank cells while it keeps moving in the same
+    /// direction, so the symbol written does not matter: for any alphabet,
+    /// a start state that moves into itself reading the blank loops forever.
     fn filter_start_state_moves_into_loop(transition_function: &TransitionFunction) -> bool {
         let start_state_key: &(u8, u8) = &(SpecialStates::StateStart.value(), 0);
         let start_state_value: Option<&(u8, u8, Direction)> =
-            transition_function.transitions.get(start_state_key);
+            transition_function.transitions().get(start_state_key);
 
         match start_state_value {
             Some(transition) => {
@@ -114,7 +175,7 @@ impl FilterGenerate {
     fn filter_moves_to_halting_state(transition_function: &TransitionFunction) -> bool {
         let start_state_key: &(u8, u8) = &(SpecialStates::StateStart.value(), 0);
         let start_state_value: Option<&(u8, u8, Direction)> =
-            transition_function.transitions.get(start_state_key);
+            transition_function.transitions().get(start_state_key);
 
         match start_state_value {
             Some(transition) => {
@@ -134,10 +195,13 @@ impl FilterGenerate {
     ///
     /// - `start_state` -- RIGHT --> `self looping state` to RIGHT
     /// - `start_state` -- LEFT --> `self looping state` to LEFT
+    ///
+    /// As for the start state, the self looping state only reads blank
+    /// cells, so it loops forever whichever symbol it writes.
     fn filter_moves_into_neighbour_loop(transition_function: &TransitionFunction) -> bool {
         let start_state_key: &(u8, u8) = &(SpecialStates::StateStart.value(), 0);
         let start_state_value: Option<&(u8, u8, Direction)> =
-            transition_function.transitions.get(start_state_key);
+            transition_function.transitions().get(start_state_key);
         // the direction in which the tape head
         // will be moving
         let start_state_direction: Direction;
@@ -157,7 +221,7 @@ impl FilterGenerate {
         }
 
         let next_state_value: Option<&(u8, u8, Direction)> =
-            transition_function.transitions.get(&next_state_key);
+            transition_function.transitions().get(&next_state_key);
 
         // check if the following state will self loop,
         // by keeping moving in the same direction and staying
@@ -173,17 +237,28 @@ impl FilterGenerate {
         }
     }
 
+    /// Gets the number of Turing machines filtered by each filter so far.
+    pub fn statistics(&self) -> FilterGenerateStats {
+        return FilterGenerateStats {
+            halting_skippers: self.halting_skippers,
+            start_state_loopers: self.start_state_loopers,
+            neighbour_state_loopers: self.neighbour_state_loopers,
+            naive_beavers: self.naive_beavers,
+            turing_machines_size: self.turing_machines_size,
+        };
+    }
+
     /// Display the number of Turing machines that was filtered
     /// by each individual filter.
     pub fn display_filtering_results(&self) {
-        let halting_skippers_percentage =
-            self.halting_skippers as f64 * 100.0 / self.turing_machines_size as f64;
+        let statistics = self.statistics();
+
+        let halting_skippers_percentage = statistics.get_percentage(statistics.halting_skippers);
         let start_state_loopers_percentage =
-            self.start_state_loopers as f64 * 100.0 / self.turing_machines_size as f64;
+            statistics.get_percentage(statistics.start_state_loopers);
         let neighbour_state_loopers_percentage =
-            self.neighbour_state_loopers as f64 * 100.0 / self.turing_machines_size as f64;
-        let naive_beavers_percentage =
-            self.naive_beavers as f64 * 100.0 / self.turing_machines_size as f64;
+            statistics.get_percentage(statistics.neighbour_state_loopers);
+        let naive_beavers_percentage = statistics.get_percentage(statistics.naive_beavers);
 
         let total = halting_skippers_percentage
             + start_state_loopers_percentage
@@ -191,26 +266,31 @@ impl FilterGenerate {
             + naive_beavers_percentage;
 
         info!(
+            filter = "halting skippers", percentage = halting_skippers_percentage;
             "Filtered a total of halting skippers: {:.2}%",
-            self.halting_skippers as f64 * 100.0 / self.turing_machines_size as f64
+            halting_skippers_percentage
         );
 
         info!(
+            filter = "start state loopers", percentage = start_state_loopers_percentage;
             "Filtered a total of start state loopers: {:.2}%",
-            self.start_state_loopers as f64 * 100.0 / self.turing_machines_size as f64
+            start_state_loopers_percentage
         );
 
         info!(
+            filter = "neighbour state loopers", percentage = neighbour_state_loopers_percentage;
             "Filtered a total of neighbour state loopers: {:.2}%",
-            self.neighbour_state_loopers as f64 * 100.0 / self.turing_machines_size as f64
+            neighbour_state_loopers_percentage
         );
 
         info!(
+            filter = "naive beavers", percentage = naive_beavers_percentage;
             "Filtered a total of naive beavers: {:.2}%",
-            self.naive_beavers as f64 * 100.0 / self.turing_machines_size as f64
+            naive_beavers_percentage
         );
 
         info!(
+            percentage = total;
             "Filtered a total of {:.2}% Turing machines with generation filters.",
             total
         );
@@ -221,6 +301,81 @@ impl FilterGenerate {
 mod tests {
     use super::*;
     use crate::delta::transition::Transition;
+    use crate::generator::generator_transition_function::GeneratorTransitionFunction;
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn turing_machines_size_five_states() {
+        let filter_generate = FilterGenerate::new(5, 2, 2);
+
+        // (2 * 2 * (5 + 1)) ^ (5 * 2)
+        assert_eq!(filter_generate.turing_machines_size, 63_403_380_965_376);
+        assert!(filter_generate.halting_skippers > 0);
+        assert!(filter_generate.halting_skippers < filter_generate.turing_machines_size);
+    }
+
+    #[test]
+    fn turing_machines_size_saturates() {
+        let filter_generate = FilterGenerate::new(50, 10, 2);
+
+        assert_eq!(filter_generate.turing_machines_size, u128::MAX);
+    }
+
+    /// Generates the transition functions in TNF with the given number
+    /// of states and symbols, and returns the statistics collected.
+    fn generate_statistics(number_of_states: u8, number_of_symbols: u8) -> FilterGenerateStats {
+        let (tx, rx) = channel();
+        let mut generator = GeneratorTransitionFunction::new(number_of_states, number_of_symbols);
+        generator.generate_tnf(&tx, 1000);
+        drop(tx);
+
+        let generated: usize = rx
+            .iter()
+            .map(|transition_functions: Vec<TransitionFunction>| transition_functions.len())
+            .sum();
+        assert!(generated > 0);
+
+        return generator.filter_generate.statistics();
+    }
+
+    #[test]
+    fn statistics_three_states() {
+        let statistics = generate_statistics(3, 1);
+
+        assert!(statistics.halting_skippers > 0);
+        assert!(statistics.start_state_loopers > 0);
+        assert!(statistics.naive_beavers > 0);
+        // (1 * 2 * (3 + 1)) ^ (3 * 1)
+        assert_eq!(statistics.turing_machines_size, 512);
+    }
+
+    #[test]
+    #[ignore = "generates every transition function with 3 states and 2 symbols, which is slow in debug builds"]
+    fn statistics_three_states_two_symbols() {
+        let statistics = generate_statistics(3, 2);
+
+        assert!(statistics.halting_skippers > 0);
+        assert!(statistics.start_state_loopers > 0);
+        assert!(statistics.naive_beavers > 0);
+        // (2 * 2 * (3 + 1)) ^ (3 * 2)
+        assert_eq!(statistics.turing_machines_size, 16_777_216);
+    }
+
+    #[test]
+    fn merge() {
+        let mut filter_generate = FilterGenerate::new(2, 2, 2);
+        let mut other = FilterGenerate::new(2, 2, 2);
+        other.start_state_loopers = 3;
+        other.naive_beavers = 2;
+
+        filter_generate.start_state_loopers = 1;
+        filter_generate.merge(&other);
+
+        assert_eq!(filter_generate.start_state_loopers, 4);
+        assert_eq!(filter_generate.neighbour_state_loopers, 0);
+        assert_eq!(filter_generate.naive_beavers, 2);
+        assert_eq!(filter_generate.halting_skippers, other.halting_skippers);
+    }
 
     #[test]
     fn filter_start_state_moves_right_loop() {
@@ -264,6 +419,49 @@ mod tests {
         assert_eq!(filter_result, false);
     }
 
+    #[test]
+    fn filter_start_state_loop_three_symbols() {
+        let mut filter_generate = FilterGenerate::new(2, 3, 2);
+        let mut transition_function: TransitionFunction = TransitionFunction::new(2, 3);
+
+        // the start state self loops, writing the last symbol of the alphabet
+        transition_function.add_transition(Transition {
+            from_state: SpecialStates::StateStart.value(),
+            from_symbol: 0,
+            to_state: SpecialStates::StateStart.value(),
+            to_symbol: 2,
+            direction: Direction::LEFT,
+        });
+
+        assert_eq!(filter_generate.filter_all(&transition_function), false);
+        assert!(filter_generate.statistics().start_state_loopers > 0);
+    }
+
+    #[test]
+    fn filter_neighbour_loop_three_symbols() {
+        let mut filter_generate = FilterGenerate::new(2, 3, 2);
+        let mut transition_function: TransitionFunction = TransitionFunction::new(2, 3);
+
+        transition_function.add_transition(Transition {
+            from_state: SpecialStates::StateStart.value(),
+            from_symbol: 0,
+            to_state: 1,
+            to_symbol: 2,
+            direction: Direction::LEFT,
+        });
+        // the next state keeps moving left, writing symbol 2
+        transition_function.add_transition(Transition {
+            from_state: 1,
+            from_symbol: 0,
+            to_state: 1,
+            to_symbol: 2,
+            direction: Direction::LEFT,
+        });
+
+        assert_eq!(filter_generate.filter_all(&transition_function), false);
+        assert!(filter_generate.statistics().neighbour_state_loopers > 0);
+    }
+
     #[test]
     fn filter_moves_right_loop() {
         let mut transition_function: TransitionFunction = TransitionFunction::new(2, 2);
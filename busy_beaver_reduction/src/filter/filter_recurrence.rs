@@ -0,0 +1,226 @@
+use std::collections::VecDeque;
+
+use crate::turing_machine::turing_machine::TuringMachine;
+
+/// Maximum number of past configurations kept in the history,
+/// which bounds the period of the recurrences that can be detected.
+const HISTORY_SIZE: usize = 256;
+
+pub struct FilterRecurrence {
+    // u8 -> state,
+    // i64 -> head position,
+    // VecDeque<u8> -> tape content
+    // usize -> tape origin
+    history: VecDeque<(u8, i64, VecDeque<u8>, usize)>,
+}
+
+impl Default for FilterRecurrence {
+    fn default() -> Self {
+        return FilterRecurrence::new();
+    }
+}
+
+impl FilterRecurrence {
+    pub fn new() -> Self {
+        return FilterRecurrence {
+            history: VecDeque::new(),
+        };
+    }
+
+    /// Given the current state of a `TuringMachine`, checks whether it recurs
+    /// a past configuration, in the sense of Lin & Rado: at the steps `t1 < t2`
+    /// the machine is in the same state, and the tape seen from the head
+    /// is the same over the cells that matter for the execution between `t1` and `t2`.
+    ///
+    /// Let `[L, R]` be the cells visited between `t1` and `t2`, relative
+    /// to the head at `t1`, and `d` the displacement of the head:
+    ///
+    /// 1. `d = 0`: if the cells from `[L, R]` are the same, the machine
+    /// will repeat the same moves forever.
+    ///
+    /// 2. `d > 0`: if the cells from `L` to the right end of the tape are
+    /// the same, the machine repeats the same moves forever, shifted by `d` cells
+    /// to the right, because it never reads the cells left of `L` again.
+    ///
+    /// 3. `d < 0`: symmetrically, for the cells from the left end of the tape to `R`.
+    ///
+    /// If a recurrence was found, it means it will loop endlessly.
+    pub fn filter(&mut self, turing_machine: &TuringMachine) -> bool {
+        let current = (
+            turing_machine.current_state,
+            turing_machine.head_position,
+            turing_machine.tape.clone(),
+            turing_machine.tape_origin,
+        );
+
+        // the left most and right most cells visited
+        // between the past configuration and the current one
+        let mut leftmost = current.1;
+        let mut rightmost = current.1;
+
+        for past in self.history.iter().rev() {
+            leftmost = leftmost.min(past.1);
+            rightmost = rightmost.max(past.1);
+
+            if past.0 == current.0 && Self::check_recurrence(past, &current, leftmost, rightmost) {
+                return false;
+            }
+        }
+
+        self.history.push_back(current);
+
+        if self.history.len() > HISTORY_SIZE {
+            self.history.pop_front();
+        }
+
+        // the filter is passed
+        return true;
+    }
+
+    /// Checks if the `current` configuration recurs the `past` one,
+    /// knowing the cells visited between them, `[leftmost, rightmost]`.
+    fn check_recurrence(
+        past: &(u8, i64, VecDeque<u8>, usize),
+        current: &(u8, i64, VecDeque<u8>, usize),
+        leftmost: i64,
+        rightmost: i64,
+    ) -> bool {
+        let displacement = current.1 - past.1;
+
+        // offsets relative to the head that need to be compared
+        let (mut offset_start, mut offset_end) = (leftmost - past.1, rightmost - past.1);
+
+        if displacement > 0 {
+            offset_end = offset_end
+                .max(Self::get_right_end(past) - past.1)
+                .max(Self::get_right_end(current) - current.1);
+        } else if displacement < 0 {
+            offset_start = offset_start
+                .min(Self::get_left_end(past) - past.1)
+                .min(Self::get_left_end(current) - current.1);
+        }
+
+        for offset in offset_start..=offset_end {
+            if Self::get_symbol(past, past.1 + offset)
+                != Self::get_symbol(current, current.1 + offset)
+            {
+                return false;
+            }
+        }
+
+        return true;
+    }
+
+    /// Gets the symbol found on the tape of a configuration at the
+    /// given coordinate; the cells outside of the tape are blank.
+    fn get_symbol(configuration: &(u8, i64, VecDeque<u8>, usize), position: i64) -> u8 {
+        let index = configuration.3 as i64 + position;
+
+        if index < 0 || index >= configuration.2.len() as i64 {
+            return 0;
+        }
+
+        return configuration.2[index as usize];
+    }
+
+    /// Gets the coordinate of the left most cell of the tape of a configuration.
+    fn get_left_end(configuration: &(u8, i64, VecDeque<u8>, usize)) -> i64 {
+        return -(configuration.3 as i64);
+    }
+
+    /// Gets the coordinate of the right most cell of the tape of a configuration.
+    fn get_right_end(configuration: &(u8, i64, VecDeque<u8>, usize)) -> i64 {
+        return configuration.2.len() as i64 - 1 - configuration.3 as i64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::delta::transition_function::TransitionFunction;
+    use crate::filter::filter_cyclers::FilterCyclers;
+    use crate::filter::filter_escapees::FilterEscapees;
+    use crate::filter::filter_translated_cyclers::FilterTranslatedCyclers;
+    use crate::turing_machine::turing_machine::TuringMachine;
+
+    use super::FilterRecurrence;
+
+    /// Non-halting machine that the escapees and cyclers filters do not catch:
+    /// it drifts to the right, leaving a 1 behind on the starting cell.
+    const RECURRENT_MACHINE: &str = "1RB0LA_1RC0RB_0LB1RH";
+
+    #[test]
+    fn filter_recurrence() {
+        let transition_function =
+            TransitionFunction::from_standard_notation(RECURRENT_MACHINE).unwrap();
+        let mut filter_recurrence: FilterRecurrence = FilterRecurrence::new();
+
+        // create the turing machines based on the transition function
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        let maximum_steps = 1000;
+
+        turing_machine.make_transition();
+
+        // execute the turing machine until it reaches the maximum
+        // number of steps OR it gets filtered out by the recurrence filter
+        while turing_machine.steps < maximum_steps {
+            if !(filter_recurrence.filter(&turing_machine)) {
+                break;
+            }
+
+            turing_machine.make_transition();
+        }
+
+        assert_eq!(turing_machine.halted, false);
+        assert_ne!(turing_machine.steps, maximum_steps);
+    }
+
+    #[test]
+    fn filter_recurrence_missed_by_other_filters() {
+        let transition_function =
+            TransitionFunction::from_standard_notation(RECURRENT_MACHINE).unwrap();
+        let mut filter_escapees: FilterEscapees = FilterEscapees::new();
+        let mut filter_cyclers: FilterCyclers = FilterCyclers::new();
+        let mut filter_translated_cyclers: FilterTranslatedCyclers = FilterTranslatedCyclers::new();
+
+        // create the turing machines based on the transition function
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        let maximum_steps = 1000;
+
+        turing_machine.make_transition();
+
+        // execute the turing machine until it reaches the maximum
+        // number of steps OR it gets filtered out by any other filter
+        while turing_machine.steps < maximum_steps {
+            if !filter_escapees.filter_short_escapees(&turing_machine)
+                || !filter_escapees.filter_long_escapees(&turing_machine)
+                || !filter_cyclers.filter(&turing_machine)
+            {
+                break;
+            }
+
+            turing_machine.make_transition();
+        }
+
+        assert_eq!(turing_machine.halted, false);
+        assert_eq!(turing_machine.steps, maximum_steps);
+
+        // the translated cyclers filter only compares the cells
+        // behind the records, so the 1 left behind does not matter
+        let transition_function =
+            TransitionFunction::from_standard_notation(RECURRENT_MACHINE).unwrap();
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+
+        turing_machine.make_transition();
+
+        while turing_machine.steps < maximum_steps {
+            if !filter_translated_cyclers.filter(&turing_machine) {
+                break;
+            }
+
+            turing_machine.make_transition();
+        }
+
+        assert_eq!(turing_machine.halted, false);
+        assert_ne!(turing_machine.steps, maximum_steps);
+    }
+}
@@ -1,26 +1,128 @@
+use crate::filter::filter_bouncer::FilterBouncer;
+use crate::filter::filter_ctl::FilterCtl;
 use crate::filter::filter_cyclers::FilterCyclers;
 use crate::filter::filter_escapees::FilterEscapees;
+use crate::filter::filter_recurrence::FilterRecurrence;
 use crate::filter::filter_translated_cyclers::FilterTranslatedCyclers;
 use crate::turing_machine::turing_machine::TuringMachine;
 
 /// Enum for the filter runtime type, to mark
 /// each running Turing machine with the filter that
 /// identified it as non-halting.
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum FilterRuntimeType {
     ShortEscapee,
     LongEscapee,
     Cycler,
     TranslatedCycler,
+    Recurrence,
+    Bouncer,
+    Ctl,
     None,
 }
 
+impl FilterRuntimeType {
+    /// Gets the value (`u8`) associated to each filter runtime type,
+    /// as stored in the database, or `None` if no filter was matched:
+    /// - `ShortEscapee` = 0
+    /// - `LongEscapee` = 1
+    /// - `Cycler` = 2
+    /// - `TranslatedCycler` = 3
+    /// - `Recurrence` = 4
+    /// - `Bouncer` = 5
+    /// - `Ctl` = 6
+    pub fn value(&self) -> Option<u8> {
+        match *self {
+            FilterRuntimeType::ShortEscapee => Some(0),
+            FilterRuntimeType::LongEscapee => Some(1),
+            FilterRuntimeType::Cycler => Some(2),
+            FilterRuntimeType::TranslatedCycler => Some(3),
+            FilterRuntimeType::Recurrence => Some(4),
+            FilterRuntimeType::Bouncer => Some(5),
+            FilterRuntimeType::Ctl => Some(6),
+            FilterRuntimeType::None => None,
+        }
+    }
+
+    /// Transforms the value given (`Option<u8>`) to a filter runtime
+    /// type, the inverse of `value`; any unknown value is `None`.
+    pub fn transform(value: Option<u8>) -> Self {
+        match value {
+            Some(0) => FilterRuntimeType::ShortEscapee,
+            Some(1) => FilterRuntimeType::LongEscapee,
+            Some(2) => FilterRuntimeType::Cycler,
+            Some(3) => FilterRuntimeType::TranslatedCycler,
+            Some(4) => FilterRuntimeType::Recurrence,
+            Some(5) => FilterRuntimeType::Bouncer,
+            Some(6) => FilterRuntimeType::Ctl,
+            _ => FilterRuntimeType::None,
+        }
+    }
+}
+
+/// Configuration of a `FilterRuntime`: the runtime
+/// filters that are enabled, in the order they are applied.
+///
+/// Each filter is identified by the `FilterRuntimeType`
+/// it marks the Turing machines with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FilterRuntimeConfig {
+    pub filters: Vec<FilterRuntimeType>,
+}
+
+impl Default for FilterRuntimeConfig {
+    fn default() -> Self {
+        return FilterRuntimeConfig::new();
+    }
+}
+
+impl FilterRuntimeConfig {
+    /// Creates the default configuration, with all
+    /// the runtime filters enabled, ending with the
+    /// CTL proof as the last resort.
+    pub fn new() -> Self {
+        return FilterRuntimeConfig {
+            filters: vec![
+                FilterRuntimeType::ShortEscapee,
+                FilterRuntimeType::LongEscapee,
+                FilterRuntimeType::Cycler,
+                FilterRuntimeType::TranslatedCycler,
+                FilterRuntimeType::Recurrence,
+                FilterRuntimeType::Bouncer,
+                FilterRuntimeType::Ctl,
+            ],
+        };
+    }
+
+    /// Creates a configuration with only the given
+    /// filters enabled, applied in the given order.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// # use busy_beaver_reduction::filter::filter_runtime::{FilterRuntimeConfig, FilterRuntimeType};
+    /// let config = FilterRuntimeConfig::with_filters(vec![
+    ///     FilterRuntimeType::Cycler,
+    ///     FilterRuntimeType::ShortEscapee,
+    /// ]);
+    /// ```
+    pub fn with_filters(filters: Vec<FilterRuntimeType>) -> Self {
+        return FilterRuntimeConfig { filters: filters };
+    }
+}
+
 /// Filter class that acts as a wrapper for all
 /// the filters that are applied during the execution
 /// of a Turing Machine:
 /// - `FilterCyclers`
 /// - `FilterTranslatedCyclers`
 /// - `FilterEscapees`
+/// - `FilterRecurrence`
+/// - `FilterBouncer`
+/// - `FilterCtl`
+///
+/// Only the filters enabled in the `FilterRuntimeConfig`
+/// are applied, in the configured order.
 ///
 /// The same Turing Machine will be passed to the other
 /// classes in order to filter it.
@@ -29,39 +131,142 @@ pub enum FilterRuntimeType {
 /// will be part of the execution of a Turing Machine,
 /// afterwards the object will be deleted.
 pub struct FilterRuntime {
+    config: FilterRuntimeConfig,
     filter_cyclers: FilterCyclers,
     filter_translated_cyclers: FilterTranslatedCyclers,
     filter_escapees: FilterEscapees,
+    filter_recurrence: FilterRecurrence,
+    filter_bouncer: FilterBouncer,
+    filter_ctl: FilterCtl,
 }
 
 impl FilterRuntime {
-    pub fn new() -> Self {
+    pub fn new(config: FilterRuntimeConfig) -> Self {
         return FilterRuntime {
+            config: config,
             filter_cyclers: FilterCyclers::new(),
             filter_translated_cyclers: FilterTranslatedCyclers::new(),
             filter_escapees: FilterEscapees::new(),
+            filter_recurrence: FilterRecurrence::new(),
+            filter_bouncer: FilterBouncer::new(),
+            filter_ctl: FilterCtl::new(),
         };
     }
 
-    /// Applies all filters of the `FilterRuntime` struct to the provided
-    /// `TuringMachine` and returns true if they were `all` passed.
+    /// Applies the enabled filters of the `FilterRuntime` struct to the provided
+    /// `TuringMachine`, in the configured order.
+    ///
+    /// Returns the type of the first filter that was not passed,
+    /// or `FilterRuntimeType::None` if they were `all` passed.
     pub fn filter_all(&mut self, turing_machine: &TuringMachine) -> FilterRuntimeType {
-        if self.filter_escapees.filter_short_escapees(turing_machine) == false {
-            return FilterRuntimeType::ShortEscapee;
+        for &filter_type in self.config.filters.iter() {
+            let passed = match filter_type {
+                FilterRuntimeType::ShortEscapee => {
+                    self.filter_escapees.filter_short_escapees(turing_machine)
+                }
+                FilterRuntimeType::LongEscapee => {
+                    self.filter_escapees.filter_long_escapees(turing_machine)
+                }
+                FilterRuntimeType::Cycler => self.filter_cyclers.filter(turing_machine),
+                FilterRuntimeType::TranslatedCycler => {
+                    self.filter_translated_cyclers.filter(turing_machine)
+                }
+                FilterRuntimeType::Recurrence => self.filter_recurrence.filter(turing_machine),
+                FilterRuntimeType::Bouncer => self.filter_bouncer.filter(turing_machine),
+                FilterRuntimeType::Ctl => self.filter_ctl.filter(turing_machine),
+                FilterRuntimeType::None => true,
+            };
+
+            if passed == false {
+                return filter_type;
+            }
         }
 
-        if self.filter_escapees.filter_long_escapees(turing_machine) == false {
-            return FilterRuntimeType::LongEscapee;
-        };
+        return FilterRuntimeType::None;
+    }
+}
 
-        if self.filter_cyclers.filter(turing_machine) == false {
-            return FilterRuntimeType::Cycler;
-        }
+#[cfg(test)]
+mod tests {
+    use crate::delta::transition_function::TransitionFunction;
+    use crate::turing_machine::turing_machine::TuringMachine;
+
+    use super::{FilterRuntime, FilterRuntimeConfig, FilterRuntimeType};
+
+    /// 2-state cycler, moving the head back and forth
+    /// between two blank cells.
+    const CYCLER_MACHINE: &str = "0RB1RH_0LA1RH";
+
+    /// Runs the machine with the given configuration, until it reaches
+    /// the maximum number of steps OR it gets filtered out.
+    fn run_with_config(config: FilterRuntimeConfig) -> FilterRuntimeType {
+        let transition_function =
+            TransitionFunction::from_standard_notation(CYCLER_MACHINE).unwrap();
+        let mut filter_runtime: FilterRuntime = FilterRuntime::new(config);
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        let maximum_steps = 1000;
+
+        turing_machine.make_transition();
+
+        while turing_machine.steps < maximum_steps {
+            let filtered = filter_runtime.filter_all(&turing_machine);
+
+            if filtered != FilterRuntimeType::None {
+                return filtered;
+            }
 
-        if self.filter_translated_cyclers.filter(turing_machine) == false {
-            return FilterRuntimeType::TranslatedCycler;
+            turing_machine.make_transition();
         }
 
         return FilterRuntimeType::None;
     }
+
+    #[test]
+    fn filter_all_default_config() {
+        let filtered = run_with_config(FilterRuntimeConfig::new());
+
+        // the cyclers filter needs the cycle to be completed twice,
+        // so the recurrence filter catches the machine first
+        assert_eq!(filtered, FilterRuntimeType::Recurrence);
+    }
+
+    #[test]
+    fn filter_all_only_escapees() {
+        let config = FilterRuntimeConfig::with_filters(vec![
+            FilterRuntimeType::ShortEscapee,
+            FilterRuntimeType::LongEscapee,
+        ]);
+        let filtered = run_with_config(config);
+
+        assert_eq!(filtered, FilterRuntimeType::None);
+    }
+
+    #[test]
+    fn value_and_transform() {
+        let filter_types = [
+            FilterRuntimeType::ShortEscapee,
+            FilterRuntimeType::LongEscapee,
+            FilterRuntimeType::Cycler,
+            FilterRuntimeType::TranslatedCycler,
+            FilterRuntimeType::Recurrence,
+            FilterRuntimeType::Bouncer,
+            FilterRuntimeType::Ctl,
+            FilterRuntimeType::None,
+        ];
+
+        for filter_type in filter_types {
+            assert_eq!(
+                FilterRuntimeType::transform(filter_type.value()),
+                filter_type
+            );
+        }
+
+        assert_eq!(FilterRuntimeType::Cycler.value(), Some(2));
+        assert_eq!(FilterRuntimeType::None.value(), None);
+        assert_eq!(
+            FilterRuntimeType::transform(Some(42)),
+            FilterRuntimeType::None
+        );
+    }
 }
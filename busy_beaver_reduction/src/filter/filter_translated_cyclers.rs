@@ -3,134 +3,172 @@ use std::collections::HashMap;
 use crate::turing_machine::direction::Direction;
 use crate::turing_machine::turing_machine::TuringMachine;
 
+/// Configuration of a Turing machine when it broke a record,
+/// reaching a new cell of the tape.
+struct Record {
+    /// coordinate of the new cell, relative to the cell
+    /// the Turing machine started on
+    head_position: i64,
+    /// coordinate of the first cell of `tape`
+    tape_start: i64,
+    /// the tape, from the cell furthest from the record
+    /// in the opposite direction up to the new cell
+    tape: Vec<u8>,
+    /// the coordinate furthest from the new cell, in the opposite
+    /// direction, the head visited since the record was broken
+    furthest_back: i64,
+}
+
 pub struct FilterTranslatedCyclers {
     // u8 -> state,
-    // direction -> direction of increase
-    // Vec<u8> -> tape content
-    history: HashMap<(u8, Direction), Vec<u8>>,
+    // direction -> direction of the record
+    // Record -> configuration when the record was broken
+    history: HashMap<(u8, Direction), Record>,
+    // leftmost and rightmost coordinates visited by the head
+    leftmost: i64,
+    rightmost: i64,
 }
 
 impl FilterTranslatedCyclers {
     pub fn new() -> Self {
         return FilterTranslatedCyclers {
             history: HashMap::new(),
+            leftmost: 0,
+            rightmost: 0,
         };
     }
 
     /// Given the current state of a `TuringMachine`, applies the following filter:
     ///
-    /// 1. When the machine's tape reaches a new cell in a certain state
-    /// for the `first time`: add the  `(current_state, direction)` = `(tape)`
-    /// entry in the history hashmap.
+    /// 1. When the machine's head reaches a new cell, to the left or to the right
+    /// of all the cells visited before, it breaks a record. If it is the `first time`
+    /// a record is broken in the current state and direction, the configuration
+    /// is added in the history hashmap, keyed by `(current_state, direction)`.
+    ///
+    /// 2. While running, for every record in the history, the furthest coordinate
+    /// the head went back to, in the opposite direction of the record, is tracked.
     ///
-    /// 2. When the machine's tape reaches a new cell in the same state
-    /// for the `second time`, it means that a translated cycle could have occurred
-    /// between the first and the second appearance": add the `(current_state, direction)` = `(tape)`
-    /// entry in the possible cyclers hashmap
+    /// 3. When a record is broken `again` in the same state and direction, the machine
+    /// is a translated cycler if the tape between the furthest coordinate the head went
+    /// back to and the record is the same as the tape behind the new record, shifted by
+    /// the distance between the records: the cells further back are never read again.
     ///
-    /// 3. When the machine's tape reaches a new cell in the same state
-    /// for the `third time`, it can be verified if the cycle took place:
-    /// if tape has the same content between (1st appearence, 2nd appearence) and
-    /// (2nd appearece, 3rd appearence) shifted L positions, it means it is a translated cycler.
+    /// 4. If the tape differs, the record in the history is replaced by the new one.
     ///
-    /// 4. If the tape differs, update the history and the possible cycler's hashmap:
-    /// - possible cycler -> history,
-    /// - state that filtered out the possible cycler -> possible cycler.
+    /// The records are tracked with the signed coordinate of the head, so
+    /// translated cyclers drifting in both directions are detected, regardless
+    /// of the cells written on the other side of the tape.
     pub fn filter(&mut self, turing_machine: &TuringMachine) -> bool {
-        // if the tape did not increase in the last iteration,
-        // the filer is considered passed
-        if turing_machine.tape_increased == false {
-            return true;
-        }
+        let head_position = turing_machine.head_position;
 
-        // extract the direction in
-        // which the tape increased
+        // extract the direction of the record, if one was broken
         let direction;
-        match turing_machine.head_position {
-            0 => direction = Direction::LEFT,
-            _ => direction = Direction::RIGHT,
-        };
-
-        let history_entry = self.history.get(&(turing_machine.current_state, direction));
+        if head_position < self.leftmost {
+            self.leftmost = head_position;
+            direction = Direction::LEFT;
+        } else if head_position > self.rightmost {
+            self.rightmost = head_position;
+            direction = Direction::RIGHT;
+        } else {
+            // if no record was broken in the last iteration,
+            // only track how far back the head went
+            self.update_furthest_back(head_position);
+            return true;
+        }
 
-        match history_entry {
-            Some(_) => {
-                // if the current state was already a possible cycler,
-                // check if the cycle was actually executed
-                let check_cycler = self.check_possible_cycler(turing_machine, direction);
+        self.update_furthest_back(head_position);
 
-                // if it wasn't, update the history
-                if check_cycler == false {
-                    self.insert_history(
-                        turing_machine.current_state,
-                        direction,
-                        turing_machine.tape.clone(),
-                    );
-                }
+        let key = (turing_machine.current_state, direction);
 
+        // if the current state broke a record in the same
+        // direction before, check if the cycle was executed
+        if let Some(record) = self.history.get(&key) {
+            if Self::check_possible_cycler(record, turing_machine, direction) == true {
                 // if the cycler exists, return false, meaning the
                 // filter was not passed
-                return !check_cycler;
+                return false;
             }
-            None => {
-                // if the current state was not reached before,
-                // insert it in the history
-                self.insert_history(
-                    turing_machine.current_state,
-                    direction,
-                    turing_machine.tape.clone(),
-                );
-
-                return true;
+        }
+
+        // otherwise, insert the record in the history
+        let record = self.get_record(turing_machine, direction);
+        self.history.insert(key, record);
+
+        return true;
+    }
+
+    /// Updates, for every record in the history, the furthest
+    /// coordinate the head went back to.
+    fn update_furthest_back(&mut self, head_position: i64) {
+        for ((_, direction), record) in self.history.iter_mut() {
+            match direction {
+                Direction::RIGHT => record.furthest_back = record.furthest_back.min(head_position),
+                Direction::LEFT => record.furthest_back = record.furthest_back.max(head_position),
             }
         }
     }
 
-    /// Given a state, a tape position and the number of steps
-    /// executed till reaching this configuration, insert the entry
-    /// in the history's hashmap.
-    fn insert_history(&mut self, state: u8, direction: Direction, tape: Vec<u8>) {
-        self.history.insert((state, direction), tape);
+    /// Gets the record broken by the Turing machine in the given `direction`,
+    /// keeping the tape from the furthest cell visited in the opposite direction.
+    fn get_record(&self, turing_machine: &TuringMachine, direction: Direction) -> Record {
+        let head_position = turing_machine.head_position;
+        let (tape_start, tape_end) = match direction {
+            Direction::RIGHT => (self.leftmost, head_position),
+            Direction::LEFT => (head_position, self.rightmost),
+        };
+
+        return Record {
+            head_position: head_position,
+            tape_start: tape_start,
+            tape: (tape_start..=tape_end)
+                .map(|coordinate| Self::get_symbol(turing_machine, coordinate))
+                .collect(),
+            furthest_back: head_position,
+        };
     }
 
-    /// Knowing that `state` is a possible cycler, which means
-    /// that was already visited twice, it appears another time, in a new
-    /// cell.
-    ///
-    /// This function checks if the values on the tape between (1st_appeareance, 2nd_appearence)
-    /// are the same with the tape values between (2nd appearence, 3rd appearence).
+    /// Gets the symbol found at the signed `coordinate` on the tape.
+    fn get_symbol(turing_machine: &TuringMachine, coordinate: i64) -> u8 {
+        let index = turing_machine.tape_origin as i64 + coordinate;
+
+        if index < 0 || index >= turing_machine.tape.len() as i64 {
+            return turing_machine.blank_symbol;
+        }
+
+        return turing_machine.tape[index as usize];
+    }
+
+    /// Knowing that the current state already broke a record in the same
+    /// `direction`, check if the tape between the furthest coordinate the head
+    /// went back to and the old record is the same as the tape behind the new
+    /// record, at the same distance.
     fn check_possible_cycler(
-        &mut self,
+        record: &Record,
         turing_machine: &TuringMachine,
         direction: Direction,
     ) -> bool {
-        let history_tape = self
-            .history
-            .get(&(turing_machine.current_state, direction))
-            .unwrap();
-        let history_tape_length = (history_tape.len() - 1) as u64;
-        let current_tape_length = (turing_machine.tape.len() - 1) as u64;
-
-        match direction {
-            Direction::RIGHT => {
-                for i in 0..history_tape.len() {
-                    // check if the tape matches in both intervals,
-                    // if it doesn't, it means its not a translated cycler
-                    if turing_machine.tape[(current_tape_length - (i as u64)) as usize]
-                        != history_tape[(history_tape_length - (i as u64)) as usize]
-                    {
-                        return false;
-                    }
-                }
-            }
-            Direction::LEFT => {
-                for i in 0..history_tape.len() {
-                    // check if the tape matches in both intervals,
-                    // if it doesn't, it means its not a translated cycler
-                    if turing_machine.tape[i] != history_tape[i] {
-                        return false;
-                    }
-                }
+        // the number of cells behind the record that can still be read
+        let distance_back = (record.head_position - record.furthest_back).abs();
+        let shift = turing_machine.head_position - record.head_position;
+
+        for offset in 0..=distance_back {
+            let coordinate = match direction {
+                Direction::RIGHT => record.head_position - offset,
+                Direction::LEFT => record.head_position + offset,
+            };
+
+            // the cells outside of the recorded tape were not visited yet
+            let history_symbol = usize::try_from(coordinate - record.tape_start)
+                .ok()
+                .and_then(|index| record.tape.get(index))
+                .copied()
+                .unwrap_or(turing_machine.blank_symbol);
+            let current_symbol = Self::get_symbol(turing_machine, coordinate + shift);
+
+            // check if the tape matches in both intervals,
+            // if it doesn't, it means its not a translated cycler
+            if history_symbol != current_symbol {
+                return false;
             }
         }
 
@@ -143,6 +181,7 @@ mod tests {
     use crate::delta::transition::Transition;
     use crate::delta::transition_function::TransitionFunction;
     use crate::turing_machine::direction::Direction;
+    use crate::turing_machine::special_states::HALT_STATE;
     use crate::turing_machine::turing_machine::TuringMachine;
 
     use super::FilterTranslatedCyclers;
@@ -159,7 +198,13 @@ mod tests {
         transition_function.add_transition(Transition::new_params(2, 0, 3, 1, Direction::LEFT));
         transition_function.add_transition(Transition::new_params(2, 1, 1, 1, Direction::LEFT));
         transition_function.add_transition(Transition::new_params(3, 0, 1, 1, Direction::RIGHT));
-        transition_function.add_transition(Transition::new_params(3, 1, 101, 1, Direction::RIGHT));
+        transition_function.add_transition(Transition::new_params(
+            3,
+            1,
+            HALT_STATE,
+            1,
+            Direction::RIGHT,
+        ));
         transition_function.add_transition(Transition::new_params(4, 0, 4, 0, Direction::RIGHT));
         transition_function.add_transition(Transition::new_params(4, 1, 1, 1, Direction::RIGHT));
 
@@ -181,4 +226,31 @@ mod tests {
 
         assert_ne!(turing_machine.steps, maximum_steps);
     }
+
+    #[test]
+    fn filter_translated_cycler_left_after_growing_right() {
+        // writes three 1s to the right, then moves to the left forever,
+        // writing 10 repeatedly: the 1s on the right are never read again
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1RH_1RC1RH_1LD1RH_1LE1LD_0LD1RH")
+                .unwrap();
+        let mut filter_translated_cyclers: FilterTranslatedCyclers = FilterTranslatedCyclers::new();
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        let maximum_steps = 1000;
+
+        turing_machine.make_transition();
+
+        while turing_machine.steps < maximum_steps {
+            if !(filter_translated_cyclers.filter(&turing_machine)) {
+                break;
+            }
+
+            turing_machine.make_transition();
+        }
+
+        assert_eq!(turing_machine.halted, false);
+        assert!(turing_machine.steps < 10);
+        assert!(turing_machine.head_position < 0);
+    }
 }
@@ -1,7 +1,10 @@
 pub mod filter;
+pub mod filter_bouncer;
 pub mod filter_compile;
+pub mod filter_ctl;
 pub mod filter_cyclers;
 pub mod filter_escapees;
 pub mod filter_generate;
+pub mod filter_recurrence;
 pub mod filter_runtime;
 pub mod filter_translated_cyclers;
@@ -1,16 +1,41 @@
-use std::sync::mpsc::{Receiver, Sender};
-use std::thread;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
 
-use log::info;
+use log::{error, info};
 
 use crate::delta::transition_function::TransitionFunction;
-use crate::generator::generator_transition_function::GeneratorTransitionFunction;
+use crate::filter::filter_compile::FilterCompile;
+use crate::filter::filter_generate::FilterGenerateStats;
+use crate::generator::generator_checkpoint::GenerationCheckpoint;
+use crate::generator::generator_file::TransitionFunctionWriter;
+use crate::generator::generator_transition_function::{
+    GenerationAlgorithm, GeneratorTransitionFunction,
+};
+use crate::progress::progress::ProgressEvent;
+use crate::turing_machine::direction::Direction;
 
-const BATCH_SIZE: usize = 100;
+const DIRECTIONS: [Direction; 2] = [Direction::LEFT, Direction::RIGHT];
 
 pub struct Generator {
     pub number_of_states: u8,
+    pub number_of_symbols: u8,
+    pub batch_size: usize,
+    pub generation_algorithm: GenerationAlgorithm,
     pub transition_functions: Vec<TransitionFunction>,
+    pub number_of_transition_functions: usize,
+    writer: Option<TransitionFunctionWriter>,
+    checkpoint: Option<(PathBuf, usize)>,
+    resume_checkpoint: Option<GenerationCheckpoint>,
+    shutdown: Option<Arc<AtomicBool>>,
+    /// thread generating the transition functions, that returns
+    /// the statistics of the generation filters once it finished
+    generation_handle: Option<JoinHandle<FilterGenerateStats>>,
+    /// statistics of the generation filters, set after `generate` finished
+    pub filter_generate_stats: Option<FilterGenerateStats>,
 
     pub tx_unfiltered_functions: Option<Sender<Vec<TransitionFunction>>>,
     pub rx_filtered_functions: Receiver<Vec<TransitionFunction>>,
@@ -19,33 +44,110 @@ pub struct Generator {
 impl Generator {
     pub fn new(
         number_of_states: u8,
+        number_of_symbols: u8,
+        batch_size: usize,
+        generation_algorithm: GenerationAlgorithm,
         tx_unfiltered_functions: Sender<Vec<TransitionFunction>>,
         rx_filtered_functions: Receiver<Vec<TransitionFunction>>,
     ) -> Self {
         Generator {
             transition_functions: Vec::new(),
+            number_of_transition_functions: 0,
+            writer: None,
+            checkpoint: None,
+            resume_checkpoint: None,
+            shutdown: None,
+            generation_handle: None,
+            filter_generate_stats: None,
             number_of_states: number_of_states,
+            number_of_symbols: number_of_symbols,
+            batch_size: batch_size,
+            generation_algorithm: generation_algorithm,
             tx_unfiltered_functions: Some(tx_unfiltered_functions),
             rx_filtered_functions: rx_filtered_functions,
         }
     }
 
+    /// Makes the generator write the filtered transition functions
+    /// to the file found at `path`, one encoding per line, instead
+    /// of keeping them in `self.transition_functions`.
+    ///
+    /// Used for generations that do not fit in memory; the transition
+    /// functions can be read back with a `TransitionFunctionReader`.
+    pub fn set_output_path(&mut self, path: &Path) -> io::Result<()> {
+        self.writer = Some(TransitionFunctionWriter::create(path)?);
+
+        return Ok(());
+    }
+
+    /// Makes the generator checkpoint the generation to the file found at `path`,
+    /// every `interval` batches, so it can be resumed if the process dies.
+    ///
+    /// Only the generation with a deque of transition indexes
+    /// ( `GenerationAlgorithm::DequeVec` ) can be checkpointed.
+    pub fn set_checkpoint(&mut self, path: &Path, interval: usize) {
+        self.checkpoint = Some((path.to_path_buf(), interval));
+    }
+
+    /// Makes the generator resume the generation from the checkpoint found at
+    /// `path`, instead of starting from scratch; the transition functions
+    /// sent before the checkpoint was taken are not generated again.
+    ///
+    /// The checkpoint is taken by the generation with a deque of transition
+    /// indexes, so the generation algorithm is set to `GenerationAlgorithm::DequeVec`.
+    pub fn resume_from_checkpoint(&mut self, path: &Path) -> io::Result<()> {
+        self.resume_checkpoint = Some(GenerationCheckpoint::load(path)?);
+        self.generation_algorithm = GenerationAlgorithm::DequeVec;
+
+        return Ok(());
+    }
+
+    /// Sets the flag that stops the generation once it is set, e.g. by
+    /// Ctrl-C; the transition functions generated until then are still
+    /// filtered and received.
+    pub fn set_shutdown(&mut self, shutdown: Arc<AtomicBool>) {
+        self.shutdown = Some(shutdown);
+    }
+
     /// Creates a new thread were the all the generation
     /// of transition functions will take place.
-    fn send_unfiletered(&mut self) {
+    ///
+    /// If `tx_progress` is given, the batches are relayed to the filter
+    /// through another thread, that sends a `ProgressEvent::Generated`
+    /// event for each one of them.
+    fn send_unfiletered(&mut self, tx_progress: Option<Sender<ProgressEvent>>) {
         let mut generator: GeneratorTransitionFunction =
-            GeneratorTransitionFunction::new(self.number_of_states);
+            GeneratorTransitionFunction::new(self.number_of_states, self.number_of_symbols);
+        generator.resume_checkpoint = self.resume_checkpoint.take();
+        generator.shutdown = self.shutdown.clone();
+
+        if let Some((checkpoint_path, checkpoint_interval)) = &self.checkpoint {
+            generator.checkpoint_path = Some(checkpoint_path.clone());
+            generator.checkpoint_interval = *checkpoint_interval;
+        }
 
         // check if the tx for the channel with unfiltered transition functions
         // was set, and if it was, start generating the transition functions
         match &self.tx_unfiltered_functions {
             Some(sender) => {
-                let tx_unfiltered_functions: Sender<Vec<TransitionFunction>> = sender.clone();
+                let mut tx_unfiltered_functions: Sender<Vec<TransitionFunction>> = sender.clone();
+                let batch_size = self.batch_size;
+                let generation_algorithm = self.generation_algorithm;
+
+                if let Some(tx_progress) = tx_progress {
+                    tx_unfiltered_functions =
+                        Self::relay_with_progress(tx_unfiltered_functions, tx_progress);
+                }
+
+                self.generation_handle = Some(thread::spawn(move || {
+                    generator.generate_all_transition_functions(
+                        generation_algorithm,
+                        tx_unfiltered_functions,
+                        batch_size,
+                    );
 
-                thread::spawn(move || {
-                    generator
-                        .generate_all_transition_functions(tx_unfiltered_functions, BATCH_SIZE);
-                });
+                    return generator.filter_generate.statistics();
+                }));
             }
             None => {}
         }
@@ -55,15 +157,70 @@ impl Generator {
         let _ = std::mem::replace(&mut self.tx_unfiltered_functions, None);
     }
 
+    /// Creates a new thread that forwards every batch of transition functions
+    /// received through the returned sender to `tx_unfiltered_functions`,
+    /// sending a `ProgressEvent::Generated` event for each batch.
+    fn relay_with_progress(
+        tx_unfiltered_functions: Sender<Vec<TransitionFunction>>,
+        tx_progress: Sender<ProgressEvent>,
+    ) -> Sender<Vec<TransitionFunction>> {
+        let (tx_generated_functions, rx_generated_functions): (
+            Sender<Vec<TransitionFunction>>,
+            Receiver<Vec<TransitionFunction>>,
+        ) = channel();
+
+        thread::spawn(move || {
+            for transition_functions in rx_generated_functions.iter() {
+                // the progress is only informative, so a dropped
+                // receiver must not stop the generation
+                let _ = tx_progress.send(ProgressEvent::Generated(transition_functions.len()));
+
+                if tx_unfiltered_functions.send(transition_functions).is_err() {
+                    break;
+                }
+            }
+        });
+
+        return tx_generated_functions;
+    }
+
     /// Listens to the channel for filtered transitions functions,
-    /// and once received, extends the `self.transition_functions` vector.
+    /// and once received, extends the `self.transition_functions` vector,
+    /// or writes them to the output file, if one was set.
     ///
     /// Listens until the connection of the channel will be dropped by the sender.
     /// After it stops listening, logs a statistic of the filtering done.
-    fn receive_filtered(&mut self) {
+    fn receive_filtered(&mut self, tx_progress: &Option<Sender<ProgressEvent>>) {
         for transition_functions_filtered in self.rx_filtered_functions.iter() {
-            self.transition_functions
-                .extend(transition_functions_filtered);
+            self.number_of_transition_functions += transition_functions_filtered.len();
+
+            if let Some(tx_progress) = tx_progress {
+                let _ =
+                    tx_progress.send(ProgressEvent::Filtered(transition_functions_filtered.len()));
+            }
+
+            match &mut self.writer {
+                Some(writer) => {
+                    for transition_function in transition_functions_filtered.iter() {
+                        if let Err(error) = writer.write(transition_function) {
+                            error!("While writing a transition function to the file: {}", error);
+                        }
+                    }
+                }
+                None => {
+                    self.transition_functions
+                        .extend(transition_functions_filtered);
+                }
+            }
+        }
+
+        if let Some(writer) = &mut self.writer {
+            if let Err(error) = writer.flush() {
+                error!(
+                    "While flushing the transition functions to the file: {}",
+                    error
+                );
+            }
         }
 
         self.filter_status();
@@ -72,12 +229,14 @@ impl Generator {
     /// Calculates what percentage of the transition functions
     /// have been filtered by the compile time filter.
     fn filter_status(&mut self) {
-        let maximum_no_of_transition_functions: usize =
+        let maximum_no_of_transition_functions: u128 =
             GeneratorTransitionFunction::get_maximum_no_of_transition_functions(
                 self.number_of_states,
+                self.number_of_symbols,
             );
 
-        let filtered_total = maximum_no_of_transition_functions - self.transition_functions.len();
+        let filtered_total = maximum_no_of_transition_functions
+            .saturating_sub(self.number_of_transition_functions as u128);
         let filtered_percentage =
             filtered_total as f64 * 100.0 / maximum_no_of_transition_functions as f64;
 
@@ -87,8 +246,90 @@ impl Generator {
         );
     }
 
-    pub fn generate(&mut self) {
-        self.send_unfiletered();
-        self.receive_filtered();
+    /// Generates and filters all the transition functions with `number_of_states`
+    /// states and `number_of_symbols` symbols on the current thread, without
+    /// channels, e.g. for tests or for small numbers of states.
+    ///
+    /// The transition functions are the same as the ones of `generate`, up to their order.
+    ///
+    /// Returns the filtered transition functions.
+    pub fn generate_collected(
+        number_of_states: u8,
+        number_of_symbols: u8,
+    ) -> Vec<TransitionFunction> {
+        let mut filter_compile = FilterCompile::new(
+            number_of_states as usize,
+            number_of_symbols as usize,
+            DIRECTIONS.len(),
+        );
+
+        return filter_compile.filter_batch(
+            GeneratorTransitionFunction::iter(number_of_states, number_of_symbols).collect(),
+        );
+    }
+
+    /// Generates and filters all the transition functions.
+    ///
+    /// If `tx_progress` is given, `ProgressEvent::Generated` and
+    /// `ProgressEvent::Filtered` events are sent through it for every batch.
+    pub fn generate(&mut self, tx_progress: Option<Sender<ProgressEvent>>) {
+        self.send_unfiletered(tx_progress.clone());
+        self.receive_filtered(&tx_progress);
+
+        if let Some(generation_handle) = self.generation_handle.take() {
+            self.filter_generate_stats = generation_handle.join().ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::filter::Filter;
+
+    #[test]
+    fn generate_collected() {
+        let transition_functions = Generator::generate_collected(2, 2);
+
+        // the same transition functions as the ones generated
+        // and filtered by the threads
+        let (tx_unfiltered_functions, rx_unfiltered_functions) = channel();
+        let (tx_filtered_functions, rx_filtered_functions) = channel();
+
+        let filter_handle = thread::spawn(move || {
+            let mut filter = Filter::new(tx_filtered_functions, rx_unfiltered_functions, 2, 2);
+            filter.receive_all_unfiltered();
+        });
+
+        let mut generator = Generator::new(
+            2,
+            2,
+            100,
+            GenerationAlgorithm::Deque,
+            tx_unfiltered_functions,
+            rx_filtered_functions,
+        );
+        generator.generate(None);
+        let _ = filter_handle.join();
+
+        let encode = |transition_functions: &[TransitionFunction]| -> Vec<String> {
+            let mut encodings: Vec<String> = transition_functions
+                .iter()
+                .map(|transition_function| transition_function.encode())
+                .collect();
+            encodings.sort();
+            encodings
+        };
+
+        assert_eq!(transition_functions.len(), 315);
+        assert_eq!(
+            encode(&transition_functions),
+            encode(&generator.transition_functions)
+        );
+
+        // the BB(2) champion is kept, as the mirror of 1RB1LB_1LA1RH
+        assert!(transition_functions.iter().any(|transition_function| {
+            transition_function.to_standard_notation().unwrap() == "1LB1RB_1RA1RH"
+        }));
     }
 }
@@ -0,0 +1,74 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// State of a generation with a deque of transition indexes
+/// ( see `GeneratorTransitionFunction::generate_all_transition_combiation_dequeue_with_vec` ),
+/// saved to disk so that the generation can be resumed if the process dies.
+///
+/// The checkpoint marks the frontier of the generation: every transition
+/// function emitted before it was taken is not part of `queue` or `pending`,
+/// so resuming from it does not emit them again.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GenerationCheckpoint {
+    /// partially completed transition functions, left to be expanded
+    pub queue: Vec<Vec<u8>>,
+    /// completed transition functions, not emitted yet
+    pub pending: Vec<Vec<u8>>,
+    /// number of transitions of the deepest transition function expanded
+    pub deepness: u8,
+    /// number of transition functions emitted before the checkpoint
+    pub emitted: usize,
+}
+
+impl GenerationCheckpoint {
+    /// Writes the checkpoint to the file found at `path`.
+    ///
+    /// The checkpoint is first written to a temporary file, which
+    /// then replaces the previous one, so a crash while saving
+    /// does not corrupt the last checkpoint.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let temporary_path = path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&temporary_path)?);
+
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()?;
+        drop(writer);
+
+        return fs::rename(temporary_path, path);
+    }
+
+    /// Reads the checkpoint from the file found at `path`.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+
+        return Ok(serde_json::from_reader(reader)?);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_and_load() {
+        let path = std::env::temp_dir().join(format!(
+            "busy_beaver_checkpoint_{}.json",
+            std::process::id()
+        ));
+        let checkpoint = GenerationCheckpoint {
+            queue: vec![vec![1, 14], vec![2, 15]],
+            pending: vec![vec![1, 14, 27, 40]],
+            deepness: 2,
+            emitted: 100,
+        };
+
+        checkpoint.save(&path).unwrap();
+        let loaded = GenerationCheckpoint::load(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(loaded, checkpoint);
+    }
+}
@@ -0,0 +1,129 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Lines, Write};
+use std::path::Path;
+
+use crate::delta::transition_function::TransitionFunction;
+
+/// Writes `TransitionFunction`s to a file, one encoding per line,
+/// so that generations that do not fit in memory can be persisted
+/// and executed later on.
+pub struct TransitionFunctionWriter {
+    writer: BufWriter<File>,
+}
+
+impl TransitionFunctionWriter {
+    /// Creates the file found at `path`, truncating it if it already exists.
+    pub fn create(path: &Path) -> io::Result<Self> {
+        let file = File::create(path)?;
+
+        return Ok(TransitionFunctionWriter {
+            writer: BufWriter::new(file),
+        });
+    }
+
+    /// Writes the encoding of the transition function on a new line.
+    pub fn write(&mut self, transition_function: &TransitionFunction) -> io::Result<()> {
+        return writeln!(self.writer, "{}", transition_function.encode());
+    }
+
+    /// Flushes the transition functions that are still buffered to the file.
+    pub fn flush(&mut self) -> io::Result<()> {
+        return self.writer.flush();
+    }
+}
+
+/// Reads the `TransitionFunction`s written by a `TransitionFunctionWriter`,
+/// decoding them one line at a time, without loading the whole file in memory.
+///
+/// The encoding does not contain the number of states and symbols,
+/// so they need to be known when reading.
+pub struct TransitionFunctionReader {
+    lines: Lines<BufReader<File>>,
+    number_of_states: u8,
+    number_of_symbols: u8,
+}
+
+impl TransitionFunctionReader {
+    pub fn open(path: &Path, number_of_states: u8, number_of_symbols: u8) -> io::Result<Self> {
+        let file = File::open(path)?;
+
+        return Ok(TransitionFunctionReader {
+            lines: BufReader::new(file).lines(),
+            number_of_states: number_of_states,
+            number_of_symbols: number_of_symbols,
+        });
+    }
+}
+
+impl Iterator for TransitionFunctionReader {
+    type Item = io::Result<TransitionFunction>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(error) => return Some(Err(error)),
+            };
+
+            // skip the empty lines, such as a trailing one
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let mut transition_function =
+                TransitionFunction::new(self.number_of_states, self.number_of_symbols);
+            if let Err(error) = transition_function.decode(line.trim().to_string()) {
+                return Some(Err(io::Error::new(io::ErrorKind::InvalidData, error)));
+            }
+
+            return Some(Ok(transition_function));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::generator_transition_function::GeneratorTransitionFunction;
+
+    #[test]
+    fn write_and_read() {
+        let path = std::env::temp_dir().join(format!(
+            "busy_beaver_transition_functions_{}.txt",
+            std::process::id()
+        ));
+
+        let mut generator = GeneratorTransitionFunction::new(2, 2);
+        generator.generate_all_transitions();
+
+        // every entry has 2 x 2 x 2 + 1 possible transitions,
+        // combine the ones of the first three entries
+        let mut transition_functions: Vec<TransitionFunction> = Vec::new();
+
+        for index in 0..100 {
+            let mut transition_function = TransitionFunction::new(2, 2);
+            transition_function.add_transition(generator.all_transitions[index % 9]);
+            transition_function.add_transition(generator.all_transitions[9 + (index / 9) % 9]);
+            transition_function.add_transition(generator.all_transitions[18 + index / 81]);
+            transition_functions.push(transition_function);
+        }
+
+        let mut writer = TransitionFunctionWriter::create(&path).unwrap();
+
+        for transition_function in transition_functions.iter() {
+            writer.write(transition_function).unwrap();
+        }
+
+        writer.flush().unwrap();
+
+        let reader = TransitionFunctionReader::open(&path, 2, 2).unwrap();
+        let transition_functions_read: Vec<TransitionFunction> = reader
+            .map(|transition_function| transition_function.unwrap())
+            .collect();
+
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(transition_functions_read.len(), 100);
+        assert_eq!(transition_functions_read, transition_functions);
+    }
+}
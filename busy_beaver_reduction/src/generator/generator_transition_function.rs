@@ -1,27 +1,90 @@
 use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::thread;
 
-use log::info;
+use log::{error, info, warn};
 
 use crate::delta::transition::Transition;
 use crate::delta::transition_function::TransitionFunction;
 use crate::filter::filter_generate::FilterGenerate;
+use crate::generator::generator_checkpoint::GenerationCheckpoint;
 use crate::turing_machine::direction::Direction;
 use crate::turing_machine::special_states::SpecialStates;
 
 const DIRECTIONS: [Direction; 2] = [Direction::LEFT, Direction::RIGHT];
-const ALPHABET: [u8; 2] = [0, 1];
-const GENERATION_ALGORITHM: &str = "RECURISVE";
+/// Number of batches sent between two checkpoints of the generation.
+const CHECKPOINT_INTERVAL: usize = 100;
+
+/// Algorithm used to generate all the transition functions:
+/// - `Recursive`: backtracking, combining the transitions recursively
+/// - `Deque`: breadth first, with a deque of `TransitionFunction`s
+/// - `DequeParallel`: same as `Deque`, split across multiple threads
+/// - `DequeVec`: breadth first, with a deque of transition indexes
+/// - `Tnf`: only the transition functions in Tree Normal Form
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GenerationAlgorithm {
+    Recursive,
+    Deque,
+    DequeParallel,
+    DequeVec,
+    Tnf,
+}
 
 pub struct GeneratorTransitionFunction {
     pub states: Vec<u8>,
     pub states_final: Vec<u8>,
+    /// tape alphabet, the symbols the transitions can read and write
+    pub alphabet: Vec<u8>,
+    /// input alphabet, the first symbols of the tape alphabet,
+    /// which are the only ones the tape holds before the execution
+    pub input_alphabet: Vec<u8>,
     pub all_transitions: Vec<Transition>,
     pub filter_generate: FilterGenerate,
+    /// Maximum length of the queue used by the generation with a deque,
+    /// after which the transition functions are completed depth first.
+    pub max_queue_len: Option<usize>,
+    /// File the generation with a deque of transition indexes
+    /// is checkpointed to, every `checkpoint_interval` batches.
+    pub checkpoint_path: Option<PathBuf>,
+    pub checkpoint_interval: usize,
+    /// Checkpoint the generation with a deque of transition indexes resumes from.
+    pub resume_checkpoint: Option<GenerationCheckpoint>,
+    /// Flag that stops the generation once it is set, e.g. by Ctrl-C; the
+    /// transition functions completed so far are still sent.
+    pub shutdown: Option<Arc<AtomicBool>>,
 }
 
 impl GeneratorTransitionFunction {
-    pub fn new(number_of_states: u8) -> Self {
+    /// Creates the generator of the transition functions with `number_of_states`
+    /// states and `alphabet_size` symbols, whose input alphabet is the tape alphabet.
+    pub fn new(number_of_states: u8, alphabet_size: u8) -> Self {
+        return GeneratorTransitionFunction::with_input_alphabet(
+            number_of_states,
+            alphabet_size,
+            alphabet_size,
+        );
+    }
+
+    /// Creates the generator of the transition functions with `number_of_states`
+    /// states, on a tape alphabet of `alphabet_size` symbols, out of which
+    /// the first `input_alphabet_size` ones form the input alphabet.
+    ///
+    /// The tape starts blank, holding only input symbols, while the transitions
+    /// can write every symbol of the tape alphabet, so they are also defined
+    /// for reading the tape-only symbols, once they were written.
+    pub fn with_input_alphabet(
+        number_of_states: u8,
+        input_alphabet_size: u8,
+        alphabet_size: u8,
+    ) -> Self {
+        assert!(
+            input_alphabet_size >= 1 && input_alphabet_size <= alphabet_size,
+            "The input alphabet must contain the blank symbol and be part of the tape alphabet."
+        );
+
         // initiate the states vector with the starting state
         let mut states: Vec<u8> = vec![SpecialStates::StateStart.value()];
         let mut states_final: Vec<u8> = vec![SpecialStates::StateStart.value()];
@@ -36,67 +99,121 @@ impl GeneratorTransitionFunction {
         // fot the states_final vector also add the halting state
         states_final.push(SpecialStates::StateHalt.value());
 
+        // the tape alphabet is made of the symbols from 0 to alphabet_size - 1,
+        // where 0 is the blank symbol
+        let alphabet: Vec<u8> = (0..alphabet_size).collect();
+        let input_alphabet: Vec<u8> = (0..input_alphabet_size).collect();
+
         info!(
-            "Generator, based on backtracking, with {} states has been created!",
-            number_of_states
+            "Generator, based on backtracking, with {} states and {} symbols has been created!",
+            number_of_states, alphabet_size
         );
 
         return GeneratorTransitionFunction {
             states: states,
             states_final: states_final,
+            alphabet: alphabet,
+            input_alphabet: input_alphabet,
             all_transitions: vec![],
             filter_generate: FilterGenerate::new(
                 number_of_states as usize,
-                ALPHABET.len(),
+                alphabet_size as usize,
                 DIRECTIONS.len(),
             ),
+            max_queue_len: None,
+            checkpoint_path: None,
+            checkpoint_interval: CHECKPOINT_INTERVAL,
+            resume_checkpoint: None,
+            shutdown: None,
         };
     }
 
     /// Considering the following variables:
     ///
     /// - N = states alphabet size
-    /// - A = tape alphabet size (0, 1) = 2
+    /// - A = tape alphabet size (0, 1, ..., `alphabet_size` - 1)
     /// - D = directions size (LEFT & RIGHT) = 2
     ///
     /// A transition function is defined as `f(N x A) = ((N + 1) x A x D)`.
     ///
     /// The number of transitions functions is `((N + 1) x A x D) ^ (N x A)`.
-    pub fn get_maximum_no_of_transition_functions(number_of_states: u8) -> usize {
-        let domain_size: u32 = number_of_states as u32 * ALPHABET.len() as u32;
-        let codomain_size: usize =
-            (number_of_states + 1) as usize * ALPHABET.len() as usize * DIRECTIONS.len() as usize;
-
-        return usize::pow(codomain_size, domain_size);
+    ///
+    /// If the number of transition functions exceeds `u128::MAX`,
+    /// a warning is logged and `u128::MAX` is returned.
+    pub fn get_maximum_no_of_transition_functions(number_of_states: u8, alphabet_size: u8) -> u128 {
+        let domain_size: u32 = number_of_states as u32 * alphabet_size as u32;
+        let codomain_size: u128 =
+            (number_of_states as u128 + 1) * alphabet_size as u128 * DIRECTIONS.len() as u128;
+
+        match u128::checked_pow(codomain_size, domain_size) {
+            Some(maximum_no_of_transition_functions) => {
+                return maximum_no_of_transition_functions;
+            }
+            None => {
+                warn!(
+                    "The number of transition functions with {} states and {} symbols exceeds {}.",
+                    number_of_states,
+                    alphabet_size,
+                    u128::MAX
+                );
+                return u128::MAX;
+            }
+        }
     }
 
     /// Given a `Vec<usize>` that contains indexes of the transitions from `self.all_transitions`
     /// used for making a transition function, build the `TransitionFunction` object and filter it
     /// using the `GenerateFilter`.
     pub fn generate_filter_by_vec(&mut self, indexes: &Vec<u8>) -> bool {
-        let mut transition_function =
-            TransitionFunction::new(self.states.len() as u8, ALPHABET.len() as u8);
+        let transition_function = self.get_transition_function_by_vec(indexes);
+
+        self.filter_generate.filter_all(&transition_function)
+    }
+
+    /// Given a `Vec<usize>` that contains indexes of the transitions from `self.all_transitions`,
+    /// build the `TransitionFunction` made of them.
+    fn get_transition_function_by_vec(&self, indexes: &Vec<u8>) -> TransitionFunction {
+        let mut transition_function = self.new_transition_function();
 
         for index in indexes {
             transition_function.add_transition(self.all_transitions[*index as usize]);
         }
 
-        self.filter_generate.filter_all(&transition_function)
+        return transition_function;
+    }
+
+    /// Creates an empty transition function on the states
+    /// and the input and tape alphabets of the generator.
+    fn new_transition_function(&self) -> TransitionFunction {
+        return TransitionFunction::with_input_alphabet(
+            self.states.len() as u8,
+            self.input_alphabet.len() as u8,
+            self.alphabet.len() as u8,
+        );
+    }
+
+    /// Checks if the generation was requested to stop ( see `shutdown` ).
+    fn is_shutdown(shutdown: &Option<Arc<AtomicBool>>) -> bool {
+        return match shutdown {
+            Some(shutdown) => shutdown.load(Ordering::Relaxed),
+            None => false,
+        };
     }
 
     /// Generates every transition that is possible
     /// withing the `states` and `alphabet` of
     pub fn generate_all_transitions(&mut self) {
-        let alphabet = ALPHABET
+        let alphabet = self
+            .alphabet
             .iter()
             .map(|item| format!("{}", item))
             .collect::<Vec<_>>()
             .join(", ");
 
         let total_possible_transitions = self.states.len()
-            * ALPHABET.len()
+            * self.alphabet.len()
             * self.states_final.len()
-            * ALPHABET.len()
+            * self.alphabet.len()
             * DIRECTIONS.len();
 
         info!(
@@ -106,7 +223,7 @@ impl GeneratorTransitionFunction {
         );
 
         for &from_state in self.states.iter() {
-            for &from_symbol in ALPHABET.iter() {
+            for &from_symbol in self.alphabet.iter() {
                 for &to_state in self.states_final.iter() {
                     // it is necessary to only generate
                     // one transition that goes into the halting state,
@@ -125,7 +242,7 @@ impl GeneratorTransitionFunction {
 
                         self.all_transitions.push(transition);
                     } else {
-                        for &to_symbol in ALPHABET.iter() {
+                        for &to_symbol in self.alphabet.iter() {
                             for &direction in DIRECTIONS.iter() {
                                 let transition: Transition = Transition {
                                     from_state: from_state,
@@ -163,15 +280,17 @@ impl GeneratorTransitionFunction {
     ///
     pub fn generate_all_transition_functions(
         &mut self,
+        generation_algorithm: GenerationAlgorithm,
         tx_unfiltered_functions: Sender<Vec<TransitionFunction>>,
         batch_size: usize,
     ) {
         // desired number of transition for a transition function
         let maximum_number_of_transitions: usize =
-            self.states.len() as usize * ALPHABET.len() as usize;
-        let maximum_number_of_transition_functions: usize =
+            self.states.len() as usize * self.alphabet.len() as usize;
+        let maximum_number_of_transition_functions: u128 =
             GeneratorTransitionFunction::get_maximum_no_of_transition_functions(
-                self.states.len() as u8
+                self.states.len() as u8,
+                self.alphabet.len() as u8,
             );
 
         // if transitions were not generated, generate them
@@ -181,29 +300,68 @@ impl GeneratorTransitionFunction {
 
         info!("Generating all possible transition functions.");
 
-        match GENERATION_ALGORITHM {
-            "DEQUE" => {
+        match generation_algorithm {
+            GenerationAlgorithm::Deque => {
                 // generate all possible functions by combining
                 // every possible function using a deque with TransitionFunctions
                 self.generate_all_transition_combiation_dequeue(
                     maximum_number_of_transitions,
                     &tx_unfiltered_functions,
                     batch_size,
+                    self.max_queue_len,
                 );
             }
-            "DEQUE_VEC" => {
-                // generate all possible functions by combining
-                // every possible function using a deque with Vec<u8> transition indexes
-                self.generate_all_transition_combiation_dequeue_with_vec(
-                    maximum_number_of_transitions as u8,
+            GenerationAlgorithm::DequeParallel => {
+                // generate all possible functions by combining every possible
+                // function using a deque with TransitionFunctions, on multiple threads
+                let number_of_workers = thread::available_parallelism()
+                    .map(|workers| workers.get())
+                    .unwrap_or(1);
+
+                self.generate_all_transition_combiation_dequeue_parallel(
+                    maximum_number_of_transitions,
                     &tx_unfiltered_functions,
                     batch_size,
+                    number_of_workers,
+                    self.max_queue_len,
                 );
             }
-            "RECURSIVE" => {
+            GenerationAlgorithm::Tnf => {
+                // generate only the transition functions in tree normal form,
+                // skipping the ones identical up to interchanging states
+                self.generate_tnf(&tx_unfiltered_functions, batch_size);
+            }
+            GenerationAlgorithm::DequeVec => {
+                // generate all possible functions by combining
+                // every possible function using a deque with Vec<u8> transition indexes,
+                // resuming from the checkpoint if one was given
+                match self.resume_checkpoint.take() {
+                    Some(checkpoint) => {
+                        info!(
+                            "Resuming the generation after {} transition functions.",
+                            checkpoint.emitted
+                        );
+
+                        self.generate_dequeue_with_vec_from_checkpoint(
+                            checkpoint,
+                            maximum_number_of_transitions as u8,
+                            &tx_unfiltered_functions,
+                            batch_size,
+                        );
+                    }
+                    None => {
+                        self.generate_all_transition_combiation_dequeue_with_vec(
+                            maximum_number_of_transitions as u8,
+                            &tx_unfiltered_functions,
+                            batch_size,
+                        );
+                    }
+                }
+            }
+            GenerationAlgorithm::Recursive => {
                 // where all transition functions will be computed
                 let transition_function: &mut TransitionFunction =
-                    &mut TransitionFunction::new(self.states.len() as u8, ALPHABET.len() as u8);
+                    &mut self.new_transition_function();
                 let transition_functions_set: &mut Vec<TransitionFunction> = &mut Vec::new();
                 let index: usize = 0;
                 let deepness: usize = 0;
@@ -214,7 +372,7 @@ impl GeneratorTransitionFunction {
                     transition_functions_set,
                     &tx_unfiltered_functions.clone(),
                     deepness,
-                    maximum_number_of_transition_functions,
+                    maximum_number_of_transitions,
                     batch_size,
                 );
 
@@ -226,8 +384,7 @@ impl GeneratorTransitionFunction {
                         .send(transition_functions_set.clone())
                         .unwrap();
                 }
-            },
-            _ => {}
+            }
         }
 
         info!(
@@ -253,6 +410,10 @@ impl GeneratorTransitionFunction {
         max_deepness: usize,
         batch_size: usize,
     ) {
+        if Self::is_shutdown(&self.shutdown) == true {
+            return;
+        }
+
         // if the maximum depth was reached, exit
         if deepness == max_deepness {
             // add the transition function to the set
@@ -282,7 +443,10 @@ impl GeneratorTransitionFunction {
             // if the transition functions does not contain
             // the current transition key, add the transition to
             // the transition function
-            if !transition_function.transitions.contains_key(transition_key) {
+            if !transition_function
+                .transitions()
+                .contains_key(transition_key)
+            {
                 transition_function.add_transition(self.all_transitions[i]);
 
                 // check if the transition function passes the
@@ -303,89 +467,454 @@ impl GeneratorTransitionFunction {
 
                 // after returing from the recursive call,
                 // delete the transition and continue on with the others
-                transition_function.transitions.remove(transition_key);
+                transition_function.remove_transition(transition_key.0, transition_key.1);
             }
         }
     }
 
+    /// Generates all the transition functions in Tree Normal Form (TNF):
+    /// the states are assigned in the order of their first use, so out of
+    /// the transition functions that are identical up to interchanging
+    /// their non-starting states, only one is generated.
+    ///
+    /// The entries are completed in order, (q_{0}, 0), (q_{0}, 1), (q_{1}, 0), and so on;
+    /// a transition can only move into a state that was already used, or into
+    /// the lowest unused state, which prunes whole subtrees of the generation.
+    ///
+    /// ### Example
+    /// For 3 states, if (q_{0}, 0) moves into q_{1}, then (q_{0}, 1)
+    /// can move into q_{0}, q_{1} or q_{2}, but if (q_{0}, 0) would move into
+    /// q_{2}, it would not be generated, since q_{1} is still unused.
+    pub fn generate_tnf(
+        &mut self,
+        tx_unfiltered_functions: &Sender<Vec<TransitionFunction>>,
+        batch_size: usize,
+    ) {
+        // if transitions were not generated, generate them
+        if self.all_transitions.is_empty() {
+            self.generate_all_transitions();
+        }
+
+        let mut transition_function: TransitionFunction = self.new_transition_function();
+        let mut transition_functions_set: Vec<TransitionFunction> = Vec::new();
+
+        self.generate_tnf_entry(
+            0,
+            SpecialStates::StateStart.value(),
+            &mut transition_function,
+            &mut transition_functions_set,
+            tx_unfiltered_functions,
+            batch_size,
+        );
+
+        // if any transition function remained unsent, send them
+        // to the compile filter
+        if transition_functions_set.len() != 0 {
+            tx_unfiltered_functions
+                .send(transition_functions_set)
+                .unwrap();
+        }
+    }
+
+    /// Completes the entry with the index `entry` of the transition function,
+    /// and recursively the ones after it, knowing that the states up to
+    /// `highest_used_state` were already used.
+    fn generate_tnf_entry(
+        &mut self,
+        entry: usize,
+        highest_used_state: u8,
+        transition_function: &mut TransitionFunction,
+        transition_functions_set: &mut Vec<TransitionFunction>,
+        tx_unfiltered_functions: &Sender<Vec<TransitionFunction>>,
+        batch_size: usize,
+    ) {
+        if Self::is_shutdown(&self.shutdown) == true {
+            return;
+        }
+
+        let number_of_entries = self.states.len() * self.alphabet.len();
+
+        // if all the entries were completed, add the transition function to the set
+        if entry == number_of_entries {
+            transition_functions_set.push(transition_function.clone());
+
+            // check if the set reached the batch size
+            if transition_functions_set.len() == batch_size {
+                tx_unfiltered_functions
+                    .send(transition_functions_set.clone())
+                    .unwrap();
+                transition_functions_set.clear();
+            }
+
+            return;
+        }
+
+        // the state whose row is completed is used as well
+        let from_state = (entry / self.alphabet.len()) as u8;
+        let highest_used_state = highest_used_state.max(from_state);
+
+        // the transitions were generated sequentally, for every entry
+        // there are the same number of possible transitions
+        let maximum_possibilites_for_entry = self.all_transitions.len() / number_of_entries;
+
+        for index in
+            maximum_possibilites_for_entry * entry..maximum_possibilites_for_entry * (entry + 1)
+        {
+            let transition = self.all_transitions[index];
+            let mut next_highest_used_state = highest_used_state;
+
+            if transition.to_state != SpecialStates::StateHalt.value() {
+                // only the lowest unused state can be used for the first time
+                if transition.to_state > highest_used_state + 1 {
+                    continue;
+                }
+
+                next_highest_used_state = highest_used_state.max(transition.to_state);
+            }
+
+            transition_function.add_transition(transition);
+
+            // check if the transition function passes the
+            // generation filters
+            if self.filter_generate.filter_all(transition_function) == true {
+                self.generate_tnf_entry(
+                    entry + 1,
+                    next_highest_used_state,
+                    transition_function,
+                    transition_functions_set,
+                    tx_unfiltered_functions,
+                    batch_size,
+                );
+            }
+
+            transition_function.remove_transition(transition.from_state, transition.from_symbol);
+        }
+    }
+
     /// Generates all possible combinations of transition
     /// with a queue, instead of making use of recursion.
     ///
     /// This method allows better control of the order in
     /// which the transition functions will be generated.
+    ///
+    /// If `max_queue_len` is given, the length of the queue is capped
+    /// to it; see `generate_dequeue_from_seeds`.
     pub fn generate_all_transition_combiation_dequeue(
         &mut self,
         maximum_number_of_transitions: usize,
         tx_unfiltered_functions: &Sender<Vec<TransitionFunction>>,
         batch_size: usize,
+        max_queue_len: Option<usize>,
     ) {
-        let mut transition_functions_set: Vec<TransitionFunction> = Vec::new();
+        let seeds = self.generate_dequeue_seeds();
+
+        Self::generate_dequeue_from_seeds(
+            &self.all_transitions,
+            seeds,
+            &mut self.filter_generate,
+            maximum_number_of_transitions,
+            tx_unfiltered_functions,
+            batch_size,
+            max_queue_len,
+            &self.shutdown,
+        );
+    }
+
+    /// Generates all possible combinations of transition with a queue,
+    /// splitting the work across `number_of_workers` threads.
+    ///
+    /// The seed transition functions, of the form (0, 0) ->, are partitioned
+    /// among the workers, each one running its own queue and sending batches
+    /// through the same channel. Every worker filters with its own `FilterGenerate`,
+    /// whose counters are aggregated at the end.
+    ///
+    /// If `max_queue_len` is given, the queue of every worker is capped to it.
+    pub fn generate_all_transition_combiation_dequeue_parallel(
+        &mut self,
+        maximum_number_of_transitions: usize,
+        tx_unfiltered_functions: &Sender<Vec<TransitionFunction>>,
+        batch_size: usize,
+        number_of_workers: usize,
+        max_queue_len: Option<usize>,
+    ) {
+        let number_of_workers = number_of_workers.max(1);
+        let seeds = self.generate_dequeue_seeds();
+
+        // partition the seeds in a round robin fashion,
+        // so the workers get similarly sized subtrees
+        let mut partitions: Vec<Vec<TransitionFunction>> = vec![Vec::new(); number_of_workers];
+
+        for (index, seed) in seeds.into_iter().enumerate() {
+            partitions[index % number_of_workers].push(seed);
+        }
+
+        let number_of_states = self.states.len();
+        let alphabet_size = self.alphabet.len();
+        let all_transitions = &self.all_transitions;
+        let shutdown = &self.shutdown;
+
+        let filters_generate: Vec<FilterGenerate> = thread::scope(|scope| {
+            let handles: Vec<_> = partitions
+                .into_iter()
+                .map(|partition| {
+                    let tx_unfiltered_functions = tx_unfiltered_functions.clone();
+
+                    scope.spawn(move || {
+                        let mut filter_generate =
+                            FilterGenerate::new(number_of_states, alphabet_size, DIRECTIONS.len());
+
+                        Self::generate_dequeue_from_seeds(
+                            all_transitions,
+                            partition,
+                            &mut filter_generate,
+                            maximum_number_of_transitions,
+                            &tx_unfiltered_functions,
+                            batch_size,
+                            max_queue_len,
+                            shutdown,
+                        );
+
+                        return filter_generate;
+                    })
+                })
+                .collect();
+
+            return handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect();
+        });
+
+        for filter_generate in filters_generate.iter() {
+            self.filter_generate.merge(filter_generate);
+        }
+    }
+
+    /// Gets an iterator over all the transition functions with `number_of_states`
+    /// states and `alphabet_size` symbols that pass the generation filters, the same
+    /// ones generated by the `Deque` algorithm, without any thread or channel.
+    ///
+    /// The transition functions are generated lazily, one for every call of `next`.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// # use busy_beaver_reduction::delta::transition_function::TransitionFunction;
+    /// # use busy_beaver_reduction::generator::generator_transition_function::GeneratorTransitionFunction;
+    /// let first_transition_functions: Vec<TransitionFunction> =
+    ///     GeneratorTransitionFunction::iter(3, 2).take(1000).collect();
+    /// ```
+    pub fn iter(
+        number_of_states: u8,
+        alphabet_size: u8,
+    ) -> impl Iterator<Item = TransitionFunction> {
+        return GeneratorTransitionFunction::iter_with_input_alphabet(
+            number_of_states,
+            alphabet_size,
+            alphabet_size,
+        );
+    }
+
+    /// Same as `iter`, with a tape alphabet of `alphabet_size` symbols, out of which
+    /// the first `input_alphabet_size` ones form the input alphabet
+    /// ( see `with_input_alphabet` ).
+    pub fn iter_with_input_alphabet(
+        number_of_states: u8,
+        input_alphabet_size: u8,
+        alphabet_size: u8,
+    ) -> impl Iterator<Item = TransitionFunction> {
+        let mut generator = GeneratorTransitionFunction::with_input_alphabet(
+            number_of_states,
+            input_alphabet_size,
+            alphabet_size,
+        );
+        generator.generate_all_transitions();
+
+        let maximum_number_of_transitions = generator.states.len() * generator.alphabet.len();
         let maximum_possibilites_for_entry =
-            self.states.len() * ALPHABET.len() * DIRECTIONS.len() + 1;
-        let mut queue: VecDeque<TransitionFunction> = VecDeque::new();
+            generator.all_transitions.len() / maximum_number_of_transitions;
+        let seeds = generator.generate_dequeue_seeds();
+
+        return TransitionFunctionIter {
+            generator: generator,
+            queue: VecDeque::from(seeds),
+            maximum_number_of_transitions: maximum_number_of_transitions,
+            maximum_possibilites_for_entry: maximum_possibilites_for_entry,
+        };
+    }
+
+    /// Counts the transition functions with `number_of_states` states and
+    /// `alphabet_size` symbols that pass the generation filters, the same
+    /// ones generated by the `Deque` algorithm, without building them.
+    ///
+    /// The entries are completed depth first, on a single `TransitionFunction`,
+    /// so there is no queue, channel or batch of transition functions kept in memory.
+    pub fn count_filtered(number_of_states: u8, alphabet_size: u8) -> u128 {
+        let mut generator = GeneratorTransitionFunction::new(number_of_states, alphabet_size);
+        generator.generate_all_transitions();
+
+        let mut transition_function = TransitionFunction::new(number_of_states, alphabet_size);
+
+        return generator.count_filtered_entry(0, &mut transition_function);
+    }
+
+    /// Counts the transition functions that pass the generation filters and
+    /// can be completed from `transition_function`, whose entries up to `entry`
+    /// were already completed.
+    fn count_filtered_entry(
+        &mut self,
+        entry: usize,
+        transition_function: &mut TransitionFunction,
+    ) -> u128 {
+        let number_of_entries = self.states.len() * self.alphabet.len();
+
+        if entry == number_of_entries {
+            return 1;
+        }
+
+        // the transitions were generated sequentally, for every entry
+        // there are the same number of possible transitions
+        let maximum_possibilites_for_entry = self.all_transitions.len() / number_of_entries;
+        let mut count: u128 = 0;
+
+        for index in
+            maximum_possibilites_for_entry * entry..maximum_possibilites_for_entry * (entry + 1)
+        {
+            let transition = self.all_transitions[index];
+            transition_function.add_transition(transition);
+
+            if self.filter_generate.filter_all(transition_function) == true {
+                count += self.count_filtered_entry(entry + 1, transition_function);
+            }
+
+            transition_function.remove_transition(transition.from_state, transition.from_symbol);
+        }
+
+        return count;
+    }
+
+    /// Creates the transition functions the generation with a queue starts from,
+    /// that separately contain all the transitions of the form (0, 0) ->,
+    /// and pass the generation filters.
+    fn generate_dequeue_seeds(&mut self) -> Vec<TransitionFunction> {
+        let maximum_possibilites_for_entry =
+            self.states.len() * self.alphabet.len() * DIRECTIONS.len() + 1;
+        let mut seeds: Vec<TransitionFunction> = Vec::new();
 
-        // initialise the queue with transition function that separately
-        // contain all the transitions of the form (0, 0) ->
         for index in 0..maximum_possibilites_for_entry {
-            let mut transition_function: TransitionFunction =
-                TransitionFunction::new(self.states.len() as u8, ALPHABET.len() as u8);
+            let mut transition_function: TransitionFunction = self.new_transition_function();
             transition_function.add_transition(self.all_transitions[index]);
 
             if self.filter_generate.filter_all(&transition_function) == true {
-                queue.push_back(transition_function);
+                seeds.push(transition_function);
             }
         }
 
+        return seeds;
+    }
+
+    /// Generates all the transition functions that can be completed
+    /// from the given `seeds`, using a queue, and sends them in batches
+    /// of `batch_size` through the channel.
+    ///
+    /// The queue holds every partially completed transition function of the
+    /// current depth, so for 4+ states it can grow to millions of entries.
+    /// If `max_queue_len` is given and the queue exceeds it, the transition
+    /// functions taken out of the queue are completed depth first, which
+    /// does not grow the queue, until it drains below half of the cap.
+    ///
+    /// Stops early once the `shutdown` flag is set.
+    fn generate_dequeue_from_seeds(
+        all_transitions: &[Transition],
+        seeds: Vec<TransitionFunction>,
+        filter_generate: &mut FilterGenerate,
+        maximum_number_of_transitions: usize,
+        tx_unfiltered_functions: &Sender<Vec<TransitionFunction>>,
+        batch_size: usize,
+        max_queue_len: Option<usize>,
+        shutdown: &Option<Arc<AtomicBool>>,
+    ) {
+        let mut transition_functions_set: Vec<TransitionFunction> = Vec::new();
+        // every entry has the same number of possible transitions
+        let maximum_possibilites_for_entry = all_transitions.len() / maximum_number_of_transitions;
+        let mut queue: VecDeque<TransitionFunction> = VecDeque::from(seeds);
+        // whether the transition functions are completed depth first,
+        // because the queue exceeded its maximum length
+        let mut depth_first = false;
+
         let mut deepness = 1;
 
         while queue.len() != 0 {
+            if Self::is_shutdown(shutdown) == true {
+                break;
+            }
+
+            if let Some(max_queue_len) = max_queue_len {
+                if depth_first == false && queue.len() > max_queue_len {
+                    warn!(
+                        "Generation queue size {} exceeded the maximum of {}, generating depth first.",
+                        queue.len(),
+                        max_queue_len
+                    );
+                    depth_first = true;
+                } else if depth_first == true && queue.len() <= max_queue_len / 2 {
+                    info!(
+                        "Generation queue size {} drained, generating breadth first.",
+                        queue.len()
+                    );
+                    depth_first = false;
+                }
+            }
+
             // extract the oldest transition function in the queue
-            let mut transition_function = queue.pop_front().unwrap();
-            let transition_function_length = transition_function.transitions.len();
+            let transition_function = queue.pop_front().unwrap();
+            let transition_function_length = transition_function.transitions().len();
 
             if transition_function_length > deepness {
-                info!("Reached deepnes {}", transition_function_length);
-                info!("Generation queue size: {}", queue.len());
+                info!(deepness = transition_function_length; "Reached deepnes {}", transition_function_length);
+                info!(queue_size = queue.len(); "Generation queue size: {}", queue.len());
                 deepness += 1;
             }
 
+            if depth_first == true {
+                // complete the transition function with a stack,
+                // whose length is bounded by the number of transitions
+                let mut stack: Vec<TransitionFunction> = vec![transition_function];
+
+                while let Some(transition_function) = stack.pop() {
+                    if transition_function.transitions().len() == maximum_number_of_transitions {
+                        Self::add_to_batch(
+                            &mut transition_functions_set,
+                            transition_function,
+                            tx_unfiltered_functions,
+                            batch_size,
+                        );
+                    } else {
+                        stack.extend(Self::expand_transition_function(
+                            all_transitions,
+                            transition_function,
+                            filter_generate,
+                            maximum_possibilites_for_entry,
+                        ));
+                    }
+                }
+            }
             // if the transition function reached the desired number of transitions,
             // add it to the set of transition functions;
-            if transition_function_length == maximum_number_of_transitions {
-                transition_functions_set.push(transition_function);
-
-                // if the transition function set reached the batch size,
-                // send the unfiltered transitions to the filter
-                if transition_functions_set.len() == batch_size {
-                    tx_unfiltered_functions
-                        .send(transition_functions_set)
-                        .unwrap();
-                    transition_functions_set = Vec::new();
-                }
+            else if transition_function_length == maximum_number_of_transitions {
+                Self::add_to_batch(
+                    &mut transition_functions_set,
+                    transition_function,
+                    tx_unfiltered_functions,
+                    batch_size,
+                );
             } else {
-                // because the transition were generated sequentally, the first ones
-                // target (q_{0}, 0), than (q_{0}, 1), and so on... iterate through the
-                // next transition that need to be added and check their validty
-                for index in maximum_possibilites_for_entry * transition_function_length
-                    ..maximum_possibilites_for_entry * (transition_function_length + 1)
-                {
-                    let transition_key: &(u8, u8) = &(
-                        self.all_transitions[index].from_state,
-                        self.all_transitions[index].from_symbol,
-                    );
-
-                    if !transition_function.transitions.contains_key(transition_key) {
-                        transition_function.add_transition(self.all_transitions[index]);
-
-                        // check if the transition function passes the
-                        // generation filters
-                        if self.filter_generate.filter_all(&transition_function) == true {
-                            queue.push_back(transition_function.clone());
-                        }
-
-                        transition_function.transitions.remove(transition_key);
-                    }
-                }
+                queue.extend(Self::expand_transition_function(
+                    all_transitions,
+                    transition_function,
+                    filter_generate,
+                    maximum_possibilites_for_entry,
+                ));
             }
 
             if queue.len() < queue.capacity() / 2 {
@@ -393,12 +922,72 @@ impl GeneratorTransitionFunction {
             }
         }
 
-        // if any transition function remained unsent, send them 
+        // if any transition function remained unsent, send them
         // to the compile filter
         if transition_functions_set.len() != 0 {
             tx_unfiltered_functions
-            .send(transition_functions_set)
-            .unwrap();
+                .send(transition_functions_set)
+                .unwrap();
+        }
+    }
+
+    /// Gets the transition functions obtained by adding to the given one each
+    /// possible transition for its next entry, that pass the generation filters.
+    fn expand_transition_function(
+        all_transitions: &[Transition],
+        mut transition_function: TransitionFunction,
+        filter_generate: &mut FilterGenerate,
+        maximum_possibilites_for_entry: usize,
+    ) -> Vec<TransitionFunction> {
+        let transition_function_length = transition_function.transitions().len();
+        let mut transition_functions: Vec<TransitionFunction> = Vec::new();
+
+        // because the transition were generated sequentally, the first ones
+        // target (q_{0}, 0), than (q_{0}, 1), and so on... iterate through the
+        // next transition that need to be added and check their validty
+        for index in maximum_possibilites_for_entry * transition_function_length
+            ..maximum_possibilites_for_entry * (transition_function_length + 1)
+        {
+            let transition_key: &(u8, u8) = &(
+                all_transitions[index].from_state,
+                all_transitions[index].from_symbol,
+            );
+
+            if !transition_function
+                .transitions()
+                .contains_key(transition_key)
+            {
+                transition_function.add_transition(all_transitions[index]);
+
+                // check if the transition function passes the
+                // generation filters
+                if filter_generate.filter_all(&transition_function) == true {
+                    transition_functions.push(transition_function.clone());
+                }
+
+                transition_function.remove_transition(transition_key.0, transition_key.1);
+            }
+        }
+
+        return transition_functions;
+    }
+
+    /// Adds a complete transition function to the set, and if the set
+    /// reached the batch size, sends it through the channel.
+    fn add_to_batch(
+        transition_functions_set: &mut Vec<TransitionFunction>,
+        transition_function: TransitionFunction,
+        tx_unfiltered_functions: &Sender<Vec<TransitionFunction>>,
+        batch_size: usize,
+    ) {
+        transition_functions_set.push(transition_function);
+
+        // if the transition function set reached the batch size,
+        // send the unfiltered transitions to the filter
+        if transition_functions_set.len() == batch_size {
+            tx_unfiltered_functions
+                .send(std::mem::take(transition_functions_set))
+                .unwrap();
         }
     }
 
@@ -415,16 +1004,19 @@ impl GeneratorTransitionFunction {
     ///
     /// To filter the `Vec` of transition indexes, a `TransitionFunction`
     /// object is built before the filtering is done.
+    ///
+    /// If `self.checkpoint_path` is set, the generation is checkpointed
+    /// to it every `self.checkpoint_interval` batches, and once it is stopped
+    /// by the `shutdown` flag.
     pub fn generate_all_transition_combiation_dequeue_with_vec(
         &mut self,
         maximum_number_of_transitions: u8,
         tx_unfiltered_functions: &Sender<Vec<TransitionFunction>>,
         batch_size: usize,
     ) {
-        let mut transition_functions_set: Vec<TransitionFunction> = Vec::new();
         let maximum_possibilites_for_entry =
-            (self.states.len() * ALPHABET.len() * DIRECTIONS.len() + 1) as u8;
-        let mut queue: VecDeque<Vec<u8>> = VecDeque::new();
+            (self.states.len() * self.alphabet.len() * DIRECTIONS.len() + 1) as u8;
+        let mut queue: Vec<Vec<u8>> = Vec::new();
 
         // initialise the queue with transition function that separately
         // contain all the transitions of the form (0, 0) ->
@@ -432,20 +1024,78 @@ impl GeneratorTransitionFunction {
             let transitions_indexes: Vec<u8> = Vec::from([index]);
 
             if self.generate_filter_by_vec(&transitions_indexes) == true {
-                queue.push_back(transitions_indexes);
+                queue.push(transitions_indexes);
             }
         }
 
-        let mut deepness = 1;
+        let checkpoint = GenerationCheckpoint {
+            queue: queue,
+            pending: Vec::new(),
+            deepness: 1,
+            emitted: 0,
+        };
+
+        self.generate_dequeue_with_vec_from_checkpoint(
+            checkpoint,
+            maximum_number_of_transitions,
+            tx_unfiltered_functions,
+            batch_size,
+        );
+    }
+
+    /// Continues the generation with a deque of transition indexes
+    /// from the state saved in the `checkpoint`.
+    ///
+    /// The completed transition functions are kept as transition indexes
+    /// until a batch is sent, right after which the checkpoint is taken,
+    /// so a checkpoint never holds a transition function that was already sent.
+    pub fn generate_dequeue_with_vec_from_checkpoint(
+        &mut self,
+        checkpoint: GenerationCheckpoint,
+        maximum_number_of_transitions: u8,
+        tx_unfiltered_functions: &Sender<Vec<TransitionFunction>>,
+        batch_size: usize,
+    ) {
+        let maximum_possibilites_for_entry =
+            (self.states.len() * self.alphabet.len() * DIRECTIONS.len() + 1) as u8;
+        let mut queue: VecDeque<Vec<u8>> = VecDeque::from(checkpoint.queue);
+        let mut pending: Vec<Vec<u8>> = checkpoint.pending;
+        let mut deepness = checkpoint.deepness;
+        let mut emitted = checkpoint.emitted;
+        let mut batches_sent: usize = 0;
 
         while queue.len() != 0 {
+            // checkpoint the generation before stopping,
+            // so it can be resumed from where it stopped
+            if Self::is_shutdown(&self.shutdown) == true {
+                self.save_checkpoint(&queue, &pending, deepness, emitted);
+                return;
+            }
+
+            // send the completed transition functions in batches,
+            // before expanding the next transition function
+            while pending.len() >= batch_size {
+                let batch: Vec<Vec<u8>> = pending.drain(..batch_size).collect();
+
+                if self.send_transitions_vecs(&batch, tx_unfiltered_functions) == false {
+                    return;
+                }
+
+                emitted += batch.len();
+                batches_sent += 1;
+
+                if batches_sent % self.checkpoint_interval.max(1) == 0 {
+                    self.save_checkpoint(&queue, &pending, deepness, emitted);
+                }
+            }
+
             // extract the oldest transition function in the queue
             let mut transitions_vec = queue.pop_front().unwrap();
             let transitions_vec_length = transitions_vec.len() as u8;
 
             if transitions_vec_length > deepness {
-                info!("Reached deepnes {}", transitions_vec_length);
-                info!("Generation queue size: {}", queue.len());
+                info!(deepness = transitions_vec_length; "Reached deepnes {}", transitions_vec_length);
+                info!(queue_size = queue.len(); "Generation queue size: {}", queue.len());
                 deepness += 1;
             }
 
@@ -461,24 +1111,7 @@ impl GeneratorTransitionFunction {
                 // generation filters
                 if self.generate_filter_by_vec(&transitions_vec) == true {
                     if transitions_vec_length + 1 == maximum_number_of_transitions {
-                        let mut transition_function =
-                            TransitionFunction::new(self.states.len() as u8, ALPHABET.len() as u8);
-
-                        for index in transitions_vec.clone() {
-                            transition_function
-                                .add_transition(self.all_transitions[index as usize]);
-                        }
-
-                        transition_functions_set.push(transition_function);
-
-                        // if the transition function set reached the batch size,
-                        // send the unfiltered transitions to the filter
-                        if transition_functions_set.len() == batch_size {
-                            tx_unfiltered_functions
-                                .send(transition_functions_set)
-                                .unwrap();
-                            transition_functions_set = Vec::new();
-                        }
+                        pending.push(transitions_vec.clone());
                     } else {
                         queue.push_back(transitions_vec.clone());
                     }
@@ -492,12 +1125,649 @@ impl GeneratorTransitionFunction {
             }
         }
 
-        // if any transition function remained unsent, send them 
+        // if any transition function remained unsent, send them
         // to the compile filter
-        if transition_functions_set.len() != 0 {
-            tx_unfiltered_functions
-            .send(transition_functions_set)
+        for batch in pending.chunks(batch_size) {
+            if self.send_transitions_vecs(batch, tx_unfiltered_functions) == false {
+                return;
+            }
+        }
+    }
+
+    /// Builds the transition functions from their transition indexes and sends
+    /// them through the channel.
+    ///
+    /// Returns false if the receiver was dropped, in which case
+    /// the generation should stop.
+    fn send_transitions_vecs(
+        &self,
+        transitions_vecs: &[Vec<u8>],
+        tx_unfiltered_functions: &Sender<Vec<TransitionFunction>>,
+    ) -> bool {
+        let transition_functions: Vec<TransitionFunction> = transitions_vecs
+            .iter()
+            .map(|transitions_vec| self.get_transition_function_by_vec(transitions_vec))
+            .collect();
+
+        if tx_unfiltered_functions.send(transition_functions).is_err() {
+            warn!("The receiver of the generated transition functions was dropped.");
+            return false;
+        }
+
+        return true;
+    }
+
+    /// Saves the state of the generation with a deque of transition
+    /// indexes to `self.checkpoint_path`, if it was set.
+    fn save_checkpoint(
+        &self,
+        queue: &VecDeque<Vec<u8>>,
+        pending: &Vec<Vec<u8>>,
+        deepness: u8,
+        emitted: usize,
+    ) {
+        let Some(checkpoint_path) = &self.checkpoint_path else {
+            return;
+        };
+
+        let checkpoint = GenerationCheckpoint {
+            queue: queue.iter().cloned().collect(),
+            pending: pending.clone(),
+            deepness: deepness,
+            emitted: emitted,
+        };
+
+        match checkpoint.save(checkpoint_path) {
+            Ok(()) => info!(
+                "Checkpointed the generation after {} transition functions.",
+                emitted
+            ),
+            Err(error) => error!("While checkpointing the generation: {}", error),
+        }
+    }
+}
+
+/// Iterator over the transition functions that pass the generation
+/// filters ( see `GeneratorTransitionFunction::iter` ).
+///
+/// The queue is used as a stack, so the transition functions are completed
+/// depth first: the first ones are yielded right away, and the queue
+/// never holds more than a few entries for every transition.
+struct TransitionFunctionIter {
+    generator: GeneratorTransitionFunction,
+    queue: VecDeque<TransitionFunction>,
+    maximum_number_of_transitions: usize,
+    maximum_possibilites_for_entry: usize,
+}
+
+impl Iterator for TransitionFunctionIter {
+    type Item = TransitionFunction;
+
+    fn next(&mut self) -> Option<TransitionFunction> {
+        while let Some(transition_function) = self.queue.pop_back() {
+            // if the transition function reached the desired number of transitions,
+            // yield it, otherwise add the next transitions to it
+            if transition_function.transitions().len() == self.maximum_number_of_transitions {
+                return Some(transition_function);
+            }
+
+            let transition_functions = GeneratorTransitionFunction::expand_transition_function(
+                &self.generator.all_transitions,
+                transition_function,
+                &mut self.generator.filter_generate,
+                self.maximum_possibilites_for_entry,
+            );
+
+            // reversed, so the transition functions are yielded in the order of the transitions
+            self.queue.extend(transition_functions.into_iter().rev());
+        }
+
+        return None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::delta::transition_function::Cell;
+    use crate::turing_machine::turing_machine::{RunOutcome, TuringMachine};
+    use itertools::Itertools;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::hash::{Hash, Hasher};
+    use std::sync::mpsc::{channel, Receiver};
+
+    #[test]
+    fn get_maximum_no_of_transition_functions() {
+        assert_eq!(
+            GeneratorTransitionFunction::get_maximum_no_of_transition_functions(2, 2),
+            12_u128.pow(4)
+        );
+
+        // (7 * 3 * 2) ^ (6 * 3) does not fit in 64 bits
+        let maximum_no_of_transition_functions =
+            GeneratorTransitionFunction::get_maximum_no_of_transition_functions(6, 3);
+
+        assert_eq!(maximum_no_of_transition_functions, 42_u128.pow(18));
+        assert!(maximum_no_of_transition_functions > u64::MAX as u128);
+    }
+
+    #[test]
+    fn get_maximum_no_of_transition_functions_saturates() {
+        assert_eq!(
+            GeneratorTransitionFunction::get_maximum_no_of_transition_functions(100, 10),
+            u128::MAX
+        );
+    }
+
+    #[test]
+    fn generate_all_transitions_three_symbols() {
+        let mut generator = GeneratorTransitionFunction::new(2, 3);
+        generator.generate_all_transitions();
+
+        // N x A entries, each one with N x A x D + 1 possibilities
+        assert_eq!(generator.all_transitions.len(), 2 * 3 * (2 * 3 * 2 + 1));
+    }
+
+    /// Generates all the transition functions with the given number of states and
+    /// symbols, checking that every one of them is complete, and returns their number.
+    fn count_all_transition_functions(number_of_states: usize, alphabet_size: usize) -> usize {
+        let mut generator =
+            GeneratorTransitionFunction::new(number_of_states as u8, alphabet_size as u8);
+        generator.generate_all_transitions();
+
+        let (tx, rx): (
+            Sender<Vec<TransitionFunction>>,
+            Receiver<Vec<TransitionFunction>>,
+        ) = channel();
+
+        let generator_handle = thread::spawn(move || {
+            generator.generate_all_transition_combiation_dequeue_with_vec(
+                (number_of_states * alphabet_size) as u8,
+                &tx,
+                1000,
+            );
+        });
+
+        let mut generated: usize = 0;
+
+        for transition_functions in rx.iter() {
+            for transition_function in transition_functions.iter() {
+                assert_eq!(transition_function.number_of_symbols, alphabet_size as u8);
+                assert_eq!(
+                    transition_function.transitions().len(),
+                    number_of_states * alphabet_size
+                );
+            }
+
+            generated += transition_functions.len();
+        }
+
+        let _ = generator_handle.join();
+
+        return generated;
+    }
+
+    #[test]
+    fn generate_all_transition_functions_three_symbols_one_state() {
+        let alphabet_size: usize = 3;
+        let generated = count_all_transition_functions(1, alphabet_size);
+
+        // for one state, (q0, 0) must halt, since moving back into q0 it would read
+        // blank cells forever; the rest of the entries are free: P ^ (A - 1) possibilities,
+        // (3 x 2 + 1) ^ 2 = 49 transition functions
+        let possibilities_for_entry = alphabet_size * 2 + 1;
+        let expected = possibilities_for_entry.pow((alphabet_size - 1) as u32);
+
+        assert_eq!(generated, expected);
+    }
+
+    #[test]
+    #[ignore = "generates 1.7M transition functions, which is slow in debug builds"]
+    fn generate_all_transition_functions_three_symbols() {
+        let number_of_states: usize = 2;
+        let alphabet_size: usize = 3;
+        let generated = count_all_transition_functions(number_of_states, alphabet_size);
+
+        // for two states, the generation filters only look at (q0, 0) and (q1, 0):
+        // - (q0, 0) must move into q1: (N - 1) x A x D possibilities
+        // - (q1, 0) must not loop in q1 in the same direction: P - A possibilities
+        // - the rest of the entries are free: P ^ (N x A - 2) possibilities
+        let possibilities_for_entry = number_of_states * alphabet_size * 2 + 1;
+        let expected = (number_of_states - 1)
+            * alphabet_size
+            * 2
+            * (possibilities_for_entry - alphabet_size)
+            * possibilities_for_entry.pow((number_of_states * alphabet_size - 2) as u32);
+
+        assert_eq!(generated, expected);
+    }
+
+    /// Runs the generation for 2 states and 2 symbols with the
+    /// given algorithm and returns the number of transition functions generated.
+    fn count_generated(generation_algorithm: GenerationAlgorithm) -> usize {
+        let (tx, rx): (
+            Sender<Vec<TransitionFunction>>,
+            Receiver<Vec<TransitionFunction>>,
+        ) = channel();
+
+        let generator_handle = thread::spawn(move || {
+            let mut generator = GeneratorTransitionFunction::new(2, 2);
+            generator.generate_all_transition_functions(generation_algorithm, tx, 100);
+        });
+
+        let generated: usize = rx
+            .iter()
+            .map(|transition_functions| transition_functions.len())
+            .sum();
+
+        let _ = generator_handle.join();
+
+        return generated;
+    }
+
+    #[test]
+    fn generation_algorithms_same_count() {
+        let generated = count_generated(GenerationAlgorithm::Deque);
+
+        assert!(generated > 0);
+        assert_eq!(
+            count_generated(GenerationAlgorithm::DequeParallel),
+            generated
+        );
+        assert_eq!(count_generated(GenerationAlgorithm::DequeVec), generated);
+        assert_eq!(count_generated(GenerationAlgorithm::Recursive), generated);
+    }
+
+    #[test]
+    fn generation_algorithms_shutdown() {
+        for generation_algorithm in [
+            GenerationAlgorithm::Recursive,
+            GenerationAlgorithm::Deque,
+            GenerationAlgorithm::DequeParallel,
+            GenerationAlgorithm::DequeVec,
+            GenerationAlgorithm::Tnf,
+        ] {
+            let (tx, rx): (
+                Sender<Vec<TransitionFunction>>,
+                Receiver<Vec<TransitionFunction>>,
+            ) = channel();
+
+            // the generation with 4 states takes hours, unless it is stopped
+            let generator_handle = thread::spawn(move || {
+                let mut generator = GeneratorTransitionFunction::new(4, 2);
+                generator.shutdown = Some(Arc::new(AtomicBool::new(true)));
+                generator.generate_all_transition_functions(generation_algorithm, tx, 100);
+            });
+
+            assert_eq!(rx.iter().count(), 0);
+            generator_handle.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn iter_same_count() {
+        let generated = count_generated(GenerationAlgorithm::Deque);
+
+        assert_eq!(GeneratorTransitionFunction::iter(2, 2).count(), generated);
+        assert_eq!(GeneratorTransitionFunction::iter(2, 2).take(10).count(), 10);
+    }
+
+    #[test]
+    fn iter_with_input_alphabet() {
+        // the input alphabet only holds the blank symbol,
+        // so 1 is a tape-only symbol, that can only be written
+        let transition_functions: Vec<TransitionFunction> =
+            GeneratorTransitionFunction::iter_with_input_alphabet(2, 1, 2).collect();
+
+        // the input alphabet does not change which transition functions are generated
+        assert_eq!(
+            transition_functions.len(),
+            GeneratorTransitionFunction::iter(2, 2).count()
+        );
+        assert!(transition_functions.iter().all(|transition_function| {
+            transition_function.input_alphabet_size() == 1
+                && transition_function.tape_alphabet_size() == 2
+        }));
+
+        // the mirror of the BB(2) champion writes only the tape-only symbol
+        let champion = transition_functions
+            .into_iter()
+            .find(|transition_function| {
+                transition_function.to_standard_notation().unwrap() == "1LB1RB_1RA1RH"
+            })
             .unwrap();
+        assert!(champion.is_tape_only_symbol(1));
+
+        let mut turing_machine = TuringMachine::new(champion);
+        assert_eq!(
+            turing_machine.run_to_halt(100),
+            RunOutcome::Halted { steps: 6, score: 4 }
+        );
+        assert_eq!(
+            turing_machine
+                .tape
+                .iter()
+                .filter(|&&symbol| turing_machine
+                    .transition_function
+                    .is_tape_only_symbol(symbol))
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn count_filtered_same_count() {
+        assert_eq!(
+            GeneratorTransitionFunction::count_filtered(3, 1),
+            GeneratorTransitionFunction::iter(3, 1).count() as u128
+        );
+        assert_eq!(
+            GeneratorTransitionFunction::count_filtered(2, 2),
+            count_generated(GenerationAlgorithm::Deque) as u128
+        );
+    }
+
+    #[test]
+    #[ignore = "generates every transition function with 3 states and 2 symbols, which is slow in debug builds"]
+    fn count_filtered_same_count_three_states() {
+        assert_eq!(
+            GeneratorTransitionFunction::count_filtered(3, 2),
+            GeneratorTransitionFunction::iter(3, 2).count() as u128
+        );
+    }
+
+    /// Runs the generation with a queue for the given number of states and 2 symbols,
+    /// with the given number of workers (0 meaning the single-threaded path) and maximum
+    /// queue length, and returns the sorted hashes of the standard notations generated.
+    fn generate_dequeue_hashes(
+        number_of_states: u8,
+        number_of_workers: usize,
+        max_queue_len: Option<usize>,
+    ) -> Vec<u64> {
+        let mut generator = GeneratorTransitionFunction::new(number_of_states, 2);
+        generator.generate_all_transitions();
+        let number_of_entries = number_of_states as usize * 2;
+
+        let (tx, rx): (
+            Sender<Vec<TransitionFunction>>,
+            Receiver<Vec<TransitionFunction>>,
+        ) = channel();
+
+        let generator_handle = thread::spawn(move || {
+            if number_of_workers == 0 {
+                generator.generate_all_transition_combiation_dequeue(
+                    number_of_entries,
+                    &tx,
+                    1000,
+                    max_queue_len,
+                );
+            } else {
+                generator.generate_all_transition_combiation_dequeue_parallel(
+                    number_of_entries,
+                    &tx,
+                    1000,
+                    number_of_workers,
+                    max_queue_len,
+                );
+            }
+        });
+
+        // hash the transition functions while they are received,
+        // to avoid keeping all of them in memory
+        let mut hashes: Vec<u64> = Vec::new();
+
+        for transition_functions in rx.iter() {
+            for transition_function in transition_functions.iter() {
+                let mut hasher = DefaultHasher::new();
+                transition_function
+                    .to_standard_notation()
+                    .unwrap()
+                    .hash(&mut hasher);
+                hashes.push(hasher.finish());
+            }
+        }
+
+        let _ = generator_handle.join();
+
+        hashes.sort_unstable();
+        return hashes;
+    }
+
+    #[test]
+    fn generate_dequeue_parallel_same_functions() {
+        let single_threaded = generate_dequeue_hashes(2, 0, None);
+        let multi_threaded = generate_dequeue_hashes(2, 4, None);
+
+        assert!(single_threaded.len() > 0);
+        assert_eq!(single_threaded, multi_threaded);
+
+        // a tiny cap switches to depth first generation
+        // almost right away, but still generates everything
+        let capped = generate_dequeue_hashes(2, 0, Some(8));
+
+        assert_eq!(single_threaded, capped);
+    }
+
+    #[test]
+    #[ignore = "generates every transition function with 3 states and 2 symbols, which is slow in debug builds"]
+    fn generate_dequeue_parallel_same_functions_three_states() {
+        let single_threaded = generate_dequeue_hashes(3, 0, None);
+        let multi_threaded = generate_dequeue_hashes(3, 4, None);
+
+        assert!(single_threaded.len() > 0);
+        assert_eq!(single_threaded, multi_threaded);
+
+        let capped = generate_dequeue_hashes(3, 0, Some(8));
+
+        assert_eq!(single_threaded, capped);
+    }
+
+    /// Runs the generation with a deque of transition indexes for the given number
+    /// of states and 2 symbols, resuming from the `checkpoint` if given, and returns
+    /// the standard notations generated, in the order they were sent.
+    fn generate_dequeue_with_vec_notations(
+        number_of_states: u8,
+        batch_size: usize,
+        checkpoint_path: Option<PathBuf>,
+        checkpoint: Option<GenerationCheckpoint>,
+    ) -> Vec<String> {
+        let mut generator = GeneratorTransitionFunction::new(number_of_states, 2);
+        generator.generate_all_transitions();
+        generator.checkpoint_path = checkpoint_path;
+        generator.checkpoint_interval = 16;
+        let number_of_entries = number_of_states * 2;
+
+        let (tx, rx): (
+            Sender<Vec<TransitionFunction>>,
+            Receiver<Vec<TransitionFunction>>,
+        ) = channel();
+
+        let generator_handle = thread::spawn(move || match checkpoint {
+            Some(checkpoint) => generator.generate_dequeue_with_vec_from_checkpoint(
+                checkpoint,
+                number_of_entries,
+                &tx,
+                batch_size,
+            ),
+            None => generator.generate_all_transition_combiation_dequeue_with_vec(
+                number_of_entries,
+                &tx,
+                batch_size,
+            ),
+        });
+
+        let notations: Vec<String> = rx
+            .iter()
+            .flatten()
+            .map(|transition_function| transition_function.to_standard_notation().unwrap())
+            .collect();
+
+        let _ = generator_handle.join();
+
+        return notations;
+    }
+
+    /// Interrupts the generation right after its only checkpoint, taken after 16
+    /// batches, resumes it from the checkpoint and checks that it yields the same
+    /// transition functions as the uninterrupted generation.
+    fn check_dequeue_with_vec_resume(number_of_states: u8, batch_size: usize) {
+        let checkpoint_path = std::env::temp_dir().join(format!(
+            "busy_beaver_generation_{}_{}.json",
+            std::process::id(),
+            number_of_states
+        ));
+
+        // the transition functions sent after the checkpoint are considered lost
+        let uninterrupted = generate_dequeue_with_vec_notations(
+            number_of_states,
+            batch_size,
+            Some(checkpoint_path.clone()),
+            None,
+        );
+        let checkpoint = GenerationCheckpoint::load(&checkpoint_path).unwrap();
+        let _ = std::fs::remove_file(&checkpoint_path);
+
+        assert_eq!(checkpoint.emitted, 16 * batch_size);
+        assert!(checkpoint.emitted < uninterrupted.len());
+
+        let mut resumed: Vec<String> = uninterrupted[..checkpoint.emitted].to_vec();
+        resumed.extend(generate_dequeue_with_vec_notations(
+            number_of_states,
+            batch_size,
+            None,
+            Some(checkpoint),
+        ));
+
+        assert_eq!(resumed.len(), uninterrupted.len());
+        assert_eq!(
+            resumed.into_iter().collect::<HashSet<String>>(),
+            uninterrupted.into_iter().collect::<HashSet<String>>()
+        );
+    }
+
+    #[test]
+    fn generate_dequeue_with_vec_resume() {
+        check_dequeue_with_vec_resume(2, 100);
+    }
+
+    #[test]
+    #[ignore = "generates every transition function with 3 states and 2 symbols, which is slow in debug builds"]
+    fn generate_dequeue_with_vec_resume_three_states() {
+        check_dequeue_with_vec_resume(3, 100_000);
+    }
+
+    /// Gets a key that is the same for all the transition functions that
+    /// are identical up to interchanging their non-starting states: the
+    /// smallest cells out of all the relabelings of the states.
+    fn get_isomorphism_key(transition_function: &TransitionFunction) -> Vec<Cell> {
+        let non_starting_states: Vec<u8> = (1..transition_function.number_of_states).collect();
+        let mut key: Option<Vec<Cell>> = None;
+
+        for permutation in non_starting_states
+            .iter()
+            .permutations(non_starting_states.len())
+        {
+            let relabel = |state: u8| -> u8 {
+                if state == SpecialStates::StateStart.value()
+                    || state == SpecialStates::StateHalt.value()
+                {
+                    return state;
+                }
+
+                return *permutation[state as usize - 1];
+            };
+
+            let mut relabeled = TransitionFunction::new(
+                transition_function.number_of_states,
+                transition_function.number_of_symbols,
+            );
+
+            for (&(from_state, from_symbol), &(to_state, to_symbol, direction)) in
+                transition_function.transitions().iter()
+            {
+                relabeled.add_transition(Transition::new_params(
+                    relabel(from_state),
+                    from_symbol,
+                    relabel(to_state),
+                    to_symbol,
+                    direction,
+                ));
+            }
+
+            let cells = relabeled.cells();
+
+            if key.as_ref().map_or(true, |key| cells < *key) {
+                key = Some(cells);
+            }
         }
+
+        return key.unwrap();
+    }
+
+    /// Checks that the generation in TNF yields one transition function for every
+    /// class of the generation with a queue identical up to interchanging states.
+    fn check_tnf_matches_deduplicated_generation(number_of_states: u8, number_of_symbols: u8) {
+        let (tx, rx): (
+            Sender<Vec<TransitionFunction>>,
+            Receiver<Vec<TransitionFunction>>,
+        ) = channel();
+
+        let generator_handle = thread::spawn(move || {
+            let mut generator =
+                GeneratorTransitionFunction::new(number_of_states, number_of_symbols);
+            generator.generate_all_transitions();
+            generator.generate_all_transition_combiation_dequeue(
+                (number_of_states * number_of_symbols) as usize,
+                &tx,
+                1000,
+                None,
+            );
+        });
+
+        // keep a hash of the key of every class of
+        // transition functions identical up to interchanging states
+        let mut classes: HashSet<u64> = HashSet::new();
+
+        for transition_functions in rx.iter() {
+            for transition_function in transition_functions.iter() {
+                let mut hasher = DefaultHasher::new();
+                get_isomorphism_key(transition_function).hash(&mut hasher);
+                classes.insert(hasher.finish());
+            }
+        }
+
+        let _ = generator_handle.join();
+
+        let (tx, rx): (
+            Sender<Vec<TransitionFunction>>,
+            Receiver<Vec<TransitionFunction>>,
+        ) = channel();
+
+        let generator_handle = thread::spawn(move || {
+            let mut generator =
+                GeneratorTransitionFunction::new(number_of_states, number_of_symbols);
+            generator.generate_tnf(&tx, 1000);
+        });
+
+        let generated_tnf: usize = rx
+            .iter()
+            .map(|transition_functions| transition_functions.len())
+            .sum();
+
+        let _ = generator_handle.join();
+
+        assert_eq!(generated_tnf, classes.len());
+    }
+
+    #[test]
+    fn generate_tnf_matches_deduplicated_generation() {
+        check_tnf_matches_deduplicated_generation(3, 1);
+    }
+
+    #[test]
+    #[ignore = "generates every transition function with 3 states and 2 symbols, which is slow in debug builds"]
+    fn generate_tnf_matches_deduplicated_generation_three_states_two_symbols() {
+        check_tnf_matches_deduplicated_generation(3, 2);
     }
 }
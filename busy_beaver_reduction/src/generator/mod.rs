@@ -1,2 +1,4 @@
 pub mod generator;
+pub mod generator_checkpoint;
+pub mod generator_file;
 pub mod generator_transition_function;
@@ -0,0 +1,10 @@
+pub mod champions;
+pub mod cli;
+pub mod database;
+pub mod delta;
+pub mod filter;
+pub mod generator;
+pub mod logger;
+pub mod mediator;
+pub mod progress;
+pub mod turing_machine;
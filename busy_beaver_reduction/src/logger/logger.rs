@@ -1,11 +1,124 @@
 use std::env;
+use std::io::Write;
+
+use log::kv::{Key, Value, VisitSource};
+use log::Record;
+use serde_json::{json, Map};
+
+/// Environment variable that selects the format of the logs:
+/// `json` for JSON lines, anything else for the human readable format.
+const LOG_FORMAT: &str = "LOG_FORMAT";
 
 /// Function that will set the `RUST_LOG` environment variable
 /// to use all levels of logging for the project's main executable.
+///
+/// If `LOG_FORMAT` is set to `json`, the records are logged
+/// as JSON lines instead ( see `load_logger_json` ).
 pub fn load_logger() {
+    if env::var(LOG_FORMAT).is_ok_and(|format| format.eq_ignore_ascii_case("json")) {
+        load_logger_json();
+        return;
+    }
+
+    set_logging_level();
+    env_logger::init();
+}
+
+/// Same as `load_logger`, but every record is logged as a JSON line,
+/// that can be parsed by log aggregation tools ( see `format_json` ).
+pub fn load_logger_json() {
+    set_logging_level();
+    env_logger::Builder::from_default_env()
+        .format(|buf, record| {
+            let line = format_json(record, &buf.timestamp().to_string());
+            writeln!(buf, "{}", line)
+        })
+        .init();
+}
+
+fn set_logging_level() {
     let logging = "RUST_LOG";
     let logging_level = "busy_beaver_reduction=trace";
 
     env::set_var(logging, logging_level);
-    env_logger::init();
+}
+
+/// Serializes the record as a JSON object, with its timestamp, level,
+/// target and message, together with its structured key-value pairs,
+/// found under `fields`.
+///
+/// EXAMPLE:
+///
+/// `info!(deepness = 3; "Reached deepness 3")` is serialized as
+/// `{"timestamp":"...","level":"INFO","target":"...","message":"Reached deepness 3","fields":{"deepness":3}}`
+pub fn format_json(record: &Record, timestamp: &str) -> String {
+    let mut fields = JsonFields(Map::new());
+    let _ = record.key_values().visit(&mut fields);
+
+    return json!({
+        "timestamp": timestamp,
+        "level": record.level().as_str(),
+        "target": record.target(),
+        "message": record.args().to_string(),
+        "fields": fields.0,
+    })
+    .to_string();
+}
+
+/// Collects the key-value pairs of a record as JSON values, keeping the
+/// numbers and booleans as they are, and the rest of them as strings.
+struct JsonFields(Map<String, serde_json::Value>);
+
+impl<'kvs> VisitSource<'kvs> for JsonFields {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), log::kv::Error> {
+        let json_value = if let Some(value) = value.to_u64() {
+            json!(value)
+        } else if let Some(value) = value.to_i64() {
+            json!(value)
+        } else if let Some(value) = value.to_f64() {
+            json!(value)
+        } else if let Some(value) = value.to_bool() {
+            json!(value)
+        } else {
+            json!(value.to_string())
+        };
+
+        self.0.insert(key.to_string(), json_value);
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::Level;
+
+    #[test]
+    fn format_json_parseable() {
+        let key_values = [
+            ("deepness", Value::from(3u64)),
+            ("queue_size", Value::from(1024u64)),
+            ("percentage", Value::from(12.5f64)),
+        ];
+        let line = format_json(
+            &Record::builder()
+                .level(Level::Info)
+                .target("busy_beaver_reduction::generator")
+                .args(format_args!("Reached deepness {}", 3))
+                .key_values(&key_values)
+                .build(),
+            "2024-01-01T00:00:00Z",
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&line).unwrap();
+
+        assert_eq!(line.contains('\n'), false);
+        assert_eq!(parsed["level"], "INFO");
+        assert_eq!(parsed["target"], "busy_beaver_reduction::generator");
+        assert_eq!(parsed["message"], "Reached deepness 3");
+        assert_eq!(parsed["timestamp"], "2024-01-01T00:00:00Z");
+        assert_eq!(parsed["fields"]["deepness"], 3);
+        assert_eq!(parsed["fields"]["queue_size"], 1024);
+        assert_eq!(parsed["fields"]["percentage"], 12.5);
+    }
 }
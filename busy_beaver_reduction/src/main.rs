@@ -1,22 +1,72 @@
-mod database;
-mod delta;
-mod filter;
-mod generator;
-mod logger;
-mod mediator;
-mod turing_machine;
-
-use crate::logger::logger::load_logger;
-use crate::mediator::mediator::Mediator;
+use busy_beaver_reduction::champions::champions::verify_champions;
+use busy_beaver_reduction::cli::arguments::Arguments;
+use busy_beaver_reduction::cli::run_stdin::run_encodings;
+use busy_beaver_reduction::logger::logger::load_logger;
+use busy_beaver_reduction::mediator::mediator::Mediator;
 
+use clap::Parser;
 use dotenv::dotenv;
+use log::{error, info};
 
 #[tokio::main]
 async fn main() {
+    let arguments = Arguments::parse();
+
     dotenv().ok();
     load_logger();
 
-    let mut bb_mediator = Mediator::new(3);
+    if arguments.verify_champions == true {
+        match verify_champions(arguments.states) {
+            Ok(()) => info!(
+                "The champion with {} states was verified.",
+                arguments.states
+            ),
+            Err(verify_error) => {
+                error!("{}", verify_error);
+                std::process::exit(1);
+            }
+        }
+
+        return;
+    }
+
+    if arguments.run_stdin == true {
+        let stdin = std::io::stdin();
+
+        if let Err(error) = run_encodings(
+            stdin.lock(),
+            &mut std::io::stdout(),
+            &mut std::io::stderr(),
+            arguments.states,
+            arguments.symbols,
+        ) {
+            error!(
+                "While running the encodings from the standard input: {}",
+                error
+            );
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
+    let mut bb_mediator = Mediator::new(arguments.states, arguments.symbols, arguments.batch_size);
+    if let Some(threads) = arguments.threads {
+        bb_mediator.set_num_threads(threads);
+    }
+    bb_mediator.set_database_batch_size(arguments.database_batch_size);
+    bb_mediator.set_dry_run(arguments.dry_run);
+    bb_mediator.set_halting_only(arguments.halting_only);
+    if let Some(report) = &arguments.report {
+        bb_mediator.set_report_path(report);
+    }
+    bb_mediator.listen_for_shutdown();
+
+    if let Some(max_steps) = arguments.rerun_holdouts {
+        bb_mediator.rerun_holdouts(max_steps).await;
+        return;
+    }
+
     bb_mediator.load_turing_machines().await;
 
     match bb_mediator.loaded {
@@ -1,73 +1,307 @@
+use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
 use std::thread;
+use std::time::Instant;
 use tokio;
 
-use log::info;
+use log::{error, info};
 
 use crate::database::manager::DatabaseManager;
 use crate::database::runner::DatabaseManagerRunner;
+use crate::database::store::TmStore;
+use crate::delta::decode_error::DecodeError;
 use crate::delta::transition_function::TransitionFunction;
 use crate::filter::filter::Filter;
+use crate::filter::filter_runtime::FilterRuntimeConfig;
 use crate::generator::generator::Generator;
+use crate::generator::generator_transition_function::GenerationAlgorithm;
+use crate::mediator::run_report::RunReport;
 use crate::turing_machine::runner::TuringMachineRunner;
-use crate::turing_machine::turing_machine::TuringMachine;
+use crate::turing_machine::turing_machine::{RunOutcome, TuringMachine};
 
-const BATCH_SIZE: usize = 1000;
+/// Default number of Turing machines inserted in the database with a single
+/// statement, independent of the `batch_size` of the generation:
+/// - the generation batches are sent through the channels between the
+/// generator and the filter, so smaller batches keep both of them busy,
+/// while bigger ones send fewer messages
+/// - the database batches are sent to the database, so bigger ones need fewer
+/// round trips, but are limited by the size of the statements
+const DATABASE_BATCH_SIZE: usize = 1000;
+const GENERATION_ALGORITHM: GenerationAlgorithm = GenerationAlgorithm::Deque;
 
 pub struct Mediator {
     number_of_states: u8,
+    number_of_symbols: u8,
+    batch_size: usize,
+    database_batch_size: usize,
     turing_machines: Vec<TuringMachine>,
+    num_threads: Option<usize>,
+    filter_runtime_config: FilterRuntimeConfig,
+    dry_run: bool,
+    halting_only: bool,
+    database: Option<DatabaseManager>,
+    shutdown: Arc<AtomicBool>,
+    report: RunReport,
+    report_path: Option<PathBuf>,
     pub loaded: bool,
 }
 
 impl Mediator {
-    pub fn new(number_of_states: u8) -> Self {
+    pub fn new(number_of_states: u8, number_of_symbols: u8, batch_size: usize) -> Self {
         Mediator {
             number_of_states: number_of_states,
+            number_of_symbols: number_of_symbols,
+            batch_size: batch_size,
+            database_batch_size: DATABASE_BATCH_SIZE,
             turing_machines: vec![],
+            num_threads: None,
+            filter_runtime_config: FilterRuntimeConfig::default(),
+            dry_run: false,
+            halting_only: false,
+            database: None,
+            shutdown: Arc::new(AtomicBool::new(false)),
+            report: RunReport::new(number_of_states, number_of_symbols),
+            report_path: None,
             loaded: false,
         }
     }
 
-    /// Tries to retrieve any turing machine from the database
-    /// that has `number_of_states` states.
+    /// Creates a mediator that stores the Turing machines in the given
+    /// `TmStore`, instead of connecting to the database configured by
+    /// the `DATABASE_URL`, e.g. an in-memory `SqliteStore` for testing.
+    pub fn with_store(
+        number_of_states: u8,
+        number_of_symbols: u8,
+        batch_size: usize,
+        store: Box<dyn TmStore>,
+    ) -> Self {
+        let mut mediator = Mediator::new(number_of_states, number_of_symbols, batch_size);
+        mediator.database = Some(DatabaseManager::from_store(store));
+
+        return mediator;
+    }
+
+    /// Sets the number of threads the Turing machines will be executed on.
+    pub fn set_num_threads(&mut self, num_threads: usize) {
+        self.num_threads = Some(num_threads);
+    }
+
+    /// Sets the runtime filters applied while executing the Turing
+    /// machines, instead of all of them in the default order.
+    pub fn set_filter_runtime_config(&mut self, filter_runtime_config: FilterRuntimeConfig) {
+        self.filter_runtime_config = filter_runtime_config;
+    }
+
+    /// Sets the number of Turing machines inserted in the database with
+    /// a single statement, instead of `DATABASE_BATCH_SIZE`.
+    pub fn set_database_batch_size(&mut self, database_batch_size: usize) {
+        self.database_batch_size = database_batch_size;
+    }
+
+    /// Sets whether the mediator runs without accessing the database:
+    /// the Turing machines are not loaded from it, and the executed
+    /// ones are discarded instead of being inserted.
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    /// Sets whether only the Turing machines that halted are inserted
+    /// in the database; the ones that did not halt are only counted.
+    pub fn set_halting_only(&mut self, halting_only: bool) {
+        self.halting_only = halting_only;
+    }
+
+    /// Sets the file the `RunReport` is written to, as JSON,
+    /// once the Turing machines finished running.
+    pub fn set_report_path(&mut self, path: &Path) {
+        self.report_path = Some(path.to_path_buf());
+    }
+
+    /// Requests the run to stop: the `TuringMachineRunner` finishes the
+    /// batch it is executing and sends it, while the remaining Turing
+    /// machines are not executed; the `DatabaseManagerRunner` then inserts
+    /// everything it received, including its incomplete batch, and exits.
     ///
-    /// If any exist, set the turing machines of the
-    /// mediator to be equal to the ones extracted from
-    /// the database.
+    /// If it is requested while generating, the generation stops as well,
+    /// and none of the transition functions generated are run.
+    pub fn shutdown(&self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+    }
+
+    /// Spawns a task that waits for Ctrl-C, and then
+    /// requests the run to stop gracefully ( see `shutdown` ).
+    ///
+    /// The generation is stopped as well, discarding the transition functions
+    /// generated so far. A second Ctrl-C exits the process right away.
+    pub fn listen_for_shutdown(&self) {
+        let shutdown = self.shutdown.clone();
+
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_err() {
+                return;
+            }
+
+            info!("Received Ctrl-C, stopping after the current batch of Turing machines...");
+            shutdown.store(true, Ordering::Relaxed);
+
+            if tokio::signal::ctrl_c().await.is_ok() {
+                info!("Received Ctrl-C again, exiting right away.");
+                std::process::exit(130);
+            }
+        });
+    }
+
+    /// Checks if any turing machine with `number_of_states` states and
+    /// `number_of_symbols` symbols is stored in the database, by counting
+    /// them, in which case the mediator is marked as `loaded`.
     ///
     /// Used when trying to generate turing machines, in order
-    /// to skip some computations.
+    /// to skip some computations; the turing machines themselves
+    /// are only selected when they are run ( see `run_and_update` ).
     pub async fn load_turing_machines(&mut self) {
-        let db_option = DatabaseManager::new().await;
-
-        match db_option {
-            // if the database manager was succesfully created,
-            // try to select all the turing machines with the
-            // desired number of states
-            Some(mut database_manager) => {
-                let tm_option = database_manager
-                    .select_turing_machines_to_run(self.number_of_states, 2)
-                    .await;
-
-                match tm_option {
-                    // if the select did not fail, check if
-                    // any such Turing Machines exist in the database
-                    Some(turing_machines) => {
-                        // if they do, it means the generation was already done,
-                        // so save the turing machines directly
-                        if turing_machines.len() > 0 {
-                            self.turing_machines = turing_machines;
-                            self.loaded = true;
-                        }
-                    }
-                    None => {}
+        if self.dry_run == true {
+            info!("Dry run, skipped loading the Turing machines from the database.");
+            return;
+        }
+
+        // connect to the database, unless a store was injected,
+        // and keep the connection for running the turing machines
+        if self.database.is_none() {
+            self.database = DatabaseManager::new().await;
+        }
+
+        // if the database manager was succesfully created, count
+        // the turing machines with the desired number of states
+        if let Some(database_manager) = self.database.as_ref() {
+            let count = database_manager
+                .count_turing_machines(self.number_of_states, self.number_of_symbols)
+                .await;
+
+            // if any exist, it means the generation was already done
+            if let Some(count) = count {
+                if count > 0 {
+                    info!("Found {} turing machines in the database.", count);
+                    self.loaded = true;
                 }
             }
-            None => {}
         }
     }
 
+    /// Selects the turing machines from the database with `number_of_states`
+    /// states and `number_of_symbols` symbols that did not halt, and sets
+    /// them as the turing machines of the mediator.
+    ///
+    /// Only their transition functions are kept, so they are
+    /// in their initial configuration, ready to be executed.
+    pub async fn select_turing_machines_to_run(&mut self) {
+        let Some(database_manager) = self.database.as_mut() else {
+            return;
+        };
+
+        if let Some(turing_machines) = database_manager
+            .select_turing_machines_to_run(self.number_of_states, self.number_of_symbols)
+            .await
+        {
+            // the turing machines are executed again from the start,
+            // not from the metrics they were stored with
+            self.turing_machines = turing_machines
+                .into_iter()
+                .map(|turing_machine| TuringMachine::new(turing_machine.transition_function))
+                .collect();
+        }
+    }
+
+    /// Re-runs the holdouts, the turing machines from the database with
+    /// `number_of_states` states and `number_of_symbols` symbols that did
+    /// not halt, for at most `new_max_steps` steps, a higher limit than
+    /// the one they were executed with.
+    ///
+    /// Only the rows of the turing machines that halted with the higher
+    /// limit are updated, since the verdict of the others did not change.
+    ///
+    /// Returns the number of holdouts that halted.
+    pub async fn rerun_holdouts(&mut self, new_max_steps: u64) -> usize {
+        if self.dry_run == true {
+            info!("Dry run, skipped re-running the holdouts from the database.");
+            return 0;
+        }
+
+        if self.database.is_none() {
+            self.database = DatabaseManager::new().await;
+        }
+
+        let Some(database_manager) = self.database.as_mut() else {
+            return 0;
+        };
+
+        let Some(holdouts) = database_manager
+            .select_turing_machines_to_run(self.number_of_states, self.number_of_symbols)
+            .await
+        else {
+            return 0;
+        };
+
+        info!(
+            "Re-running {} holdouts for at most {} steps...",
+            holdouts.len(),
+            new_max_steps
+        );
+
+        // the holdouts are executed again from the start,
+        // not from the metrics they were stored with
+        let mut turing_machines: Vec<TuringMachine> = holdouts
+            .into_iter()
+            .map(|holdout| TuringMachine::new(holdout.transition_function))
+            .collect();
+
+        let filter_runtime_config = &self.filter_runtime_config;
+        turing_machines.par_iter_mut().for_each(|turing_machine| {
+            turing_machine.execute_with_config(new_max_steps, filter_runtime_config);
+        });
+
+        let mut halted: usize = 0;
+
+        for turing_machine in turing_machines {
+            if turing_machine.halted == true {
+                database_manager.update_turing_machine(turing_machine).await;
+                halted += 1;
+            }
+        }
+
+        info!("{} holdouts halted with the higher step limit.", halted);
+
+        return halted;
+    }
+
+    /// Runs a single Turing machine, from the encoding of its transition
+    /// function ( see `TransitionFunction::encode` ), with the states and
+    /// symbols of the mediator, through all the runtime filters, without
+    /// generating the transition functions or accessing the database.
+    ///
+    /// If `max_steps` is not provided, the Turing machine will run
+    /// for the default number of steps of its number of states.
+    ///
+    /// Returns how the execution ended, together with the executed
+    /// Turing machine, holding its metrics, or the `DecodeError`
+    /// if the encoding is not valid.
+    pub fn run_single(
+        &self,
+        encoded: &str,
+        max_steps: Option<u64>,
+    ) -> Result<(RunOutcome, TuringMachine), DecodeError> {
+        let mut turing_machine =
+            TuringMachine::from_encoded(encoded, self.number_of_states, self.number_of_symbols)?;
+        let max_steps =
+            max_steps.unwrap_or(TuringMachine::get_default_max_steps(self.number_of_states));
+
+        let run_outcome = turing_machine.run_to_halt(max_steps);
+
+        return Ok((run_outcome, turing_machine));
+    }
+
     /// Checks if the generation already took place, aka
     /// there are turing machines with the desired number of states
     /// in the database. If there aren'y any, it:
@@ -80,6 +314,8 @@ impl Mediator {
     /// will be generating unfiltered transition functions and
     /// will wait to receive the filtered from the `Filter`.
     pub async fn generate_and_filter(&mut self) {
+        let start_time = Instant::now();
+
         // mpsc channel used for sending unfiltered transition functions
         // from the generator to the filter
         let (tx_unfiltered_functions, rx_unfiltered_functions): (
@@ -87,8 +323,11 @@ impl Mediator {
             Receiver<Vec<TransitionFunction>>,
         ) = channel();
 
-        // create a copy of number of states
+        // create a copy of number of states, symbols and the batch size
         let number_of_states = self.number_of_states;
+        let number_of_symbols = self.number_of_symbols;
+        let batch_size = self.batch_size;
+        let shutdown = self.shutdown.clone();
 
         // mpsc channel used for sending filtered transition function
         // from the filter to the generator
@@ -103,29 +342,57 @@ impl Mediator {
                 tx_filtered_functions,
                 rx_unfiltered_functions,
                 number_of_states,
+                number_of_symbols,
             );
+            // the generation in Tree Normal Form has no halt placement duplicates
+            filter
+                .filter_compile
+                .set_halt_placement(GENERATION_ALGORITHM != GenerationAlgorithm::Tnf);
 
             filter.receive_all_unfiltered();
+
+            return filter.filter_compile.statistics();
         });
 
         // creates a new thread for the generator
         let generator_handle = thread::spawn(move || {
             let mut generator = Generator::new(
                 number_of_states,
+                number_of_symbols,
+                batch_size,
+                GENERATION_ALGORITHM,
                 tx_unfiltered_functions,
                 rx_filtered_functions,
             );
+            generator.set_shutdown(shutdown);
 
-            generator.generate();
+            generator.generate(None);
 
             // returns the transition functions generated
-            // by the generator
-            return generator.transition_functions;
+            // by the generator, with the statistics of the generation
+            return (
+                generator.transition_functions,
+                generator.filter_generate_stats,
+            );
         });
 
         // waits for both threads to finish running
-        let _ = filter_handle.join();
-        let transition_functions_generated = generator_handle.join().unwrap();
+        let filter_compile_stats = filter_handle.join().unwrap();
+        let (transition_functions_generated, filter_generate_stats) =
+            generator_handle.join().unwrap();
+
+        self.report.record_generation(
+            filter_generate_stats,
+            filter_compile_stats,
+            start_time.elapsed(),
+        );
+
+        // an interrupted generation is incomplete, so its transition functions
+        // are not run, otherwise the next run would skip the generation
+        if self.shutdown.load(Ordering::Relaxed) == true {
+            info!("Generation stopped, the transition functions generated were discarded.");
+            return;
+        }
 
         self.make_turing_machines(transition_functions_generated);
     }
@@ -148,7 +415,17 @@ impl Mediator {
     ///
     /// Creates a new thread that will wait for executed `TuringMachine`s;
     /// after receiving them, it will update their entry in the database.
-    pub async fn run_and_update(self) {
+    ///
+    /// The turing machines that did not halt are selected from the
+    /// database first, if the mediator does not hold any.
+    pub async fn run_and_update(mut self) {
+        // the turing machines were only counted when they were loaded
+        if self.turing_machines.is_empty() == true {
+            self.select_turing_machines_to_run().await;
+        }
+
+        let start_time = Instant::now();
+
         // mpsc channel used for sending terminated turing machines
         // from the turing machine runner to the database
         let (tx_turing_machine, rx_turing_machine): (
@@ -159,22 +436,25 @@ impl Mediator {
         let database_handler;
 
         // creates a new thread for the database insertions
-        database_handler = tokio::spawn(async {
+        let database = self.database.take();
+        database_handler = tokio::spawn(async move {
             let mut database_manager_runner = DatabaseManagerRunner::new(rx_turing_machine);
+            if let Some(database) = database {
+                database_manager_runner.set_database(database);
+            }
             database_manager_runner
                 .receive_and_update_turing_machines()
                 .await;
         });
 
         // creates a new thread to run turing machines
-        let tm_runner_handler = tokio::spawn(async {
-            let mut tm_runner = TuringMachineRunner::new(tx_turing_machine);
-            tm_runner.run(self.turing_machines).await;
-        });
+        let tm_runner_handler = self.spawn_tm_runner(tx_turing_machine);
 
         // wait for both threads to finish
         let _ = database_handler.await;
-        let _ = tm_runner_handler.await;
+        let tm_runner = tm_runner_handler.await;
+
+        self.finish_report(tm_runner.ok(), start_time);
     }
 
     /// Creates a new thread that will build `TuringMachine`s based
@@ -183,7 +463,16 @@ impl Mediator {
     ///
     /// Creates a new thread that will wait for executed `TuringMachine`s;
     /// after receiving them, it will bulk insert them in the database.
-    pub async fn run_and_insert(self) {
+    ///
+    /// In a dry run, the executed `TuringMachine`s are discarded instead.
+    pub async fn run_and_insert(mut self) {
+        if self.dry_run == true {
+            self.run_dry().await;
+            return;
+        }
+
+        let start_time = Instant::now();
+
         // mpsc channel used for sending terminated turing machines
         // from the turing machine runner to the database
         let (tx_turing_machine, rx_turing_machine): (
@@ -194,21 +483,397 @@ impl Mediator {
         let database_handler;
 
         // creates a new thread for the database insertions
-        database_handler = tokio::spawn(async {
+        let halting_only = self.halting_only;
+        let database_batch_size = self.database_batch_size;
+        let database = self.database.take();
+        database_handler = tokio::spawn(async move {
             let mut database_manager_runner = DatabaseManagerRunner::new(rx_turing_machine);
+            database_manager_runner.set_halting_only(halting_only);
+            database_manager_runner.set_batch_size(database_batch_size);
+            if let Some(database) = database {
+                database_manager_runner.set_database(database);
+            }
             database_manager_runner
                 .receive_and_insert_turing_machines()
                 .await;
         });
 
         // creates a new thread to run turing machines
-        let tm_runner_handler = tokio::spawn(async {
-            let mut tm_runner = TuringMachineRunner::new(tx_turing_machine);
-            tm_runner.run(self.turing_machines).await;
-        });
+        let tm_runner_handler = self.spawn_tm_runner(tx_turing_machine);
 
         // wait for both threads to finish
         let _ = database_handler.await;
-        let _ = tm_runner_handler.await;
+        let tm_runner = tm_runner_handler.await;
+
+        self.finish_report(tm_runner.ok(), start_time);
+    }
+
+    /// Executes all the `TuringMachine`s without accessing the database,
+    /// discarding them after they are executed.
+    ///
+    /// Returns the halted `TuringMachine` with the highest score,
+    /// if any of them halted.
+    pub async fn run_dry(mut self) -> Option<TuringMachine> {
+        let start_time = Instant::now();
+
+        // mpsc channel used for sending terminated turing machines
+        // from the turing machine runner to the sink
+        let (tx_turing_machine, mut rx_turing_machine): (
+            tokio::sync::mpsc::Sender<TuringMachine>,
+            tokio::sync::mpsc::Receiver<TuringMachine>,
+        ) = tokio::sync::mpsc::channel(1000);
+
+        // creates a new thread that only keeps the champion
+        let sink_handler = tokio::spawn(async move {
+            let mut champion: Option<TuringMachine> = None;
+
+            while let Some(turing_machine) = rx_turing_machine.recv().await {
+                if turing_machine.halted == false {
+                    continue;
+                }
+
+                let is_champion = match &champion {
+                    Some(champion) => turing_machine.better_than(champion),
+                    None => true,
+                };
+
+                if is_champion == true {
+                    champion = Some(turing_machine);
+                }
+            }
+
+            return champion;
+        });
+
+        // creates a new thread to run turing machines
+        let tm_runner_handler = self.spawn_tm_runner(tx_turing_machine);
+
+        // wait for both threads to finish
+        let tm_runner = tm_runner_handler.await;
+        let champion = sink_handler.await.unwrap();
+
+        self.finish_report(tm_runner.ok(), start_time);
+
+        info!("Dry run finished, the Turing machines were not inserted in the database.");
+
+        return champion;
+    }
+
+    /// Spawns the task that executes the turing machines of the mediator
+    /// and sends them through `tx_turing_machine`, returning the
+    /// `TuringMachineRunner` once every turing machine was sent.
+    fn spawn_tm_runner(
+        &mut self,
+        tx_turing_machine: tokio::sync::mpsc::Sender<TuringMachine>,
+    ) -> tokio::task::JoinHandle<TuringMachineRunner> {
+        let turing_machines = std::mem::take(&mut self.turing_machines);
+        let num_threads = self.num_threads;
+        let filter_runtime_config = self.filter_runtime_config.clone();
+        let shutdown = self.shutdown.clone();
+
+        return tokio::spawn(async move {
+            let mut tm_runner = TuringMachineRunner::new(tx_turing_machine);
+            if let Some(num_threads) = num_threads {
+                tm_runner.set_num_threads(num_threads);
+            }
+            tm_runner.set_filter_runtime_config(filter_runtime_config);
+            tm_runner.set_shutdown(shutdown);
+            tm_runner.run(turing_machines, None, None).await;
+
+            return tm_runner;
+        });
+    }
+
+    /// Records the run of the `tm_runner`, started at `start_time`, in the
+    /// report, and writes it if a path was set ( see `set_report_path` ).
+    fn finish_report(&mut self, tm_runner: Option<TuringMachineRunner>, start_time: Instant) {
+        if let Some(tm_runner) = tm_runner {
+            self.report.record_run(&tm_runner, start_time.elapsed());
+        }
+
+        let Some(report_path) = &self.report_path else {
+            return;
+        };
+
+        match self.report.write(report_path) {
+            Ok(()) => info!("Wrote the run report to {}.", report_path.display()),
+            Err(error) => error!("While writing the run report: {}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::sqlite_store::SqliteStore;
+    use crate::generator::generator_transition_function::GeneratorTransitionFunction;
+
+    #[tokio::test]
+    async fn dry_run_two_states() {
+        let mut bb_mediator = Mediator::new(2, 2, 100);
+        bb_mediator.set_dry_run(true);
+
+        bb_mediator.load_turing_machines().await;
+        assert_eq!(bb_mediator.loaded, false);
+
+        bb_mediator.generate_and_filter().await;
+        let champion = bb_mediator.run_dry().await.unwrap();
+
+        // the BB(2) champion writes 4 ones in 6 steps
+        assert_eq!(champion.score, 4);
+        assert_eq!(champion.steps, 6);
+    }
+
+    #[tokio::test]
+    async fn dry_run_one_state() {
+        let mut bb_mediator = Mediator::new(1, 2, 100);
+        bb_mediator.set_dry_run(true);
+        bb_mediator.generate_and_filter().await;
+
+        // a 1-state Turing machine that does not halt right away
+        // never halts, so only the ones halting right away are kept
+        assert!(bb_mediator.turing_machines.is_empty() == false);
+        for turing_machine in bb_mediator.turing_machines.iter() {
+            assert!(turing_machine
+                .transition_function
+                .to_standard_notation()
+                .unwrap()
+                .starts_with("1RH"));
+        }
+
+        let champion = bb_mediator.run_dry().await.unwrap();
+
+        // the BB(1) champion writes 1 one in 1 step
+        assert_eq!(champion.score, 1);
+        assert_eq!(champion.steps, 1);
+    }
+
+    #[tokio::test]
+    async fn load_turing_machines_from_store() {
+        let mut store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        let turing_machines: Vec<TuringMachine> = ["1RB1LB_1LA1RH", "0RB1RH_0LA1RH"]
+            .iter()
+            .map(|encoded| {
+                TuringMachine::new(TransitionFunction::from_standard_notation(encoded).unwrap())
+            })
+            .collect();
+        store.batch_insert(&turing_machines).await.unwrap();
+
+        let mut bb_mediator = Mediator::with_store(2, 2, 100, Box::new(store));
+        bb_mediator.load_turing_machines().await;
+
+        // the turing machines are only counted
+        assert_eq!(bb_mediator.loaded, true);
+        assert_eq!(bb_mediator.turing_machines.len(), 0);
+
+        bb_mediator.select_turing_machines_to_run().await;
+        assert_eq!(bb_mediator.turing_machines.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn load_turing_machines_from_empty_store() {
+        let store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+
+        let mut bb_mediator = Mediator::with_store(2, 2, 100, Box::new(store));
+        bb_mediator.load_turing_machines().await;
+
+        assert_eq!(bb_mediator.loaded, false);
+        assert_eq!(bb_mediator.turing_machines.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn rerun_holdouts() {
+        // halts after 47 steps, so it is a holdout with a limit of 21 steps
+        let mut transition_function = TransitionFunction::new(4, 2);
+        transition_function
+            .decode(
+                "0,0,1,1,0|0,1,3,0,1|1,0,3,0,0|1,1,255,1,1|2,0,0,1,0|2,1,2,1,1|3,0,2,1,1|3,1,2,0,1"
+                    .to_string(),
+            )
+            .unwrap();
+
+        let mut turing_machine = TuringMachine::new(transition_function);
+        turing_machine.execute(21);
+        assert_eq!(turing_machine.halted, false);
+
+        let mut store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        store.insert(turing_machine).await;
+
+        let mut bb_mediator = Mediator::with_store(4, 2, 100, Box::new(store));
+
+        assert_eq!(bb_mediator.rerun_holdouts(50).await, 1);
+
+        // the row flipped to halted, so it is no longer a holdout
+        let database_manager = bb_mediator.database.as_mut().unwrap();
+        let holdouts = database_manager
+            .select_turing_machines_to_run(4, 2)
+            .await
+            .unwrap();
+        let champions = database_manager.select_champions(4, 2, 10).await.unwrap();
+
+        assert_eq!(holdouts.len(), 0);
+        assert_eq!(champions.len(), 1);
+        assert_eq!(champions[0].steps, 47);
+    }
+
+    #[tokio::test]
+    async fn run_and_update_stored_holdout() {
+        // BB(4) champion, stored after it was executed for 21 steps
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA0LC_1RH1LD_1RD0RA").unwrap();
+        let mut turing_machine = TuringMachine::new(transition_function);
+        turing_machine.execute(21);
+        assert_eq!(turing_machine.halted, false);
+
+        let mut store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        store.insert(turing_machine).await;
+
+        let bb_mediator = Mediator::with_store(4, 2, 100, Box::new(store.clone()));
+        bb_mediator.run_and_update().await;
+
+        // it is executed again from the start, so it halts after 107 steps
+        let champions = store.select_champions(4, 2, 10).await.unwrap();
+
+        assert_eq!(champions.len(), 1);
+        assert_eq!(champions[0].steps, 107);
+        assert_eq!(champions[0].score, 13);
+    }
+
+    #[tokio::test]
+    async fn shutdown_flushes_executed_batch() {
+        let store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        let mut bb_mediator = Mediator::with_store(3, 2, 100, Box::new(store.clone()));
+        bb_mediator.set_database_batch_size(300);
+        bb_mediator.turing_machines = GeneratorTransitionFunction::iter(3, 2)
+            .take(2500)
+            .map(TuringMachine::new)
+            .collect();
+
+        // requested before running, so only the first batch is executed
+        bb_mediator.shutdown();
+        bb_mediator.run_and_insert().await;
+
+        // the last 100 Turing machines did not fill a database
+        // batch of 300, but they were inserted as well
+        assert_eq!(store.count(3, 2).await, Some(1000));
+    }
+
+    #[tokio::test]
+    async fn shutdown_during_generation() {
+        // the generation with 4 states takes hours, unless it is stopped
+        let mut bb_mediator = Mediator::new(4, 2, 100);
+        bb_mediator.set_dry_run(true);
+
+        let shutdown = bb_mediator.shutdown.clone();
+        let shutdown_handle = thread::spawn(move || {
+            thread::sleep(std::time::Duration::from_millis(200));
+            shutdown.store(true, Ordering::Relaxed);
+        });
+
+        bb_mediator.generate_and_filter().await;
+        shutdown_handle.join().unwrap();
+
+        // the interrupted generation is not run
+        assert!(bb_mediator.turing_machines.is_empty() == true);
+        assert!(bb_mediator.run_dry().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn generate_custom_batch_size() {
+        let mut bb_mediator = Mediator::new(2, 2, 100);
+        bb_mediator.set_dry_run(true);
+        bb_mediator.generate_and_filter().await;
+
+        // the generator sends batches of 7, so the transition functions
+        // left at the end make up an incomplete batch, that is still sent
+        let mut bb_mediator_custom = Mediator::new(2, 2, 7);
+        bb_mediator_custom.set_dry_run(true);
+        bb_mediator_custom.generate_and_filter().await;
+
+        let get_encodings = |mediator: &Mediator| {
+            let mut encodings: Vec<String> = mediator
+                .turing_machines
+                .iter()
+                .map(|turing_machine| turing_machine.transition_function.encode())
+                .collect();
+            encodings.sort();
+            encodings
+        };
+
+        let encodings = get_encodings(&bb_mediator);
+
+        assert!(encodings.is_empty() == false);
+        assert_eq!(get_encodings(&bb_mediator_custom), encodings);
+    }
+
+    #[tokio::test]
+    async fn run_and_insert_writes_report() {
+        let report_path = std::env::temp_dir().join(format!(
+            "busy_beaver_reduction_report_{}.json",
+            std::process::id()
+        ));
+        let store = SqliteStore::connect("sqlite::memory:").await.unwrap();
+        let mut bb_mediator = Mediator::with_store(2, 2, 100, Box::new(store.clone()));
+        bb_mediator.set_report_path(&report_path);
+
+        bb_mediator.generate_and_filter().await;
+        let generated = bb_mediator.turing_machines.len() as i64;
+        bb_mediator.run_and_insert().await;
+
+        let report: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&report_path).unwrap()).unwrap();
+        let _ = std::fs::remove_file(&report_path);
+
+        // the BB(2) champion writes 4 ones in 6 steps
+        assert_eq!(report["champion"]["score"], 4);
+        assert_eq!(report["champion"]["steps"], 6);
+        assert_eq!(report["executed"], generated);
+        assert_eq!(
+            report["generated"].as_i64().unwrap() - report["filtered_compile"].as_i64().unwrap(),
+            generated
+        );
+        assert!(report["filtered_generate"].as_u64().unwrap() > 0);
+        assert_eq!(store.count(2, 2).await, Some(generated));
+    }
+
+    #[test]
+    fn run_single() {
+        let bb_mediator = Mediator::new(3, 2, 100);
+
+        // the BB(3) champion, with the highest score, 1RB1RH_0RC1RB_1LC1LA
+        let (run_outcome, turing_machine) = bb_mediator
+            .run_single(
+                "0,0,1,1,1|0,1,255,1,1|1,0,2,0,1|1,1,1,1,1|2,0,2,1,0|2,1,0,1,0",
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(
+            run_outcome,
+            RunOutcome::Halted {
+                steps: 14,
+                score: 6
+            }
+        );
+        assert_eq!(turing_machine.halted, true);
+
+        // the 3-state Turing machine with the most steps, 1RB1RH_1LB0RC_1LC1LA,
+        // halts exactly at the default step cap, but not with a lower one
+        let encoded = "0,0,1,1,1|0,1,255,1,1|1,0,1,1,0|1,1,2,0,1|2,0,2,1,0|2,1,0,1,0";
+        let (run_outcome, _) = bb_mediator.run_single(encoded, None).unwrap();
+
+        assert_eq!(
+            run_outcome,
+            RunOutcome::Halted {
+                steps: 21,
+                score: 5
+            }
+        );
+        assert_eq!(
+            bb_mediator.run_single(encoded, Some(20)).unwrap().0,
+            RunOutcome::StepLimit
+        );
+
+        assert!(bb_mediator.run_single("not an encoding", None).is_err());
     }
 }
@@ -1 +1,2 @@
 pub mod mediator;
+pub mod run_report;
@@ -0,0 +1,106 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::filter::filter_compile::FilterCompileStats;
+use crate::filter::filter_generate::FilterGenerateStats;
+use crate::turing_machine::runner::TuringMachineRunner;
+use crate::turing_machine::turing_machine::TuringMachine;
+
+/// Halted Turing machine with the highest score of a run.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct RunReportChampion {
+    pub standard_notation: String,
+    pub score: u64,
+    pub steps: u64,
+}
+
+/// Machine-readable summary of a completed run, aggregating the statistics
+/// of the generation, the compile filter and the `TuringMachineRunner`,
+/// written as JSON at the end of the run ( see `Mediator::set_report_path` ).
+///
+/// The stages that were not part of the run, e.g. the generation when the
+/// Turing machines were selected from the database, are left at 0.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct RunReport {
+    pub number_of_states: u8,
+    pub number_of_symbols: u8,
+    /// transition functions generated, that passed the generation filters
+    pub generated: i64,
+    /// Turing machines filtered during the generation, out of all the possible ones
+    pub filtered_generate: u128,
+    /// transition functions filtered by the compile filter, out of the `generated` ones
+    pub filtered_compile: i64,
+    /// executed Turing machines identified as non-halting by the runtime filters
+    pub filtered_runtime: i64,
+    pub executed: i64,
+    pub halted: i64,
+    pub champion: Option<RunReportChampion>,
+    pub generation_seconds: f64,
+    pub run_seconds: f64,
+}
+
+impl RunReport {
+    pub fn new(number_of_states: u8, number_of_symbols: u8) -> Self {
+        return RunReport {
+            number_of_states: number_of_states,
+            number_of_symbols: number_of_symbols,
+            ..RunReport::default()
+        };
+    }
+
+    /// Records the statistics of the generation and of the compile filter,
+    /// which took `duration` together.
+    pub fn record_generation(
+        &mut self,
+        filter_generate_stats: Option<FilterGenerateStats>,
+        filter_compile_stats: FilterCompileStats,
+        duration: Duration,
+    ) {
+        if let Some(filter_generate_stats) = filter_generate_stats {
+            self.filtered_generate = filter_generate_stats.get_filtered();
+        }
+
+        self.generated = filter_compile_stats.received;
+        self.filtered_compile = filter_compile_stats.get_filtered();
+        self.generation_seconds = duration.as_secs_f64();
+    }
+
+    /// Records the statistics and the champion of the `TuringMachineRunner`,
+    /// after it executed the Turing machines in `duration`.
+    pub fn record_run(&mut self, tm_runner: &TuringMachineRunner, duration: Duration) {
+        self.filtered_runtime = tm_runner.statistics().get_filtered();
+        self.executed = tm_runner.halted + tm_runner.non_halting;
+        self.halted = tm_runner.halted;
+        self.champion = tm_runner.champion.as_ref().map(RunReport::get_champion);
+        self.run_seconds = duration.as_secs_f64();
+    }
+
+    /// Gets the champion of the report; its transition function is written with the
+    /// crate's own encoding if it has too many states for the standard notation.
+    fn get_champion(turing_machine: &TuringMachine) -> RunReportChampion {
+        let transition_function = &turing_machine.transition_function;
+
+        return RunReportChampion {
+            standard_notation: transition_function
+                .to_standard_notation()
+                .unwrap_or_else(|_| transition_function.encode()),
+            score: turing_machine.score,
+            steps: turing_machine.steps,
+        };
+    }
+
+    /// Writes the report as JSON to the file found at `path`,
+    /// overwriting it if it already exists.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writer.flush()?;
+
+        return Ok(());
+    }
+}
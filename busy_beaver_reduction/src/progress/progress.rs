@@ -0,0 +1,14 @@
+/// Event sent through the progress channel during a long run,
+/// so that its progress can be displayed, e.g. as a progress bar.
+///
+/// The events are sent once per batch, each one holding
+/// the number of items of the batch:
+/// - `Generated`: transition functions generated, before the compile filters
+/// - `Filtered`: transition functions that passed the compile filters
+/// - `Executed`: Turing machines executed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ProgressEvent {
+    Generated(usize),
+    Filtered(usize),
+    Executed(usize),
+}
@@ -1,4 +1,6 @@
-#[derive(Clone, Copy, PartialEq, Debug, Eq, Hash)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Debug, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Direction {
     LEFT,
     RIGHT,
@@ -15,6 +17,27 @@ impl Direction {
         }
     }
 
+    /// Gets the label (`char`) associated to each direction,
+    /// as used in the standard Busy Beaver notation:
+    /// - `LEFT` = L
+    /// - `RIGHT` = R
+    pub fn label(&self) -> char {
+        match *self {
+            Direction::LEFT => 'L',
+            Direction::RIGHT => 'R',
+        }
+    }
+
+    /// Gets the opposite direction:
+    /// - `LEFT` -> RIGHT
+    /// - `RIGHT` -> LEFT
+    pub fn opposite(&self) -> Self {
+        match *self {
+            Direction::LEFT => Direction::RIGHT,
+            Direction::RIGHT => Direction::LEFT,
+        }
+    }
+
     /// Transforms the value given (`u8`) to a Direction:
     /// - `0` = LEFT
     /// - `1` = RIGHT
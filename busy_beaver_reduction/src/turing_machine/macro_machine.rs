@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+
+use crate::delta::transition_function::TransitionFunction;
+use crate::turing_machine::direction::Direction;
+use crate::turing_machine::special_states::SpecialStates;
+use crate::turing_machine::turing_machine::HaltReason;
+
+/// Symbol written by a transition that is not defined,
+/// the same as in `TuringMachine::make_transition`.
+const UNDEFINED_TRANSITION_SYMBOL: u8 = 1;
+
+/// Side of a block through which the head left it, after running inside it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BlockExit {
+    Moved(Direction),
+    Halted(HaltReason),
+    /// the head was still inside the block when the steps ran out
+    Unfinished,
+}
+
+/// Result of running the Turing machine inside a single block,
+/// from the moment the head entered it until it left it.
+#[derive(Clone, Debug, PartialEq)]
+struct MacroTransition {
+    block: Vec<u8>,
+    state: u8,
+    exit: BlockExit,
+    steps: u64,
+}
+
+/// Macro machine, that simulates a Turing machine on a tape grouped
+/// in blocks of `block_size` cells, making a single step for every block
+/// the head passes through ( see `TuringMachine::execute_macro` ).
+///
+/// Each side of the tape is kept as a stack of runs of identical blocks,
+/// with the run closest to the head on top, so a sweep across a uniform
+/// region, in the same state, advances over the whole run at once.
+pub struct MacroMachine<'a> {
+    transition_function: &'a TransitionFunction,
+    block_size: usize,
+    blank_symbol: u8,
+    // (u8, Direction, Vec<u8>) -> (state, side the head entered from, block),
+    // precomputed the first time the head enters a block in that way
+    transitions: HashMap<(u8, Direction, Vec<u8>), MacroTransition>,
+    // (Vec<u8>, u64) -> (block, number of times it is repeated)
+    left: Vec<(Vec<u8>, u64)>,
+    right: Vec<(Vec<u8>, u64)>,
+    // the head is on the edge of the block on top of the stack it is facing
+    facing: Direction,
+    pub state: u8,
+    pub steps: u64,
+    pub halted: bool,
+    pub halt_reason: Option<HaltReason>,
+}
+
+impl<'a> MacroMachine<'a> {
+    /// Creates the macro machine in the initial configuration: the blank
+    /// tape, with the head on the first cell of a block, in the start state.
+    pub fn new(
+        transition_function: &'a TransitionFunction,
+        block_size: usize,
+        blank_symbol: u8,
+    ) -> Self {
+        return MacroMachine {
+            transition_function: transition_function,
+            block_size: block_size.max(1),
+            blank_symbol: blank_symbol,
+            transitions: HashMap::new(),
+            left: Vec::new(),
+            right: Vec::new(),
+            facing: Direction::RIGHT,
+            state: SpecialStates::StateStart.value(),
+            steps: 0,
+            halted: false,
+            halt_reason: None,
+        };
+    }
+
+    /// Runs the macro machine until it is halted or until the next
+    /// step would make it go over `max_steps` steps, in which case
+    /// it is stopped with exactly `max_steps` steps, like the naive
+    /// simulation that was not halted.
+    pub fn execute(&mut self, max_steps: u64) {
+        while self.halted == false && self.steps < max_steps {
+            if self.make_step(max_steps - self.steps) == false {
+                self.steps = max_steps;
+            }
+        }
+    }
+
+    /// Makes a single macro step, moving the head through the block it
+    /// is facing, or through the whole run of identical blocks if it
+    /// crosses them in the same state and direction.
+    ///
+    /// Returns `false` if there were not enough `remaining_steps` to make it.
+    fn make_step(&mut self, remaining_steps: u64) -> bool {
+        let facing = self.facing;
+        let (block, count) = match Self::stack(&mut self.left, &mut self.right, facing).last() {
+            Some(run) => run.clone(),
+            // the blank region at the end of the tape never ends
+            None => (vec![self.blank_symbol; self.block_size], u64::MAX),
+        };
+
+        let transition = self.get_transition(self.state, facing, block, remaining_steps);
+
+        if transition.exit == BlockExit::Unfinished || transition.steps > remaining_steps {
+            return false;
+        }
+
+        // the head crosses the whole run, so it can be swept at once,
+        // as long as it does not go over the remaining steps
+        let mut crossed: u64 = 1;
+
+        if transition.exit == BlockExit::Moved(facing) && transition.state == self.state {
+            crossed = count.min(remaining_steps / transition.steps);
+        }
+
+        Self::pop(
+            Self::stack(&mut self.left, &mut self.right, facing),
+            crossed,
+        );
+        self.steps += crossed * transition.steps;
+        self.state = transition.state;
+
+        match transition.exit {
+            BlockExit::Moved(direction) if direction == facing => {
+                let opposite = Self::opposite(facing);
+                let stack = Self::stack(&mut self.left, &mut self.right, opposite);
+                Self::push(stack, transition.block, crossed);
+            }
+            BlockExit::Moved(direction) => {
+                // the head turned around, and it is now
+                // facing the other side of the tape
+                let stack = Self::stack(&mut self.left, &mut self.right, facing);
+                Self::push(stack, transition.block, 1);
+                self.facing = direction;
+            }
+            BlockExit::Halted(halt_reason) => {
+                let stack = Self::stack(&mut self.left, &mut self.right, facing);
+                Self::push(stack, transition.block, 1);
+                self.halted = true;
+                self.halt_reason = Some(halt_reason);
+            }
+            BlockExit::Unfinished => {}
+        }
+
+        return true;
+    }
+
+    /// Gets the macro transition of entering `block` in `state`, while `facing`
+    /// it, computing it the first time by running the Turing machine inside it.
+    ///
+    /// The transitions that did not finish in `remaining_steps` are not stored,
+    /// since they depend on the steps that were left.
+    fn get_transition(
+        &mut self,
+        state: u8,
+        facing: Direction,
+        block: Vec<u8>,
+        remaining_steps: u64,
+    ) -> MacroTransition {
+        let key = (state, facing, block);
+
+        if let Some(transition) = self.transitions.get(&key) {
+            return transition.clone();
+        }
+
+        let transition = self.compute_transition(&key, remaining_steps);
+
+        if transition.exit != BlockExit::Unfinished {
+            self.transitions.insert(key, transition.clone());
+        }
+
+        return transition;
+    }
+
+    /// Runs the Turing machine inside the block, starting from its edge
+    /// that is closest to the side it came from, for at most `remaining_steps`.
+    fn compute_transition(
+        &self,
+        key: &(u8, Direction, Vec<u8>),
+        remaining_steps: u64,
+    ) -> MacroTransition {
+        let (mut state, facing, block) = key.clone();
+        let mut block = block;
+        let mut head: i64 = match facing {
+            Direction::RIGHT => 0,
+            Direction::LEFT => self.block_size as i64 - 1,
+        };
+        let mut steps: u64 = 0;
+
+        while steps < remaining_steps {
+            let symbol = block[head as usize];
+            steps += 1;
+
+            match self.transition_function.transitions().get(&(state, symbol)) {
+                Some(&(next_state, written, direction)) => {
+                    block[head as usize] = written;
+                    state = next_state;
+
+                    // the halting transition does not move the head
+                    if next_state == SpecialStates::StateHalt.value() {
+                        return MacroTransition {
+                            block: block,
+                            state: state,
+                            exit: BlockExit::Halted(HaltReason::HaltState),
+                            steps: steps,
+                        };
+                    }
+
+                    match direction {
+                        Direction::LEFT => head -= 1,
+                        Direction::RIGHT => head += 1,
+                    }
+
+                    if head < 0 || head >= self.block_size as i64 {
+                        return MacroTransition {
+                            block: block,
+                            state: state,
+                            exit: BlockExit::Moved(direction),
+                            steps: steps,
+                        };
+                    }
+                }
+                None => {
+                    block[head as usize] = UNDEFINED_TRANSITION_SYMBOL;
+
+                    return MacroTransition {
+                        block: block,
+                        state: state,
+                        exit: BlockExit::Halted(HaltReason::UndefinedTransition),
+                        steps: steps,
+                    };
+                }
+            }
+        }
+
+        return MacroTransition {
+            block: block,
+            state: state,
+            exit: BlockExit::Unfinished,
+            steps: steps,
+        };
+    }
+
+    /// Counts the cells of the tape that do not hold the blank symbol.
+    pub fn get_score(&self) -> u64 {
+        return self
+            .left
+            .iter()
+            .chain(self.right.iter())
+            .map(|(block, count)| {
+                let non_blank = block
+                    .iter()
+                    .filter(|&&symbol| symbol != self.blank_symbol)
+                    .count() as u64;
+
+                non_blank.saturating_mul(*count)
+            })
+            .sum();
+    }
+
+    /// Gets the stack of runs on the given side of the head.
+    fn stack<'b>(
+        left: &'b mut Vec<(Vec<u8>, u64)>,
+        right: &'b mut Vec<(Vec<u8>, u64)>,
+        side: Direction,
+    ) -> &'b mut Vec<(Vec<u8>, u64)> {
+        match side {
+            Direction::LEFT => return left,
+            Direction::RIGHT => return right,
+        }
+    }
+
+    fn opposite(direction: Direction) -> Direction {
+        match direction {
+            Direction::LEFT => return Direction::RIGHT,
+            Direction::RIGHT => return Direction::LEFT,
+        }
+    }
+
+    /// Adds `count` copies of the block on top of the stack,
+    /// merging them with the run on top if it has the same block.
+    fn push(stack: &mut Vec<(Vec<u8>, u64)>, block: Vec<u8>, count: u64) {
+        if let Some(run) = stack.last_mut() {
+            if run.0 == block {
+                run.1 += count;
+                return;
+            }
+        }
+
+        stack.push((block, count));
+    }
+
+    /// Removes `count` copies of the block on top of the stack; an empty
+    /// stack holds the blank region, from which nothing is removed.
+    fn pop(stack: &mut Vec<(Vec<u8>, u64)>, count: u64) {
+        if let Some(run) = stack.last_mut() {
+            run.1 -= count;
+
+            if run.1 == 0 {
+                stack.pop();
+            }
+        }
+    }
+}
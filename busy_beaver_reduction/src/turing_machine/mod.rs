@@ -1,4 +1,7 @@
 pub mod direction;
+pub mod macro_machine;
 pub mod runner;
 pub mod special_states;
+pub mod tape;
 pub mod turing_machine;
+pub mod turing_machine_csv;
@@ -1,14 +1,112 @@
 use rayon;
 use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::mpsc::RecvTimeoutError;
 use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 use tokio::sync::mpsc::Sender;
 use tokio::sync::{Semaphore, SemaphorePermit};
 
-use crate::filter::filter_runtime::FilterRuntimeType;
+use crate::filter::filter_runtime::{FilterRuntimeConfig, FilterRuntimeType};
+use crate::progress::progress::ProgressEvent;
 use crate::turing_machine::turing_machine::TuringMachine;
 use log::{error, info};
 
+/// Number of threads used when the available parallelism cannot be determined.
 const MAXIMUM_THREADS: usize = 8;
+/// Number of Turing machines executed between two `ProgressEvent::Executed` events.
+const PROGRESS_BATCH_SIZE: usize = 1000;
+/// Interval at which the live counters are logged while running.
+const LIVE_COUNTERS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Counters of the Turing machines executed so far, incremented
+/// by the threads of the pool as soon as each Turing machine finishes,
+/// so they can be read while the Turing machines are still running.
+#[derive(Default)]
+pub struct LiveCounters {
+    pub halted: AtomicI64,
+    pub non_halted: AtomicI64,
+    pub short_escapers: AtomicI64,
+    pub long_escapers: AtomicI64,
+    pub cyclers: AtomicI64,
+    pub translated_cyclers: AtomicI64,
+    pub recurrences: AtomicI64,
+    pub bouncers: AtomicI64,
+    pub ctls: AtomicI64,
+}
+
+impl LiveCounters {
+    /// Increments the counters matching the result of the executed Turing machine.
+    pub fn record(&self, turing_machine: &TuringMachine) {
+        if turing_machine.halted == true {
+            self.halted.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.non_halted.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let counter = match turing_machine.filtered {
+            FilterRuntimeType::ShortEscapee => &self.short_escapers,
+            FilterRuntimeType::LongEscapee => &self.long_escapers,
+            FilterRuntimeType::Cycler => &self.cyclers,
+            FilterRuntimeType::TranslatedCycler => &self.translated_cyclers,
+            FilterRuntimeType::Recurrence => &self.recurrences,
+            FilterRuntimeType::Bouncer => &self.bouncers,
+            FilterRuntimeType::Ctl => &self.ctls,
+            FilterRuntimeType::None => return,
+        };
+
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Logs the current value of the counters.
+    pub fn display(&self) {
+        info!(
+            "Executed so far: {} halted, {} non-halted ({} short escapers, {} long escapers, {} cyclers, {} translated cyclers, {} recurrences, {} bouncers, {} CTLs).",
+            self.halted.load(Ordering::Relaxed),
+            self.non_halted.load(Ordering::Relaxed),
+            self.short_escapers.load(Ordering::Relaxed),
+            self.long_escapers.load(Ordering::Relaxed),
+            self.cyclers.load(Ordering::Relaxed),
+            self.translated_cyclers.load(Ordering::Relaxed),
+            self.recurrences.load(Ordering::Relaxed),
+            self.bouncers.load(Ordering::Relaxed),
+            self.ctls.load(Ordering::Relaxed),
+        );
+    }
+}
+
+/// Number of Turing machines identified as non-halting by each
+/// runtime filter, out of the `non_halting` ones.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RuntimeFilterStats {
+    pub short_escapers: i64,
+    pub long_escapers: i64,
+    pub cyclers: i64,
+    pub translated_cyclers: i64,
+    pub recurrences: i64,
+    pub bouncers: i64,
+    pub ctls: i64,
+    pub non_halting: i64,
+}
+
+impl RuntimeFilterStats {
+    /// Gets the percentage of the non-halting Turing machines that `filtered` represents.
+    pub fn get_percentage(&self, filtered: i64) -> f64 {
+        return filtered as f64 * 100.0 / self.non_halting as f64;
+    }
+
+    /// Gets the number of Turing machines identified by all the runtime filters.
+    pub fn get_filtered(&self) -> i64 {
+        return self.short_escapers
+            + self.long_escapers
+            + self.cyclers
+            + self.translated_cyclers
+            + self.recurrences
+            + self.bouncers
+            + self.ctls;
+    }
+}
 
 pub struct TuringMachineRunner {
     pub tx_turing_machines: Option<Sender<TuringMachine>>,
@@ -16,6 +114,19 @@ pub struct TuringMachineRunner {
     pub long_escapers: i64,
     pub cyclers: i64,
     pub translated_cyclers: i64,
+    pub recurrences: i64,
+    pub bouncers: i64,
+    pub ctls: i64,
+    pub non_halting: i64,
+    pub halted: i64,
+    pub invalid: i64,
+    /// halted Turing machine with the highest score, out of the ones executed
+    pub champion: Option<TuringMachine>,
+    pub live_counters: Arc<LiveCounters>,
+    pub num_threads: usize,
+    /// runtime filters applied while executing the Turing machines
+    pub filter_runtime_config: FilterRuntimeConfig,
+    shutdown: Arc<AtomicBool>,
 }
 
 impl TuringMachineRunner {
@@ -26,12 +137,47 @@ impl TuringMachineRunner {
             long_escapers: 0,
             cyclers: 0,
             translated_cyclers: 0,
+            recurrences: 0,
+            bouncers: 0,
+            ctls: 0,
+            non_halting: 0,
+            halted: 0,
+            invalid: 0,
+            champion: None,
+            live_counters: Arc::new(LiveCounters::default()),
+            num_threads: thread::available_parallelism()
+                .map(|num_threads| num_threads.get())
+                .unwrap_or(MAXIMUM_THREADS),
+            filter_runtime_config: FilterRuntimeConfig::default(),
+            shutdown: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Sets the number of threads of the pool the Turing machines are
+    /// executed on, instead of the available parallelism of the machine.
+    pub fn set_num_threads(&mut self, num_threads: usize) {
+        self.num_threads = num_threads.max(1);
+    }
+
+    /// Sets the runtime filters applied while executing the Turing
+    /// machines, instead of all of them in the default order.
+    pub fn set_filter_runtime_config(&mut self, filter_runtime_config: FilterRuntimeConfig) {
+        self.filter_runtime_config = filter_runtime_config;
+    }
+
+    /// Sets the flag that requests the runner to stop, e.g. on Ctrl-C:
+    /// once it is set, the batch of Turing machines being executed is
+    /// finished and sent, while the ones left are not executed.
+    pub fn set_shutdown(&mut self, shutdown: Arc<AtomicBool>) {
+        self.shutdown = shutdown;
+    }
+
     /// Given an array of `TransitionFunction`s, use the pool of threads
     /// to create a new Turing Machine for each one
-    /// and start executing them.
+    /// and start executing them for at most `max_steps` steps.
+    ///
+    /// If `max_steps` is not provided, each Turing machine will run
+    /// for the default number of steps of its number of states.
     ///
     /// After the execution, each thread from the pool will send
     /// the `TuringMachine` instance through the mpsc channel configured
@@ -39,25 +185,99 @@ impl TuringMachineRunner {
     ///
     /// Consumer on the other side of the mpsc channel will insert the turing
     /// machines in the database.
-    pub async fn run(&mut self, mut turing_machines: Vec<TuringMachine>) {
+    ///
+    /// If `tx_progress` is given, a `ProgressEvent::Executed` event is sent
+    /// through it after every `PROGRESS_BATCH_SIZE` Turing machines executed.
+    ///
+    /// While running, the `live_counters` are updated after each Turing machine
+    /// and logged every `LIVE_COUNTERS_INTERVAL` by a separate thread.
+    ///
+    /// If the shutdown was requested ( see `set_shutdown` ), only the Turing
+    /// machines executed until the end of the current batch are sent.
+    ///
+    /// The Turing machines whose transition function is not deterministic
+    /// and complete, e.g. decoded from a corrupted row, would halt on the
+    /// missing transition, so they are counted as `invalid` instead of
+    /// being executed, and they are not sent.
+    pub async fn run(
+        &mut self,
+        turing_machines: Vec<TuringMachine>,
+        max_steps: Option<u64>,
+        tx_progress: Option<std::sync::mpsc::Sender<ProgressEvent>>,
+    ) {
+        let (mut turing_machines, invalid): (Vec<TuringMachine>, Vec<TuringMachine>) =
+            turing_machines.into_iter().partition(|turing_machine| {
+                turing_machine
+                    .transition_function
+                    .is_deterministic_complete()
+            });
+
+        if invalid.is_empty() == false {
+            self.invalid += invalid.len() as i64;
+            error!(
+                "Skipped running {} Turing machines whose transition function is not deterministic and complete.",
+                invalid.len()
+            );
+        }
+
         info!(
             "Started running turing machine. {} total machines to run...",
             turing_machines.len()
         );
 
         let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(MAXIMUM_THREADS)
+            .num_threads(self.num_threads)
             .build()
             .unwrap();
+        let filter_runtime_config = &self.filter_runtime_config;
+
+        // log the live counters periodically, until the
+        // stop signal is sent or the sender is dropped
+        let live_counters = self.live_counters.clone();
+        let (tx_stop, rx_stop) = std::sync::mpsc::channel::<()>();
+        let live_counters_logger = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = rx_stop.recv_timeout(LIVE_COUNTERS_INTERVAL)
+            {
+                live_counters.display();
+            }
+        });
 
-        pool.install(|| {
-            turing_machines.par_iter_mut().for_each(|turing_machine| {
-                turing_machine.execute();
+        let total = turing_machines.len();
+        let mut executed: usize = 0;
+
+        for turing_machines_batch in turing_machines.chunks_mut(PROGRESS_BATCH_SIZE) {
+            pool.install(|| {
+                turing_machines_batch
+                    .par_iter_mut()
+                    .for_each(|turing_machine| {
+                        let max_steps = max_steps.unwrap_or(TuringMachine::get_default_max_steps(
+                            turing_machine.transition_function.number_of_states,
+                        ));
+                        turing_machine.execute_with_config(max_steps, filter_runtime_config);
+                        self.live_counters.record(turing_machine);
+                    });
             });
-        });
 
-        // counter for the number of Turing machines that did not halt
-        let mut non_halting_turing_machines_size: i64 = 0;
+            if let Some(tx_progress) = &tx_progress {
+                let _ = tx_progress.send(ProgressEvent::Executed(turing_machines_batch.len()));
+            }
+
+            executed += turing_machines_batch.len();
+
+            if self.shutdown.load(Ordering::Relaxed) == true {
+                info!(
+                    "Shutdown requested, stopped running after {} out of {} Turing machines.",
+                    executed, total
+                );
+                break;
+            }
+        }
+
+        // the Turing machines that were not executed are not sent
+        turing_machines.truncate(executed);
+
+        let _ = tx_stop.send(());
+        let _ = live_counters_logger.join();
 
         for turing_machine in turing_machines {
             // check if the machines was fileted
@@ -66,11 +286,25 @@ impl TuringMachineRunner {
                 FilterRuntimeType::LongEscapee => self.long_escapers += 1,
                 FilterRuntimeType::Cycler => self.cyclers += 1,
                 FilterRuntimeType::TranslatedCycler => self.translated_cyclers += 1,
+                FilterRuntimeType::Recurrence => self.recurrences += 1,
+                FilterRuntimeType::Bouncer => self.bouncers += 1,
+                FilterRuntimeType::Ctl => self.ctls += 1,
                 FilterRuntimeType::None => {}
             }
 
             if turing_machine.halted == false {
-                non_halting_turing_machines_size += 1;
+                self.non_halting += 1;
+            } else {
+                self.halted += 1;
+
+                let is_champion = match &self.champion {
+                    Some(champion) => turing_machine.better_than(champion),
+                    None => true,
+                };
+
+                if is_champion == true {
+                    self.champion = Some(turing_machine.clone());
+                }
             }
 
             let turing_machine_channel: Sender<TuringMachine> =
@@ -78,7 +312,7 @@ impl TuringMachineRunner {
             let _ = turing_machine_channel.send(turing_machine).await;
         }
 
-        self.display_filtering_results(non_halting_turing_machines_size);
+        self.display_filtering_results();
 
         // after the running of every TuringMachine,
         // drop the communication channel with the database
@@ -101,6 +335,7 @@ impl TuringMachineRunner {
             let turing_machine_channel: Sender<TuringMachine> =
                 self.tx_turing_machines.clone().unwrap();
             let semaphore = semaphore.clone();
+            let filter_runtime_config = self.filter_runtime_config.clone();
 
             let turing_machine_execution = tokio::spawn(async move {
                 // wait for the permission to execute the Turing machine
@@ -113,7 +348,10 @@ impl TuringMachineRunner {
                 // create a rayon thread to execute the CPU bound task,
                 // the task of executing the turing machine
                 rayon::spawn(move || {
-                    turing_machine.execute();
+                    let max_steps = TuringMachine::get_default_max_steps(
+                        turing_machine.transition_function.number_of_states,
+                    );
+                    turing_machine.execute_with_config(max_steps, &filter_runtime_config);
                     let _ = send.send(turing_machine);
                 });
 
@@ -148,40 +386,307 @@ impl TuringMachineRunner {
         info!("Dropped communication channel betwenn Turing Machine and Database Manager runners.");
     }
 
-    pub fn display_filtering_results(&self, turing_machines_size: i64) {
-        let short_escapers_percentage =
-            self.short_escapers as f64 * 100.0 / turing_machines_size as f64;
-        let long_escapers_percentage =
-            self.long_escapers as f64 * 100.0 / turing_machines_size as f64;
-        let cyclers_percentage = self.cyclers as f64 * 100.0 / turing_machines_size as f64;
+    /// Gets the number of Turing machines identified as non-halting
+    /// by each runtime filter, after `run` finished.
+    pub fn statistics(&self) -> RuntimeFilterStats {
+        return RuntimeFilterStats {
+            short_escapers: self.short_escapers,
+            long_escapers: self.long_escapers,
+            cyclers: self.cyclers,
+            translated_cyclers: self.translated_cyclers,
+            recurrences: self.recurrences,
+            bouncers: self.bouncers,
+            ctls: self.ctls,
+            non_halting: self.non_halting,
+        };
+    }
+
+    pub fn display_filtering_results(&self) {
+        let statistics = self.statistics();
+
+        let short_escapers_percentage = statistics.get_percentage(statistics.short_escapers);
+        let long_escapers_percentage = statistics.get_percentage(statistics.long_escapers);
+        let cyclers_percentage = statistics.get_percentage(statistics.cyclers);
         let translated_cyclers_percentage =
-            self.translated_cyclers as f64 * 100.0 / turing_machines_size as f64;
+            statistics.get_percentage(statistics.translated_cyclers);
+        let recurrences_percentage = statistics.get_percentage(statistics.recurrences);
+        let bouncers_percentage = statistics.get_percentage(statistics.bouncers);
+        let ctls_percentage = statistics.get_percentage(statistics.ctls);
 
         let total = short_escapers_percentage
             + long_escapers_percentage
             + cyclers_percentage
-            + translated_cyclers_percentage;
+            + translated_cyclers_percentage
+            + recurrences_percentage
+            + bouncers_percentage
+            + ctls_percentage;
 
         info!(
+            filter = "short escapers", percentage = short_escapers_percentage;
             "Filtered a total of short escapers: {:.2}%",
             short_escapers_percentage
         );
 
         info!(
+            filter = "long escapers", percentage = long_escapers_percentage;
             "Filtered a total of long escapers: {:.2}%",
             long_escapers_percentage
         );
 
-        info!("Filtered a total of cyclers: {:.2}%", cyclers_percentage);
+        info!(
+            filter = "cyclers", percentage = cyclers_percentage;
+            "Filtered a total of cyclers: {:.2}%",
+            cyclers_percentage
+        );
 
         info!(
+            filter = "translated cyclers", percentage = translated_cyclers_percentage;
             "Filtered a total of translated cyclers: {:.2}%",
             translated_cyclers_percentage
         );
 
         info!(
+            filter = "recurrences", percentage = recurrences_percentage;
+            "Filtered a total of recurrences: {:.2}%",
+            recurrences_percentage
+        );
+
+        info!(
+            filter = "bouncers", percentage = bouncers_percentage;
+            "Filtered a total of bouncers: {:.2}%",
+            bouncers_percentage
+        );
+
+        info!(
+            filter = "CTLs", percentage = ctls_percentage;
+            "Filtered a total of CTLs: {:.2}%",
+            ctls_percentage
+        );
+
+        info!(
+            percentage = total;
             "Filtered a total of {:.2}% Turing machines HOLDOUTS with runtime filters.",
             total
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::delta::transition_function::TransitionFunction;
+    use crate::turing_machine::turing_machine::TuringMachine;
+
+    #[tokio::test]
+    async fn run_sends_executed_progress() {
+        let (tx_turing_machines, mut rx_turing_machines) = tokio::sync::mpsc::channel(1000);
+        let (tx_progress, rx_progress) = std::sync::mpsc::channel();
+
+        let turing_machines: Vec<TuringMachine> = ["1RB1LB_1LA1RH", "0RB1RH_0LA1RH"]
+            .iter()
+            .map(|encoded| {
+                TuringMachine::new(TransitionFunction::from_standard_notation(encoded).unwrap())
+            })
+            .collect();
+
+        let mut tm_runner = TuringMachineRunner::new(tx_turing_machines);
+        tm_runner
+            .run(turing_machines, Some(100), Some(tx_progress))
+            .await;
+
+        let mut executed: usize = 0;
+
+        while let Some(_) = rx_turing_machines.recv().await {
+            executed += 1;
+        }
+
+        let events: Vec<ProgressEvent> = rx_progress.try_iter().collect();
+
+        assert_eq!(executed, 2);
+        assert!(events.contains(&ProgressEvent::Executed(2)));
+    }
+
+    #[tokio::test]
+    async fn run_skips_invalid() {
+        let (tx_turing_machines, mut rx_turing_machines) = tokio::sync::mpsc::channel(1000);
+
+        // the second one has no transition for (B, 1), so it would halt on it
+        let turing_machines: Vec<TuringMachine> = ["1RB1LB_1LA1RH", "1RB1LB_1LA---"]
+            .iter()
+            .map(|encoded| {
+                TuringMachine::new(TransitionFunction::from_standard_notation(encoded).unwrap())
+            })
+            .collect();
+
+        assert_eq!(
+            turing_machines[1]
+                .transition_function
+                .is_deterministic_complete(),
+            false
+        );
+
+        let mut tm_runner = TuringMachineRunner::new(tx_turing_machines);
+        tm_runner.run(turing_machines, Some(100), None).await;
+
+        let mut received: Vec<TuringMachine> = Vec::new();
+
+        while let Some(turing_machine) = rx_turing_machines.recv().await {
+            received.push(turing_machine);
+        }
+
+        assert_eq!(tm_runner.invalid, 1);
+        assert_eq!(received.len(), 1);
+        assert_eq!(
+            received[0]
+                .transition_function
+                .to_standard_notation()
+                .unwrap(),
+            "1RB1LB_1LA1RH"
+        );
+        assert_eq!(tm_runner.live_counters.halted.load(Ordering::Relaxed), 1);
+        assert_eq!(tm_runner.non_halting, 0);
+    }
+
+    #[tokio::test]
+    async fn run_with_num_threads() {
+        let (tx_turing_machines, mut rx_turing_machines) = tokio::sync::mpsc::channel(1000);
+
+        let turing_machines: Vec<TuringMachine> = ["1RB1LB_1LA1RH", "1RB1RH_1LB0RA"]
+            .iter()
+            .map(|encoded| {
+                TuringMachine::new(TransitionFunction::from_standard_notation(encoded).unwrap())
+            })
+            .collect();
+
+        let mut tm_runner = TuringMachineRunner::new(tx_turing_machines);
+        tm_runner.set_num_threads(2);
+        tm_runner.run(turing_machines, Some(100), None).await;
+
+        let mut executed: usize = 0;
+
+        while let Some(turing_machine) = rx_turing_machines.recv().await {
+            assert!(turing_machine.steps > 0);
+            executed += 1;
+        }
+
+        assert_eq!(tm_runner.num_threads, 2);
+        assert_eq!(executed, 2);
+    }
+
+    #[tokio::test]
+    async fn run_with_filter_runtime_config() {
+        let (tx_turing_machines, mut rx_turing_machines) = tokio::sync::mpsc::channel(1000);
+
+        // a cycler that never writes on the tape, which is caught by
+        // the cyclers filter once the recurrence filter is disabled
+        let turing_machines: Vec<TuringMachine> = ["0RB1RH_0LA1RH"]
+            .iter()
+            .map(|encoded| {
+                TuringMachine::new(TransitionFunction::from_standard_notation(encoded).unwrap())
+            })
+            .collect();
+
+        let mut tm_runner = TuringMachineRunner::new(tx_turing_machines);
+        tm_runner.set_filter_runtime_config(FilterRuntimeConfig::with_filters(vec![
+            FilterRuntimeType::Cycler,
+        ]));
+        tm_runner.run(turing_machines, Some(100), None).await;
+
+        while let Some(_) = rx_turing_machines.recv().await {}
+
+        assert_eq!(tm_runner.cyclers, 1);
+        assert_eq!(tm_runner.recurrences, 0);
+    }
+
+    #[tokio::test]
+    async fn statistics() {
+        let (tx_turing_machines, mut rx_turing_machines) = tokio::sync::mpsc::channel(1000);
+
+        // the BB(2) champion and a cycler that never writes
+        // on the tape, moving back and forth between two cells
+        let turing_machines: Vec<TuringMachine> = ["1RB1LB_1LA1RH", "0RB1RH_0LA1RH"]
+            .iter()
+            .map(|encoded| {
+                TuringMachine::new(TransitionFunction::from_standard_notation(encoded).unwrap())
+            })
+            .collect();
+
+        let mut tm_runner = TuringMachineRunner::new(tx_turing_machines);
+        tm_runner.run(turing_machines, Some(100), None).await;
+
+        while let Some(_) = rx_turing_machines.recv().await {}
+
+        let statistics = tm_runner.statistics();
+
+        // the cycler is caught by the recurrence filter before
+        // the cyclers filter sees its cycle completed twice
+        assert_eq!(statistics.cyclers, 0);
+        assert_eq!(statistics.recurrences, 1);
+        assert_eq!(statistics.non_halting, 1);
+        assert_eq!(statistics.short_escapers, 0);
+        assert_eq!(statistics.translated_cyclers, 0);
+    }
+
+    #[tokio::test]
+    async fn run_live_counters_match_tally() {
+        let (tx_turing_machines, mut rx_turing_machines) = tokio::sync::mpsc::channel(1000);
+
+        // halting and non-halting Turing machines
+        let turing_machines: Vec<TuringMachine> = [
+            "1RB1LB_1LA1RH",
+            "1RB1RH_0RA0RA",
+            "1RB1RH_1LA1LA",
+            "1RB1RH_1LC0LA_1RA0RC",
+        ]
+        .iter()
+        .map(|encoded| {
+            TuringMachine::new(TransitionFunction::from_standard_notation(encoded).unwrap())
+        })
+        .collect();
+
+        let mut tm_runner = TuringMachineRunner::new(tx_turing_machines);
+        tm_runner.run(turing_machines, Some(100), None).await;
+
+        let mut halted: i64 = 0;
+        let mut non_halted: i64 = 0;
+
+        while let Some(turing_machine) = rx_turing_machines.recv().await {
+            if turing_machine.halted == true {
+                halted += 1;
+            } else {
+                non_halted += 1;
+            }
+        }
+
+        let live_counters = &tm_runner.live_counters;
+
+        assert_eq!(live_counters.halted.load(Ordering::Relaxed), halted);
+        assert_eq!(live_counters.non_halted.load(Ordering::Relaxed), non_halted);
+        assert_eq!(
+            live_counters.short_escapers.load(Ordering::Relaxed),
+            tm_runner.short_escapers
+        );
+        assert_eq!(
+            live_counters.long_escapers.load(Ordering::Relaxed),
+            tm_runner.long_escapers
+        );
+        assert_eq!(
+            live_counters.cyclers.load(Ordering::Relaxed),
+            tm_runner.cyclers
+        );
+        assert_eq!(
+            live_counters.translated_cyclers.load(Ordering::Relaxed),
+            tm_runner.translated_cyclers
+        );
+        assert_eq!(
+            live_counters.recurrences.load(Ordering::Relaxed),
+            tm_runner.recurrences
+        );
+        assert_eq!(
+            live_counters.bouncers.load(Ordering::Relaxed),
+            tm_runner.bouncers
+        );
+        assert_eq!(live_counters.ctls.load(Ordering::Relaxed), tm_runner.ctls);
+        assert_eq!(halted + non_halted, 4);
+    }
+}
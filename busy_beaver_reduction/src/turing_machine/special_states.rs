@@ -1,3 +1,18 @@
+/// State every Turing machine starts in.
+pub const START_STATE: u8 = 0;
+/// Sentinel used for the halting state, outside of the range of the states
+/// of any Turing machine, which are numbered from 0 to `number_of_states - 1`,
+/// so it never collides with a real state.
+///
+/// It is part of the encoding of the transition functions, e.g. `0,1,255,1,1`
+/// for a transition into the halting state ( see `TransitionFunction::encode` ).
+pub const HALT_STATE: u8 = u8::MAX;
+/// Sentinel previously used for the halting state, which can still be found
+/// in the encodings stored by older versions, e.g. `0,1,101,1,1`; it is read
+/// as the halting state for Turing machines with at most 101 states
+/// ( see `TransitionFunction::decode` ).
+pub const LEGACY_HALT_STATE: u8 = 101;
+
 pub enum SpecialStates {
     StateStart,
     StateHalt,
@@ -6,25 +21,25 @@ pub enum SpecialStates {
 
 impl SpecialStates {
     /// Gets the value (`u8`) associated to each special state:
-    /// - `StateStart` = 0
-    /// - `StateHalt` = 101
-    /// - `Default` = 1
+    /// - `StateStart` = `START_STATE`
+    /// - `StateHalt` = `HALT_STATE`
+    /// - `Default` = 1, the first state after the starting one
     pub fn value(&self) -> u8 {
         match *self {
-            SpecialStates::StateStart => 0,
-            SpecialStates::StateHalt => 101,
+            SpecialStates::StateStart => START_STATE,
+            SpecialStates::StateHalt => HALT_STATE,
             SpecialStates::Default => 1,
         }
     }
 
     /// Transforms the value given (`u8`) to a SpecialStates:
-    /// - `0` = StateStart
-    /// - `101` = StateHalt
-    /// - `_` = Default
+    /// - `START_STATE` = StateStart
+    /// - `HALT_STATE` = StateHalt
+    /// - `_` = Default, any other state
     pub fn transform(state: u8) -> Self {
         match state {
-            0 => SpecialStates::StateStart,
-            101 => SpecialStates::StateHalt,
+            START_STATE => SpecialStates::StateStart,
+            HALT_STATE => SpecialStates::StateHalt,
             _ => SpecialStates::Default,
         }
     }
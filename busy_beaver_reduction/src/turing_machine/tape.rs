@@ -0,0 +1,229 @@
+use std::collections::VecDeque;
+
+/// Number of cells packed in a word of a `BitTape`.
+const CELLS_PER_WORD: usize = u64::BITS as usize;
+
+/// Backing storage of the tape of a `TuringMachine`: a sequence of cells,
+/// indexed from 0, that can be extended at both ends.
+pub trait Tape: Clone {
+    /// Creates a tape with a single cell, holding `symbol`.
+    fn with_cell(symbol: u8) -> Self;
+
+    /// Checks if the tape can hold every symbol of an
+    /// alphabet with `number_of_symbols` symbols.
+    fn supports_alphabet(_number_of_symbols: u8) -> bool {
+        return true;
+    }
+
+    /// Gets the number of cells of the tape.
+    fn len(&self) -> usize;
+
+    /// Checks if the tape has no cells.
+    fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    /// Gets the symbol found in the cell at `index`.
+    fn get(&self, index: usize) -> u8;
+
+    /// Writes `symbol` in the cell at `index`.
+    fn set(&mut self, index: usize, symbol: u8);
+
+    /// Adds a new cell, holding `symbol`, in front of the tape,
+    /// which shifts the indexes of the other cells to the right.
+    fn push_front(&mut self, symbol: u8);
+
+    /// Adds a new cell, holding `symbol`, at the end of the tape.
+    fn push_back(&mut self, symbol: u8);
+
+    /// Counts the cells that do not hold the `blank_symbol`.
+    fn count_non_blank(&self, blank_symbol: u8) -> u64;
+
+    /// Clears the tape to a single cell, holding `symbol`,
+    /// keeping the memory it already allocated.
+    fn reset(&mut self, symbol: u8);
+}
+
+/// The default tape, with a full byte for every cell,
+/// which can hold any symbol.
+impl Tape for VecDeque<u8> {
+    fn with_cell(symbol: u8) -> Self {
+        return VecDeque::from([symbol]);
+    }
+
+    fn len(&self) -> usize {
+        return VecDeque::len(self);
+    }
+
+    fn get(&self, index: usize) -> u8 {
+        return self[index];
+    }
+
+    fn set(&mut self, index: usize, symbol: u8) {
+        self[index] = symbol;
+    }
+
+    fn push_front(&mut self, symbol: u8) {
+        VecDeque::push_front(self, symbol);
+    }
+
+    fn push_back(&mut self, symbol: u8) {
+        VecDeque::push_back(self, symbol);
+    }
+
+    fn count_non_blank(&self, blank_symbol: u8) -> u64 {
+        return self
+            .iter()
+            .filter(|&&symbol| symbol != blank_symbol)
+            .count() as u64;
+    }
+
+    fn reset(&mut self, symbol: u8) {
+        self.clear();
+        VecDeque::push_back(self, symbol);
+    }
+}
+
+/// Tape for the binary alphabet, packing 64 cells in every word,
+/// so it uses 8 times less memory than a `VecDeque<u8>`.
+///
+/// Only the symbols 0 and 1 can be written, so it can only back
+/// the tape of Turing machines with 2 symbols.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BitTape {
+    words: VecDeque<u64>,
+    /// index of the bit holding the first cell,
+    /// counted from the start of the first word
+    offset: usize,
+    len: usize,
+}
+
+impl BitTape {
+    /// Gets the word and the bit within it that hold the cell at `index`.
+    fn locate(&self, index: usize) -> (usize, usize) {
+        let bit = self.offset + index;
+
+        return (bit / CELLS_PER_WORD, bit % CELLS_PER_WORD);
+    }
+}
+
+impl Tape for BitTape {
+    fn with_cell(symbol: u8) -> Self {
+        let mut tape = BitTape {
+            words: VecDeque::from([0]),
+            offset: 0,
+            len: 1,
+        };
+        tape.set(0, symbol);
+
+        return tape;
+    }
+
+    fn supports_alphabet(number_of_symbols: u8) -> bool {
+        return number_of_symbols == 2;
+    }
+
+    fn len(&self) -> usize {
+        return self.len;
+    }
+
+    fn get(&self, index: usize) -> u8 {
+        let (word, bit) = self.locate(index);
+
+        return ((self.words[word] >> bit) & 1) as u8;
+    }
+
+    fn set(&mut self, index: usize, symbol: u8) {
+        assert!(symbol <= 1, "A BitTape can only hold the symbols 0 and 1.");
+
+        let (word, bit) = self.locate(index);
+
+        if symbol == 0 {
+            self.words[word] &= !(1 << bit);
+        } else {
+            self.words[word] |= 1 << bit;
+        }
+    }
+
+    fn push_front(&mut self, symbol: u8) {
+        // there is no bit left in front of the first cell, add a new word
+        if self.offset == 0 {
+            self.words.push_front(0);
+            self.offset = CELLS_PER_WORD;
+        }
+
+        self.offset -= 1;
+        self.len += 1;
+        self.set(0, symbol);
+    }
+
+    fn push_back(&mut self, symbol: u8) {
+        // there is no bit left after the last cell, add a new word
+        if self.offset + self.len == self.words.len() * CELLS_PER_WORD {
+            self.words.push_back(0);
+        }
+
+        self.len += 1;
+        self.set(self.len - 1, symbol);
+    }
+
+    fn count_non_blank(&self, blank_symbol: u8) -> u64 {
+        // the bits outside of the tape are never written, so they are 0
+        let ones: u64 = self.words.iter().map(|word| word.count_ones() as u64).sum();
+
+        if blank_symbol == 0 {
+            return ones;
+        }
+
+        return self.len as u64 - ones;
+    }
+
+    fn reset(&mut self, symbol: u8) {
+        self.words.clear();
+        self.words.push_back(0);
+        self.offset = 0;
+        self.len = 1;
+        self.set(0, symbol);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bit_tape_matches_vec_tape() {
+        let mut bit_tape = BitTape::with_cell(0);
+        let mut vec_tape: VecDeque<u8> = Tape::with_cell(0);
+
+        // extend both tapes over several words, at both ends
+        for index in 0..150u32 {
+            let symbol = (index % 3 == 0) as u8;
+
+            Tape::push_front(&mut bit_tape, symbol);
+            Tape::push_front(&mut vec_tape, symbol);
+            Tape::push_back(&mut bit_tape, 1 - symbol);
+            Tape::push_back(&mut vec_tape, 1 - symbol);
+        }
+
+        bit_tape.set(7, 1);
+        Tape::set(&mut vec_tape, 7, 1);
+        bit_tape.set(200, 0);
+        Tape::set(&mut vec_tape, 200, 0);
+
+        assert_eq!(Tape::len(&bit_tape), Tape::len(&vec_tape));
+        for index in 0..Tape::len(&vec_tape) {
+            assert_eq!(bit_tape.get(index), Tape::get(&vec_tape, index));
+        }
+        assert_eq!(bit_tape.count_non_blank(0), vec_tape.count_non_blank(0));
+        assert_eq!(bit_tape.count_non_blank(1), vec_tape.count_non_blank(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "can only hold the symbols 0 and 1")]
+    fn bit_tape_rejects_other_symbols() {
+        let mut bit_tape = BitTape::with_cell(0);
+
+        bit_tape.set(0, 2);
+    }
+}
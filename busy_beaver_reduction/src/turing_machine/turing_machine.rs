@@ -1,93 +1,338 @@
 use crypto::digest::Digest;
 use crypto::sha2::Sha256;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
 use std::time::{Duration, Instant};
 
+use crate::delta::decode_error::DecodeError;
 use crate::delta::transition_function::TransitionFunction;
-use crate::filter::filter_runtime::FilterRuntime;
-use crate::filter::filter_runtime::FilterRuntimeType;
+use crate::filter::filter_runtime::{FilterRuntime, FilterRuntimeConfig, FilterRuntimeType};
 use crate::turing_machine::direction::Direction;
+use crate::turing_machine::macro_machine::MacroMachine;
 use crate::turing_machine::special_states::SpecialStates;
+use crate::turing_machine::tape::Tape;
+use crate::turing_machine::turing_machine_csv::escape_csv_field;
 
-const MAX_STEPS_TO_RUN: i64 = 21;
+/// Number of steps after which the known `S(n)` champions halt,
+/// indexed by the number of states (`S(1)` ... `S(5)`).
+const KNOWN_MAXIMUM_STEPS: [u64; 5] = [1, 6, 21, 107, 47_176_870];
+/// Symbol every cell of the tape holds before it is written,
+/// unless another blank symbol is set ( see `set_blank_symbol` ).
+const BLANK_SYMBOL: u8 = 0;
+/// Default number of cells the tape can grow to while executing, after which
+/// the execution is aborted, so a single Turing machine that keeps growing
+/// its tape cannot use all the memory shared by the runner's threads.
+const MAX_TAPE_CELLS: usize = 1 << 24;
+/// Number of steps between two checks of the elapsed time, when the
+/// execution has a timeout, so the clock is not read at every step.
+const TIMEOUT_CHECK_INTERVAL: u64 = 256;
 
-#[derive(Clone)]
-pub struct TuringMachine {
+/// Only the fields that are persisted in the database are serialized;
+/// the rest of them describe the execution and are rebuilt on deserialization.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "TuringMachineRecord")]
+pub struct TuringMachine<T: Tape = VecDeque<u8>> {
     pub transition_function: TransitionFunction,
-    pub tape: Vec<u8>,
+    #[serde(skip_serializing)]
+    pub tape: T,
+    #[serde(skip_serializing)]
+    pub blank_symbol: u8,
+    #[serde(skip_serializing)]
+    pub tape_origin: usize,
+    #[serde(skip_serializing)]
     pub tape_increased: bool,
-    pub head_position: usize,
+    #[serde(skip_serializing)]
+    pub head_position: i64,
+    #[serde(skip_serializing)]
     pub current_state: u8,
     pub halted: bool,
-    pub steps: i64,
-    pub score: i32,
+    #[serde(skip_serializing)]
+    pub halt_reason: Option<HaltReason>,
+    #[serde(skip_serializing)]
+    pub timed_out: bool,
+    #[serde(skip_serializing)]
+    pub max_tape_cells: usize,
+    #[serde(skip_serializing)]
+    pub tape_limited: bool,
+    pub steps: u64,
+    pub score: u64,
+    pub space: i64,
     pub runtime: i64,
+    #[serde(skip_serializing)]
+    pub extent: (isize, isize),
+    #[serde(skip_serializing)]
     pub filtered: FilterRuntimeType,
+    pub filtered_at_step: Option<u64>,
 }
 
-impl TuringMachine {
-    pub fn new(transition_function: TransitionFunction) -> Self {
+/// The persisted fields of a `TuringMachine`,
+/// used to deserialize it.
+#[derive(Deserialize)]
+struct TuringMachineRecord {
+    transition_function: TransitionFunction,
+    halted: bool,
+    steps: u64,
+    score: u64,
+    space: i64,
+    runtime: i64,
+    filtered_at_step: Option<u64>,
+}
+
+impl<T: Tape> From<TuringMachineRecord> for TuringMachine<T> {
+    fn from(record: TuringMachineRecord) -> Self {
+        let mut turing_machine = TuringMachine::with_tape(record.transition_function);
+        turing_machine.halted = record.halted;
+        turing_machine.steps = record.steps;
+        turing_machine.score = record.score;
+        turing_machine.space = record.space;
+        turing_machine.runtime = record.runtime;
+        turing_machine.filtered_at_step = record.filtered_at_step;
+
+        turing_machine
+    }
+}
+
+/// Configuration of a Turing machine after one step of its
+/// execution, recorded by `TuringMachine::execute_traced`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TraceStep {
+    pub step: u64,
+    pub head_position: i64,
+    pub current_state: u8,
+    /// coordinate of the first cell of `tape`,
+    /// relative to the cell the Turing machine started on
+    pub tape_start: i64,
+    /// the tape, trimmed of the blank cells from both ends
+    pub tape: Vec<u8>,
+}
+
+/// Reason for which a Turing machine halted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HaltReason {
+    /// A transition moved the Turing machine into the halting state.
+    HaltState,
+    /// There was no transition defined for the current state and the
+    /// symbol under the head, which is treated as a halting transition.
+    UndefinedTransition,
+}
+
+/// Outcome of running a Turing machine with `TuringMachine::run_to_halt`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RunOutcome {
+    /// The Turing machine halted after `steps` steps, with `score` 1s on the tape.
+    Halted { steps: u64, score: u64 },
+    /// The Turing machine was identified as non-halting by a runtime filter.
+    Filtered(FilterRuntimeType),
+    /// The Turing machine reached the maximum number of steps without halting.
+    StepLimit,
+    /// The Turing machine ran for longer than its timeout without halting.
+    TimedOut,
+    /// The tape of the Turing machine grew over `max_tape_cells` cells.
+    TapeLimit,
+}
+
+/// Outcome of advancing a Turing machine with `TuringMachine::step_n`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StepOutcome {
+    /// Number of steps that were actually made.
+    pub steps: u64,
+    /// Whether the Turing machine is halted, by a halting
+    /// transition or by an undefined one.
+    pub halted: bool,
+}
+
+impl<T: Tape> TuringMachine<T> {
+    /// Creates a Turing machine whose tape is backed by `T`
+    /// ( see `Tape` ), e.g. a `BitTape` for the binary alphabet.
+    ///
+    /// Panics if `T` can not hold every symbol of the transition function.
+    pub fn with_tape(transition_function: TransitionFunction) -> Self {
+        assert!(
+            T::supports_alphabet(transition_function.number_of_symbols),
+            "The tape can not hold the {} symbols of the transition function.",
+            transition_function.number_of_symbols
+        );
+
         TuringMachine {
             transition_function: transition_function,
-            tape: vec![0],
+            tape: T::with_cell(BLANK_SYMBOL),
+            blank_symbol: BLANK_SYMBOL,
+            tape_origin: 0,
             tape_increased: false,
             head_position: 0,
             current_state: SpecialStates::StateStart.value(),
             halted: false,
+            halt_reason: None,
+            timed_out: false,
+            max_tape_cells: MAX_TAPE_CELLS,
+            tape_limited: false,
             steps: 0,
             score: 0,
+            space: 0,
             runtime: 0,
+            extent: (0, 0),
             filtered: FilterRuntimeType::None,
+            filtered_at_step: None,
         }
     }
 
-    /// Calculate the score from the tape, the number
-    /// of 1s written on the tape.
+    /// Resets the Turing machine to its initial configuration, the same as
+    /// a newly created one, with the given `transition_function`.
+    ///
+    /// The tape is cleared to a single blank cell, keeping the memory it
+    /// already allocated, so the same Turing machine can be reused
+    /// for running many transition functions.
+    pub fn reset(&mut self, transition_function: TransitionFunction) {
+        self.transition_function = transition_function;
+        self.tape.reset(BLANK_SYMBOL);
+        self.blank_symbol = BLANK_SYMBOL;
+        self.tape_origin = 0;
+        self.tape_increased = false;
+        self.head_position = 0;
+        self.current_state = SpecialStates::StateStart.value();
+        self.halted = false;
+        self.halt_reason = None;
+        self.timed_out = false;
+        self.max_tape_cells = MAX_TAPE_CELLS;
+        self.tape_limited = false;
+        self.steps = 0;
+        self.score = 0;
+        self.space = 0;
+        self.runtime = 0;
+        self.extent = (0, 0);
+        self.filtered = FilterRuntimeType::None;
+        self.filtered_at_step = None;
+    }
+
+    /// Gets the index in the `tape` of the cell the head is pointing at.
+    ///
+    /// The `head_position` is a signed coordinate, relative to the
+    /// cell the Turing machine started on, found at `tape_origin`.
+    pub fn get_head_index(&self) -> usize {
+        (self.tape_origin as i64 + self.head_position) as usize
+    }
+
+    /// Gets the symbol found on the tape where the head is pointing at.
+    pub fn get_head_symbol(&self) -> u8 {
+        self.tape.get(self.get_head_index())
+    }
+
+    /// Calculate the score from the tape, the number of non-blank
+    /// cells on the tape; for a binary alphabet, the number of 1s.
+    ///
+    /// The score is kept up to date by every transition made
+    /// ( see `write_head_symbol` ), so the whole tape only needs
+    /// to be scanned to recompute it, e.g. to verify it.
     pub fn set_score(&mut self) {
-        for &symbol in self.tape.iter() {
-            if symbol == 1 {
-                self.score += 1;
-            }
+        self.score = self.tape.count_non_blank(self.blank_symbol);
+    }
+
+    /// Writes `symbol` in the cell the head is pointing at, updating the
+    /// score incrementally: it increases when a blank cell is written
+    /// with another symbol, and decreases when a cell is blanked.
+    fn write_head_symbol(&mut self, symbol: u8) {
+        let head_index = self.get_head_index();
+        let previous = self.tape.get(head_index);
+
+        self.tape.set(head_index, symbol);
+
+        // read the symbol back, since the tape might not hold every symbol
+        let written = self.tape.get(head_index);
+
+        if previous == self.blank_symbol && written != self.blank_symbol {
+            self.score += 1;
+        } else if previous != self.blank_symbol && written == self.blank_symbol {
+            self.score -= 1;
         }
     }
 
-    /// Sets the runtime for the execution of the
-    /// turing machine, given a `core::time::Duration` object.
-    pub fn set_runtime(&mut self, time: Duration) {
-        self.runtime = time.as_secs() as i64;
+    /// Calculate the space from the tape, the maximum
+    /// number of cells the tape reached during the execution.
+    pub fn set_space(&mut self) {
+        // the tape is never shrinked, so its current
+        // length is the maximum length it reached
+        self.space = self.space.max(self.tape.len() as i64);
     }
 
-    /// Runs the turing machine until it is halted or until
-    /// it is stopped by a runtime filter.
+    /// Gets the extent of the tape after the execution, as computed by
+    /// `set_extent`; together with the final `head_position`, it can be
+    /// cross-checked against the tape signatures of the published records.
+    pub fn final_extent(&self) -> (isize, isize) {
+        return self.extent;
+    }
+
+    /// Compares the results of two Turing machines, for choosing the
+    /// Busy Beaver champion: the higher score wins, ties are broken by
+    /// fewer steps, and then by the encoding of the transition function,
+    /// so the order is total and the champion does not depend on the
+    /// order the Turing machines were executed in.
     ///
-    /// Uses a `FilterRuntime` object that is watching
-    /// carefully the execution of the turing machine.
-    /// If at any time the filters are not passed, stop the execution.
-    pub fn execute(&mut self) {
-        let start_time: Instant = Instant::now();
-        let mut filter_runtime: FilterRuntime = FilterRuntime::new();
+    /// Returns `Ordering::Greater` if `self` is the better one.
+    pub fn compare_results(&self, other: &Self) -> Ordering {
+        return self
+            .score
+            .cmp(&other.score)
+            .then_with(|| other.steps.cmp(&self.steps))
+            .then_with(|| {
+                other
+                    .transition_function
+                    .encode()
+                    .cmp(&self.transition_function.encode())
+            });
+    }
 
-        self.make_transition();
+    /// Checks if the results of the Turing machine are
+    /// strictly better than the `other` ones ( see `compare_results` ).
+    pub fn better_than(&self, other: &Self) -> bool {
+        return self.compare_results(other) == Ordering::Greater;
+    }
 
-        while self.halted != true && self.steps < MAX_STEPS_TO_RUN {
-            let filter_result: FilterRuntimeType = filter_runtime.filter_all(&self);
+    /// Gets the champion of the Turing machines, the one with
+    /// the best results ( see `compare_results` ).
+    ///
+    /// Returns `None` if there are no Turing machines.
+    pub fn best_of(turing_machines: &[Self]) -> Option<&Self> {
+        return turing_machines
+            .iter()
+            .max_by(|first, second| first.compare_results(second));
+    }
 
-            match filter_result {
-                FilterRuntimeType::ShortEscapee
-                | FilterRuntimeType::LongEscapee
-                | FilterRuntimeType::Cycler
-                | FilterRuntimeType::TranslatedCycler => {
-                    self.filtered = filter_result;
-                    break;
-                }
-                FilterRuntimeType::None => {}
-            };
+    /// Merges the results of multiple runs, keeping one Turing machine for
+    /// every encoding of the transition function, in the order they first
+    /// appeared; on a conflict, the copy that halted is preferred, and then
+    /// the one with the higher score.
+    pub fn dedup_by_encoding(turing_machines: Vec<Self>) -> Vec<Self> {
+        // String -> index of the Turing machine kept for the encoding
+        let mut indexes: HashMap<String, usize> = HashMap::new();
+        let mut deduplicated: Vec<Self> = Vec::new();
 
-            self.make_transition();
+        for turing_machine in turing_machines {
+            match indexes.get(&turing_machine.transition_function.encode()) {
+                Some(&index) => {
+                    let kept = &deduplicated[index];
+
+                    if (turing_machine.halted, turing_machine.score) > (kept.halted, kept.score) {
+                        deduplicated[index] = turing_machine;
+                    }
+                }
+                None => {
+                    indexes.insert(
+                        turing_machine.transition_function.encode(),
+                        deduplicated.len(),
+                    );
+                    deduplicated.push(turing_machine);
+                }
+            }
         }
 
-        // set the metrics for the turing machine
-        self.set_score();
-        self.set_runtime(start_time.elapsed());
+        return deduplicated;
+    }
+
+    /// Sets the runtime for the execution of the
+    /// turing machine, given a `core::time::Duration` object.
+    pub fn set_runtime(&mut self, time: Duration) {
+        self.runtime = time.as_secs() as i64;
     }
 
     /// Tries to make a transition of the Turing Machine
@@ -95,23 +340,40 @@ impl TuringMachine {
     /// the `tape` at the `head_position` position.
     ///
     /// If the transition exists in the `transition_function`,
-    /// it will be made.
+    /// it will be made. Otherwise, the Turing machine halts,
+    /// as if it made a halting transition: it writes a 1 and
+    /// the step is counted, but the head does not move.
     ///
-    /// Return whether the transition describes is possible.
+    /// Return whether the transition was defined; a Turing
+    /// machine that already halted does not make any transition.
     pub fn make_transition(&mut self) -> bool {
+        if self.halted == true {
+            return false;
+        }
+
         let possible_transition = self
             .transition_function
-            .transitions
-            .get(&(self.current_state, self.tape[self.head_position]));
+            .transitions()
+            .get(&(self.current_state, self.get_head_symbol()));
 
         match possible_transition {
-            Some(transition) => {
+            Some(&transition) => {
                 // by default, tape is not increased
                 self.tape_increased = false;
                 // change the current state
                 self.current_state = transition.0;
                 // write the new value to the tape
-                self.tape[self.head_position] = transition.1;
+                self.write_head_symbol(transition.1);
+
+                // a halting transition only writes its symbol, the head
+                // does not move, so the tape is not extended by it
+                if transition.0 == SpecialStates::StateHalt.value() {
+                    self.steps += 1;
+                    self.is_halted();
+
+                    return true;
+                }
+
                 // move the header of the tape
                 self.move_(transition.2);
 
@@ -121,6 +383,14 @@ impl TuringMachine {
                 return true;
             }
             None => {
+                self.tape_increased = false;
+                // the halting transitions always write a 1
+                self.write_head_symbol(1);
+                self.steps += 1;
+
+                self.halted = true;
+                self.halt_reason = Some(HaltReason::UndefinedTransition);
+
                 return false;
             }
         }
@@ -138,17 +408,18 @@ impl TuringMachine {
     }
 
     /// Moves the `head` (`head_position`) of the Turing Machine
-    /// to the left only if it does not exceed the
-    /// left most position of the tape.
+    /// to the left and `extends` the tape if necessary.
     pub fn move_left(&mut self) {
         // if the head is at the left most position,
-        // insert a new element there
-        if self.head_position == 0 {
-            self.tape.insert(0, 0);
+        // add a new value in front of the tape, which
+        // shifts the origin of the tape to the right
+        if self.get_head_index() == 0 {
+            self.tape.push_front(self.blank_symbol);
+            self.tape_origin += 1;
             self.tape_increased = true;
-        } else {
-            self.head_position -= 1;
         }
+
+        self.head_position -= 1;
     }
 
     /// Moves the `head` (`head_position`) of the Turing Machine
@@ -159,8 +430,8 @@ impl TuringMachine {
         // if the tape length is exceeded, add
         // a new value on the tape, where the head
         // will be pointing at
-        if self.tape.len() - 1 < self.head_position {
-            self.tape.push(0);
+        if self.tape.len() == self.get_head_index() {
+            self.tape.push_back(self.blank_symbol);
             self.tape_increased = true;
         }
     }
@@ -173,21 +444,1154 @@ impl TuringMachine {
         let state_: SpecialStates = SpecialStates::transform(self.current_state);
 
         match state_ {
-            SpecialStates::StateHalt => self.halted = true,
+            SpecialStates::StateHalt => {
+                self.halted = true;
+                self.halt_reason = Some(HaltReason::HaltState);
+            }
             _ => {}
         }
     }
 
+    /// Advances the turing machine by at most `n` transitions, stopping
+    /// early if it halts, without any runtime filter, so it can be
+    /// inspected between the calls, e.g. by a debugger.
+    ///
+    /// Only the score is kept up to date; the other metrics, e.g. the space,
+    /// are set by the executions that run the turing machine to its end.
+    pub fn step_n(&mut self, n: u64) -> StepOutcome {
+        let steps = self.steps;
+
+        while self.halted == false && self.steps - steps < n {
+            self.make_transition();
+        }
+
+        return StepOutcome {
+            steps: self.steps - steps,
+            halted: self.halted,
+        };
+    }
+
+    /// Runs the turing machine until it is halted or until it reaches
+    /// `max_steps` steps, without any runtime filter, e.g. to run a
+    /// known champion on a `BitTape` for millions of steps.
+    pub fn execute_unfiltered(&mut self, max_steps: u64) {
+        let start_time: Instant = Instant::now();
+
+        // the score is kept up to date from the one of the current tape,
+        // not from a score that was set without writing the tape
+        self.set_score();
+
+        while self.halted != true && self.steps < max_steps {
+            self.make_transition();
+        }
+
+        // set the metrics for the turing machine,
+        // the score was kept up to date by the transitions
+        debug_assert_eq!(self.score, self.tape.count_non_blank(self.blank_symbol));
+        self.set_space();
+        self.set_runtime(start_time.elapsed());
+    }
+
+    /// Runs the turing machine from its initial configuration, until it is
+    /// halted or until it reaches `max_steps` steps, using a `MacroMachine`
+    /// that groups the tape in blocks of `block_size` cells, so the sweeps
+    /// across uniform regions of the tape are made in a single step.
+    ///
+    /// The halting, the steps and the score are the same as the ones of
+    /// `execute_unfiltered`, but the tape of the turing machine is not written.
+    pub fn execute_macro(&mut self, block_size: usize, max_steps: u64) -> RunOutcome {
+        let start_time: Instant = Instant::now();
+        let mut macro_machine =
+            MacroMachine::new(&self.transition_function, block_size, self.blank_symbol);
+
+        macro_machine.execute(max_steps);
+
+        self.halted = macro_machine.halted;
+        self.halt_reason = macro_machine.halt_reason;
+        self.current_state = macro_machine.state;
+        self.steps = macro_machine.steps;
+        self.score = macro_machine.get_score();
+        self.set_runtime(start_time.elapsed());
+
+        if self.halted == true {
+            return RunOutcome::Halted {
+                steps: self.steps,
+                score: self.score,
+            };
+        }
+
+        return RunOutcome::StepLimit;
+    }
+}
+
+impl TuringMachine {
+    pub fn new(transition_function: TransitionFunction) -> Self {
+        return TuringMachine::with_tape(transition_function);
+    }
+
+    /// Creates a Turing machine with `number_of_states` states and
+    /// `number_of_symbols` symbols from the encoding of its transition
+    /// function ( see `TransitionFunction::encode` ).
+    ///
+    /// Returns the `DecodeError` if the encoding is not valid.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// # use busy_beaver_reduction::turing_machine::turing_machine::{RunOutcome, TuringMachine};
+    /// // the BB(2) champion, 1RB1LB_1LA1RH
+    /// let mut turing_machine =
+    ///     TuringMachine::from_encoded("0,0,1,1,1|0,1,1,1,0|1,0,0,1,0|1,1,255,1,1", 2, 2)
+    ///         .unwrap();
+    ///
+    /// assert_eq!(
+    ///     turing_machine.run_to_halt(100),
+    ///     RunOutcome::Halted { steps: 6, score: 4 }
+    /// );
+    /// ```
+    pub fn from_encoded(
+        encoded: &str,
+        number_of_states: u8,
+        number_of_symbols: u8,
+    ) -> Result<Self, DecodeError> {
+        let mut transition_function = TransitionFunction::new(number_of_states, number_of_symbols);
+        transition_function.decode(encoded.to_string())?;
+
+        return Ok(TuringMachine::new(transition_function));
+    }
+
+    /// Sets the symbol every cell of the tape holds before it is written,
+    /// which is not counted in the score; the tape is reset to a single blank cell,
+    /// so it should be set before the Turing machine is executed.
+    ///
+    /// The `blank_symbol` should be one of the input symbols of the transition
+    /// function, since the tape holds it before the execution.
+    pub fn set_blank_symbol(&mut self, blank_symbol: u8) {
+        debug_assert!(blank_symbol < self.transition_function.input_alphabet_size());
+
+        self.blank_symbol = blank_symbol;
+        self.tape = VecDeque::from([blank_symbol]);
+        self.tape_origin = 0;
+        self.score = 0;
+    }
+
+    /// Calculate the extent from the tape, the coordinates of the leftmost
+    /// and rightmost non-blank cells, relative to the cell the Turing machine
+    /// started on.
+    ///
+    /// If every cell is blank, both ends are the position of the head.
+    pub fn set_extent(&mut self) {
+        self.extent = match self.get_written_region() {
+            Some((trimmed_start, trimmed_tape)) => (
+                trimmed_start as isize,
+                trimmed_start as isize + trimmed_tape.len() as isize - 1,
+            ),
+            None => (self.head_position as isize, self.head_position as isize),
+        };
+    }
+
+    /// Gets the default maximum number of steps to run a Turing machine
+    /// with `number_of_states` states for: the number of steps the
+    /// champion for `S(number_of_states)` halts in.
+    ///
+    /// For a number of states whose champion is not known,
+    /// the step limit of the largest known champion is used.
+    pub fn get_default_max_steps(number_of_states: u8) -> u64 {
+        let index = (number_of_states as usize).clamp(1, KNOWN_MAXIMUM_STEPS.len()) - 1;
+
+        return KNOWN_MAXIMUM_STEPS[index];
+    }
+
+    /// Sets the number of cells the tape can grow to while executing,
+    /// instead of `MAX_TAPE_CELLS`, after which the execution is
+    /// aborted and the turing machine is marked as `tape_limited`.
+    pub fn set_max_tape_cells(&mut self, max_tape_cells: usize) {
+        self.max_tape_cells = max_tape_cells;
+    }
+
+    /// Runs the turing machine until it is halted, until it reaches
+    /// `max_steps` steps or until it is stopped by a runtime filter.
+    ///
+    /// Uses a `FilterRuntime` object that is watching
+    /// carefully the execution of the turing machine.
+    /// If at any time the filters are not passed, stop the execution.
+    ///
+    /// The execution is also aborted once the tape grows over
+    /// `max_tape_cells` cells ( see `set_max_tape_cells` ).
+    pub fn execute(&mut self, max_steps: u64) {
+        self.execute_with_config(max_steps, &FilterRuntimeConfig::default());
+    }
+
+    /// Runs the turing machine in the same way as `execute`, but only
+    /// the runtime filters enabled in the `config` are applied, in the
+    /// configured order, e.g. to measure the catch rate of each filter.
+    pub fn execute_with_config(&mut self, max_steps: u64, config: &FilterRuntimeConfig) {
+        self.execute_with(max_steps, None, config, |_| {});
+    }
+
+    /// Runs the turing machine in the same way as `execute`, but it is also
+    /// stopped once it runs for longer than `timeout`, in which case it is
+    /// marked as `timed_out`.
+    ///
+    /// The elapsed time is checked every `TIMEOUT_CHECK_INTERVAL` steps.
+    pub fn execute_with_timeout(&mut self, max_steps: u64, timeout: Duration) {
+        self.execute_with(
+            max_steps,
+            Some(timeout),
+            &FilterRuntimeConfig::default(),
+            |_| {},
+        );
+    }
+
+    /// Runs the turing machine in the same way as `execute`, for
+    /// at most `max_steps` steps, and returns how the execution ended.
+    pub fn run_to_halt(&mut self, max_steps: u64) -> RunOutcome {
+        self.execute(max_steps);
+
+        if self.halted == true {
+            return RunOutcome::Halted {
+                steps: self.steps,
+                score: self.score,
+            };
+        }
+
+        if self.timed_out == true {
+            return RunOutcome::TimedOut;
+        }
+
+        if self.tape_limited == true {
+            return RunOutcome::TapeLimit;
+        }
+
+        match self.filtered {
+            FilterRuntimeType::None => return RunOutcome::StepLimit,
+            filtered => return RunOutcome::Filtered(filtered),
+        }
+    }
+
+    /// Runs the turing machine in the same way as `execute`, recording
+    /// its configuration after every step, e.g. for a space-time diagram.
+    ///
+    /// Returns the recorded configurations, in the order of the steps.
+    pub fn execute_traced(&mut self, max_steps: u64) -> Vec<TraceStep> {
+        let mut trace: Vec<TraceStep> = Vec::new();
+
+        self.execute_with(
+            max_steps,
+            None,
+            &FilterRuntimeConfig::default(),
+            |turing_machine| {
+                trace.push(turing_machine.get_trace_step());
+            },
+        );
+
+        return trace;
+    }
+
+    /// Runs the turing machine as described by `execute`, with the
+    /// runtime filters of the `config`, calling `on_step` after every
+    /// transition made, and stopping it after the `timeout`, if there is one.
+    ///
+    /// `execute` passes a closure that does nothing, which is optimized
+    /// away, so the execution without a trace is not slowed down.
+    fn execute_with<F: FnMut(&TuringMachine)>(
+        &mut self,
+        max_steps: u64,
+        timeout: Option<Duration>,
+        config: &FilterRuntimeConfig,
+        mut on_step: F,
+    ) {
+        let start_time: Instant = Instant::now();
+        let mut filter_runtime: FilterRuntime = FilterRuntime::new(config.clone());
+
+        // the score is kept up to date from the one of the current tape,
+        // not from a score that was set without writing the tape
+        self.set_score();
+
+        self.make_step(&mut on_step);
+
+        while self.halted != true && self.steps < max_steps {
+            let filter_result: FilterRuntimeType = filter_runtime.filter_all(&self);
+
+            match filter_result {
+                FilterRuntimeType::ShortEscapee
+                | FilterRuntimeType::LongEscapee
+                | FilterRuntimeType::Cycler
+                | FilterRuntimeType::TranslatedCycler
+                | FilterRuntimeType::Recurrence
+                | FilterRuntimeType::Bouncer
+                | FilterRuntimeType::Ctl => {
+                    self.filtered = filter_result;
+                    self.filtered_at_step = Some(self.steps);
+                    break;
+                }
+                FilterRuntimeType::None => {}
+            };
+
+            if let Some(timeout) = timeout {
+                if self.steps % TIMEOUT_CHECK_INTERVAL == 0 && start_time.elapsed() > timeout {
+                    self.timed_out = true;
+                    break;
+                }
+            }
+
+            // the tape only grows by one cell at a time
+            if self.tape_increased == true && self.tape.len() > self.max_tape_cells {
+                self.tape_limited = true;
+                break;
+            }
+
+            self.make_step(&mut on_step);
+        }
+
+        // set the metrics for the turing machine,
+        // the score was kept up to date by the transitions
+        debug_assert_eq!(self.score, self.tape.count_non_blank(self.blank_symbol));
+        self.set_space();
+        self.set_extent();
+        self.set_runtime(start_time.elapsed());
+    }
+
+    /// Makes a transition of the Turing machine, calling
+    /// `on_step` if it made a step.
+    fn make_step<F: FnMut(&TuringMachine)>(&mut self, on_step: &mut F) {
+        let steps = self.steps;
+
+        self.make_transition();
+
+        if self.steps != steps {
+            on_step(self);
+        }
+    }
+
     /// Encodes the Turing Machine's overall state as
-    /// a tuple `(String, usize, u8)`, where:
-    /// - String: hashed value of the tape
-    /// - usize: current head position
+    /// a tuple `(String, i64, u8)`, where:
+    /// - String: hashed value of the tape, trimmed of the blank
+    /// cells from both ends, together with the coordinate where it starts
+    /// - i64: current head position
     /// - u8: current state
-    pub fn encode(&self) -> (String, usize, u8) {
+    ///
+    /// Because the tape is trimmed, visiting a blank cell
+    /// does not change the encoding.
+    pub fn encode(&self) -> (String, i64, u8) {
         let mut hasher = Sha256::new();
-        hasher.input(&self.tape);
+
+        if let Some((trimmed_start, trimmed_tape)) = self.get_written_region() {
+            hasher.input(&trimmed_start.to_be_bytes());
+            hasher.input(&trimmed_tape);
+        }
+
         let hashed_tape = hasher.result_str();
 
         (hashed_tape, self.head_position, self.current_state)
     }
+
+    /// Gets the region of the tape between the first and the last
+    /// non-blank cells, together with the coordinate where it starts.
+    ///
+    /// Returns `None` if all the cells of the tape are blank.
+    pub fn get_written_region(&self) -> Option<(i64, Vec<u8>)> {
+        let first = self
+            .tape
+            .iter()
+            .position(|&symbol| symbol != self.blank_symbol)?;
+        let last = self
+            .tape
+            .iter()
+            .rposition(|&symbol| symbol != self.blank_symbol)?;
+
+        let trimmed_start = first as i64 - self.tape_origin as i64;
+        let trimmed_tape: Vec<u8> = self.tape.range(first..=last).copied().collect();
+
+        return Some((trimmed_start, trimmed_tape));
+    }
+
+    /// Gets the results of the Turing machine as a CSV row,
+    /// with the columns of `turing_machine_csv::CSV_HEADER`.
+    ///
+    /// The encoding contains commas, so it is quoted.
+    pub fn to_csv_row(&self) -> String {
+        return format!(
+            "{},{},{},{},{},{},{}",
+            escape_csv_field(&self.transition_function.encode()),
+            self.transition_function.number_of_states,
+            self.transition_function.number_of_symbols,
+            self.halted,
+            self.steps,
+            self.score,
+            self.runtime
+        );
+    }
+
+    /// Gets the current configuration of the Turing machine as a `TraceStep`.
+    fn get_trace_step(&self) -> TraceStep {
+        let (tape_start, tape) = self.get_written_region().unwrap_or((0, Vec::new()));
+
+        return TraceStep {
+            step: self.steps,
+            head_position: self.head_position,
+            current_state: self.current_state,
+            tape_start: tape_start,
+            tape: tape,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::delta::transition::Transition;
+    use crate::turing_machine::tape::BitTape;
+    use sqlx::TypeInfo;
+    use std::collections::HashSet;
+
+    /// Transition function of a 4-state Turing machine
+    /// that halts after exactly 47 steps.
+    const HALTS_IN_47_STEPS: &str =
+        "0,0,1,1,0|0,1,3,0,1|1,0,3,0,0|1,1,255,1,1|2,0,0,1,0|2,1,2,1,1|3,0,2,1,1|3,1,2,0,1";
+
+    #[test]
+    fn execute_with_max_steps() {
+        let mut transition_function: TransitionFunction = TransitionFunction::new(4, 2);
+        transition_function
+            .decode(HALTS_IN_47_STEPS.to_string())
+            .unwrap();
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        turing_machine.execute(100);
+
+        assert_eq!(turing_machine.halted, true);
+        assert_eq!(turing_machine.steps, 47);
+    }
+
+    #[test]
+    fn execute_stops_at_max_steps() {
+        let mut transition_function: TransitionFunction = TransitionFunction::new(4, 2);
+        transition_function
+            .decode(HALTS_IN_47_STEPS.to_string())
+            .unwrap();
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        turing_machine.execute(21);
+
+        assert_eq!(turing_machine.halted, false);
+        assert_eq!(turing_machine.steps, 21);
+    }
+
+    #[test]
+    fn execute_sets_filtered_at_step() {
+        // 2-state cycler, moving the head back and forth
+        // between two blank cells
+        let transition_function =
+            TransitionFunction::from_standard_notation("0RB1RH_0LA1RH").unwrap();
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        let maximum_steps = TuringMachine::get_default_max_steps(2);
+        turing_machine.execute(1000);
+
+        assert!(matches!(
+            turing_machine.filtered,
+            FilterRuntimeType::Recurrence
+        ));
+        assert_eq!(turing_machine.filtered_at_step, Some(turing_machine.steps));
+        assert!(turing_machine.filtered_at_step.unwrap() < maximum_steps);
+    }
+
+    #[test]
+    fn execute_with_config() {
+        // the same 2-state cycler, with only the escapees filters enabled
+        let transition_function =
+            TransitionFunction::from_standard_notation("0RB1RH_0LA1RH").unwrap();
+        let config = FilterRuntimeConfig::with_filters(vec![
+            FilterRuntimeType::ShortEscapee,
+            FilterRuntimeType::LongEscapee,
+        ]);
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        turing_machine.execute_with_config(1000, &config);
+
+        assert_eq!(turing_machine.filtered, FilterRuntimeType::None);
+        assert_eq!(turing_machine.filtered_at_step, None);
+        assert_eq!(turing_machine.halted, false);
+        assert_eq!(turing_machine.steps, 1000);
+    }
+
+    #[test]
+    fn execute_halts_on_undefined_transition() {
+        // (q_{1}, 0) is undefined
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_---1LA").unwrap();
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        turing_machine.execute(100);
+
+        assert_eq!(turing_machine.halted, true);
+        assert_eq!(
+            turing_machine.halt_reason,
+            Some(HaltReason::UndefinedTransition)
+        );
+        assert_eq!(turing_machine.filtered, FilterRuntimeType::None);
+        assert_eq!(turing_machine.steps, 2);
+        assert_eq!(turing_machine.score, 2);
+
+        // a Turing machine that halted does not make transitions anymore
+        assert_eq!(turing_machine.make_transition(), false);
+        assert_eq!(turing_machine.steps, 2);
+    }
+
+    #[test]
+    fn execute_halts_on_halt_state() {
+        let mut transition_function: TransitionFunction = TransitionFunction::new(4, 2);
+        transition_function
+            .decode(HALTS_IN_47_STEPS.to_string())
+            .unwrap();
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        turing_machine.execute(100);
+
+        assert_eq!(turing_machine.halt_reason, Some(HaltReason::HaltState));
+    }
+
+    #[test]
+    fn execute_halt_transition_does_not_move() {
+        // the BB(1) champion, 1RH
+        let transition_function = TransitionFunction::from_standard_notation("1RH").unwrap();
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        turing_machine.execute(100);
+
+        // a single 1 is written on the tape,
+        // which is not extended by the halting transition
+        assert_eq!(turing_machine.halted, true);
+        assert_eq!(turing_machine.steps, 1);
+        assert_eq!(turing_machine.score, 1);
+        assert_eq!(turing_machine.space, 1);
+        assert_eq!(turing_machine.head_position, 0);
+        assert_eq!(turing_machine.tape, VecDeque::from(vec![1]));
+    }
+
+    #[test]
+    fn execute_with_timeout() {
+        // the BB(5) champion, which does not halt in the first million steps
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LC_1RC1RB_1RD0LE_1LA1LD_1RH0LA")
+                .unwrap();
+
+        // every step is slowed down, so the timeout of 1ms is exceeded
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function.clone());
+        turing_machine.execute_with(
+            1_000_000,
+            Some(Duration::from_millis(1)),
+            &FilterRuntimeConfig::default(),
+            |_| {
+                std::thread::sleep(Duration::from_micros(10));
+            },
+        );
+
+        assert_eq!(turing_machine.timed_out, true);
+        assert_eq!(turing_machine.halted, false);
+        assert_eq!(turing_machine.filtered, FilterRuntimeType::None);
+        assert!(turing_machine.steps < 1_000_000);
+
+        // without the timeout, the step limit is reached
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        assert_eq!(turing_machine.run_to_halt(1000), RunOutcome::StepLimit);
+        assert_eq!(turing_machine.timed_out, false);
+    }
+
+    #[test]
+    fn step_n() {
+        // BB(3) champion, that halts after 14 steps
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1RH_0RC1RB_1LC1LA").unwrap();
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function.clone());
+        for _ in 0..3 {
+            assert_eq!(
+                turing_machine.step_n(3),
+                StepOutcome {
+                    steps: 3,
+                    halted: false
+                }
+            );
+        }
+
+        let mut turing_machine_once: TuringMachine = TuringMachine::new(transition_function);
+        turing_machine_once.execute_unfiltered(9);
+
+        assert_eq!(turing_machine.steps, 9);
+        assert_eq!(turing_machine.tape, turing_machine_once.tape);
+        assert_eq!(turing_machine.tape_origin, turing_machine_once.tape_origin);
+        assert_eq!(
+            turing_machine.head_position,
+            turing_machine_once.head_position
+        );
+        assert_eq!(
+            turing_machine.current_state,
+            turing_machine_once.current_state
+        );
+        assert_eq!(turing_machine.score, turing_machine_once.score);
+
+        // it stops early once it halts, and then it does not step anymore
+        assert_eq!(
+            turing_machine.step_n(10),
+            StepOutcome {
+                steps: 5,
+                halted: true
+            }
+        );
+        assert_eq!(turing_machine.score, 6);
+        assert_eq!(
+            turing_machine.step_n(10),
+            StepOutcome {
+                steps: 0,
+                halted: true
+            }
+        );
+    }
+
+    #[test]
+    fn execute_with_tape_limit() {
+        // a bouncer, whose tape grows by a cell at every bounce,
+        // which is identified by the runtime filters after 28 steps
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LA_1LA1RB").unwrap();
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function.clone());
+        turing_machine.set_max_tape_cells(4);
+
+        assert_eq!(turing_machine.run_to_halt(1000), RunOutcome::TapeLimit);
+        assert_eq!(turing_machine.tape_limited, true);
+        assert_eq!(turing_machine.halted, false);
+        assert_eq!(turing_machine.filtered, FilterRuntimeType::None);
+        assert_eq!(turing_machine.tape.len(), 5);
+        assert!(turing_machine.steps < 28);
+
+        // without the small cap, it is filtered
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        assert_eq!(
+            turing_machine.run_to_halt(1000),
+            RunOutcome::Filtered(FilterRuntimeType::Bouncer)
+        );
+        assert_eq!(turing_machine.tape_limited, false);
+    }
+
+    #[test]
+    fn execute_halted_is_not_filtered() {
+        let mut transition_function: TransitionFunction = TransitionFunction::new(4, 2);
+        transition_function
+            .decode(HALTS_IN_47_STEPS.to_string())
+            .unwrap();
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        turing_machine.execute(100);
+
+        assert_eq!(turing_machine.filtered_at_step, None);
+    }
+
+    #[test]
+    fn run_to_halt() {
+        let mut champion =
+            TuringMachine::from_encoded("0,0,1,1,1|0,1,1,1,0|1,0,0,1,0|1,1,255,1,1", 2, 2).unwrap();
+
+        assert_eq!(
+            champion.run_to_halt(100),
+            RunOutcome::Halted { steps: 6, score: 4 }
+        );
+
+        let mut turing_machine = TuringMachine::from_encoded(HALTS_IN_47_STEPS, 4, 2).unwrap();
+
+        assert_eq!(turing_machine.run_to_halt(21), RunOutcome::StepLimit);
+
+        let mut cycler = TuringMachine::new(
+            TransitionFunction::from_standard_notation("0RB1RH_0LA1RH").unwrap(),
+        );
+
+        assert_eq!(
+            cycler.run_to_halt(1000),
+            RunOutcome::Filtered(FilterRuntimeType::Recurrence)
+        );
+    }
+
+    #[test]
+    fn execute_traced() {
+        let mut transition_function: TransitionFunction = TransitionFunction::new(4, 2);
+        transition_function
+            .decode(HALTS_IN_47_STEPS.to_string())
+            .unwrap();
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        let trace = turing_machine.execute_traced(10);
+
+        assert_eq!(trace.len(), 10);
+        assert!(trace.windows(2).all(|steps| steps[0].step < steps[1].step));
+
+        // the last step recorded is the final configuration
+        let last_step = trace.last().unwrap();
+
+        assert_eq!(last_step.step, turing_machine.steps);
+        assert_eq!(last_step.head_position, turing_machine.head_position);
+        assert_eq!(last_step.current_state, turing_machine.current_state);
+        assert_eq!(
+            last_step.tape.iter().filter(|&&symbol| symbol == 1).count() as u64,
+            turing_machine.score
+        );
+    }
+
+    #[test]
+    fn get_default_max_steps() {
+        assert_eq!(TuringMachine::get_default_max_steps(2), 6);
+        assert_eq!(TuringMachine::get_default_max_steps(3), 21);
+        assert_eq!(TuringMachine::get_default_max_steps(4), 107);
+        assert_eq!(TuringMachine::get_default_max_steps(9), 47_176_870);
+    }
+
+    #[test]
+    fn serde_json_round_trip() {
+        let mut transition_function: TransitionFunction = TransitionFunction::new(4, 2);
+        transition_function
+            .decode(HALTS_IN_47_STEPS.to_string())
+            .unwrap();
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        turing_machine.execute(100);
+
+        let turing_machine_json = serde_json::to_string(&turing_machine).unwrap();
+        let turing_machine_deserialized: TuringMachine =
+            serde_json::from_str(&turing_machine_json).unwrap();
+
+        assert!(!turing_machine_json.contains("tape"));
+        assert_eq!(
+            turing_machine_deserialized.transition_function,
+            turing_machine.transition_function
+        );
+        assert_eq!(turing_machine_deserialized.halted, turing_machine.halted);
+        assert_eq!(turing_machine_deserialized.steps, turing_machine.steps);
+        assert_eq!(turing_machine_deserialized.score, turing_machine.score);
+        assert_eq!(turing_machine_deserialized.space, turing_machine.space);
+        assert_eq!(turing_machine_deserialized.runtime, turing_machine.runtime);
+        assert_eq!(
+            turing_machine_deserialized.filtered_at_step,
+            turing_machine.filtered_at_step
+        );
+    }
+
+    #[test]
+    fn final_extent() {
+        // the BB(2) champion
+        let mut turing_machine = TuringMachine::new(
+            TransitionFunction::from_standard_notation("1RB1LB_1LA1RH").unwrap(),
+        );
+        turing_machine.execute(100);
+
+        assert_eq!(turing_machine.halted, true);
+        assert_eq!(turing_machine.steps, 6);
+        assert_eq!(turing_machine.score, 4);
+        assert_eq!(turing_machine.final_extent(), (-2, 1));
+        // the halting transition does not move the head
+        assert_eq!(turing_machine.head_position, -1);
+    }
+
+    #[test]
+    fn set_space() {
+        let mut transition_function: TransitionFunction = TransitionFunction::new(3, 2);
+        transition_function
+            .decode("0,0,1,1,1|0,1,2,1,1|1,0,2,1,0|1,1,255,1,1|2,0,0,1,1|2,1,1,0,0".to_string())
+            .unwrap();
+
+        // run the turing machine without the runtime filters,
+        // until it halts after visiting 7 cells
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+
+        while turing_machine.halted == false && turing_machine.make_transition() {}
+
+        turing_machine.set_space();
+
+        assert_eq!(turing_machine.steps, 13);
+        assert_eq!(turing_machine.space, 7);
+    }
+
+    #[test]
+    fn dedup_by_encoding() {
+        let get_turing_machine = |encoded: &str, max_steps: u64| {
+            let mut turing_machine: TuringMachine =
+                TuringMachine::new(TransitionFunction::from_standard_notation(encoded).unwrap());
+            turing_machine.execute(max_steps);
+            turing_machine
+        };
+
+        // the BB(4) champion is in both runs, but it only
+        // halted in the second one, with a higher limit
+        let first = vec![
+            get_turing_machine("1RB1LB_1LA0LC_1RH1LD_1RD0RA", 50),
+            get_turing_machine("1RB1LB_1LA1RH", 100),
+        ];
+        let second = vec![
+            get_turing_machine("1RB1LA_1LA1RH", 100),
+            get_turing_machine("1RB1LB_1LA0LC_1RH1LD_1RD0RA", 200),
+        ];
+
+        let merged = TuringMachine::dedup_by_encoding(first.into_iter().chain(second).collect());
+        let encodings: HashSet<String> = merged
+            .iter()
+            .map(|turing_machine| turing_machine.transition_function.encode())
+            .collect();
+
+        assert_eq!(merged.len(), 3);
+        assert_eq!(encodings.len(), 3);
+        assert_eq!(merged[0].halted, true);
+        assert_eq!(merged[0].score, 13);
+    }
+
+    #[test]
+    fn reset_same_as_new() {
+        let bb4 =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA0LC_1RH1LD_1RD0RA").unwrap();
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(
+            TransitionFunction::from_standard_notation("1RB1LB_1LA1RH").unwrap(),
+        );
+        turing_machine.execute(100);
+        assert_eq!(turing_machine.halted, true);
+
+        turing_machine.reset(bb4.clone());
+        turing_machine.execute(1000);
+
+        let mut fresh: TuringMachine = TuringMachine::new(bb4);
+        fresh.execute(1000);
+
+        assert_eq!(turing_machine.halted, fresh.halted);
+        assert_eq!(turing_machine.halt_reason, fresh.halt_reason);
+        assert_eq!(turing_machine.steps, fresh.steps);
+        assert_eq!(turing_machine.score, fresh.score);
+        assert_eq!(turing_machine.space, fresh.space);
+        assert_eq!(turing_machine.head_position, fresh.head_position);
+        assert_eq!(turing_machine.current_state, fresh.current_state);
+        assert_eq!(turing_machine.extent, fresh.extent);
+        assert_eq!(turing_machine.filtered, fresh.filtered);
+        assert_eq!(
+            turing_machine.get_written_region(),
+            fresh.get_written_region()
+        );
+        assert_eq!(turing_machine.steps, 107);
+        assert_eq!(turing_machine.score, 13);
+
+        let mut bit_tape: TuringMachine<BitTape> = TuringMachine::with_tape(
+            TransitionFunction::from_standard_notation("1RB1LB_1LA1RH").unwrap(),
+        );
+        bit_tape.execute_unfiltered(100);
+        bit_tape.reset(TransitionFunction::new(2, 2));
+
+        assert_eq!(bit_tape.tape.len(), 1);
+        assert_eq!(bit_tape.get_head_symbol(), 0);
+    }
+
+    #[test]
+    fn head_position_signed() {
+        let mut turing_machine: TuringMachine = TuringMachine::new(TransitionFunction::new(1, 2));
+
+        turing_machine.move_left();
+        turing_machine.move_left();
+        turing_machine.move_right();
+
+        // the coordinate is relative to the starting cell,
+        // while the index is relative to the front of the tape
+        assert_eq!(turing_machine.head_position, -1);
+        assert_eq!(turing_machine.get_head_index(), 1);
+        assert_eq!(turing_machine.tape_origin, 2);
+    }
+
+    #[test]
+    fn move_left_extends_tape() {
+        let mut transition_function: TransitionFunction = TransitionFunction::new(1, 2);
+        transition_function.add_transition(Transition::new_params(0, 0, 0, 1, Direction::LEFT));
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+
+        // every move to the left reaches a new cell, which
+        // is added in front of the tape in constant time
+        for _ in 0..1000 {
+            turing_machine.make_transition();
+        }
+
+        turing_machine.set_score();
+
+        assert_eq!(turing_machine.head_position, -1000);
+        assert_eq!(turing_machine.get_head_index(), 0);
+        assert_eq!(turing_machine.tape_origin, 1000);
+        assert_eq!(turing_machine.tape.len(), 1001);
+        assert_eq!(turing_machine.score, 1000);
+    }
+
+    #[test]
+    fn encode_ignores_blank_cells() {
+        let mut transition_function: TransitionFunction = TransitionFunction::new(2, 2);
+        transition_function.add_transition(Transition::new_params(0, 0, 1, 1, Direction::LEFT));
+        transition_function.add_transition(Transition::new_params(1, 0, 0, 0, Direction::RIGHT));
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        let encoding_start = turing_machine.encode();
+
+        // write a 1, visit a blank cell on the left, then come back
+        turing_machine.make_transition();
+        turing_machine.make_transition();
+        let encoding_back = turing_machine.encode();
+
+        assert_eq!(turing_machine.tape.len(), 2);
+        assert_eq!(encoding_back.1, encoding_start.1);
+        assert_eq!(encoding_back.2, encoding_start.2);
+        assert_ne!(encoding_back.0, encoding_start.0);
+
+        // extending the tape with another blank
+        // cell does not change the encoding
+        turing_machine.tape.push_front(0);
+        turing_machine.tape_origin += 1;
+
+        assert_eq!(turing_machine.encode(), encoding_back);
+    }
+
+    #[test]
+    fn set_score_multiple_symbols() {
+        let transition_function: TransitionFunction = TransitionFunction::new(2, 4);
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        turing_machine.tape = VecDeque::from(vec![0, 1, 2, 0, 3]);
+
+        turing_machine.set_score();
+
+        assert_eq!(turing_machine.score, 3);
+    }
+
+    #[test]
+    fn score_incremental_same_as_full_scan() {
+        // writes a 1, then overwrites it back with a 0 and keeps erasing
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB0LB_0LA1RH").unwrap();
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+
+        turing_machine.make_transition();
+        assert_eq!(turing_machine.score, 1);
+
+        for _ in 0..10 {
+            turing_machine.make_transition();
+            assert_eq!(
+                turing_machine.score,
+                turing_machine
+                    .tape
+                    .count_non_blank(turing_machine.blank_symbol)
+            );
+        }
+
+        assert_eq!(turing_machine.score, 0);
+
+        // BB(4) champion, that erases some of the 1s it wrote
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA0LC_1RH1LD_1RD0RA").unwrap();
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+
+        while turing_machine.halted == false {
+            turing_machine.make_transition();
+            assert_eq!(
+                turing_machine.score,
+                turing_machine
+                    .tape
+                    .count_non_blank(turing_machine.blank_symbol)
+            );
+        }
+
+        assert_eq!(turing_machine.score, 13);
+
+        let score = turing_machine.score;
+        turing_machine.set_score();
+        assert_eq!(turing_machine.score, score);
+    }
+
+    #[test]
+    fn execute_ignores_stale_score() {
+        // BB(2) champion, with a score that does not match its blank tape
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA1RH").unwrap();
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        turing_machine.score = 3;
+
+        turing_machine.execute(100);
+
+        assert_eq!(turing_machine.steps, 6);
+        assert_eq!(turing_machine.score, 4);
+    }
+
+    #[test]
+    fn set_blank_symbol() {
+        let transition_function: TransitionFunction = TransitionFunction::new(2, 3);
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        turing_machine.set_blank_symbol(2);
+
+        assert_eq!(turing_machine.tape, VecDeque::from(vec![2]));
+
+        // the tape is extended with the blank symbol
+        turing_machine.move_right();
+        turing_machine.move_left();
+        turing_machine.move_left();
+        assert_eq!(turing_machine.tape, VecDeque::from(vec![2, 2, 2]));
+
+        turing_machine.tape = VecDeque::from(vec![2, 0, 1, 2, 1]);
+        turing_machine.set_score();
+
+        assert_eq!(turing_machine.score, 3);
+    }
+
+    #[test]
+    fn set_score_large_tape() {
+        let transition_function: TransitionFunction = TransitionFunction::new(2, 2);
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+        turing_machine.tape = VecDeque::from(vec![1; 100_000]);
+        turing_machine.steps = 47_176_870;
+
+        turing_machine.set_score();
+
+        // the values are bound in the database as `BIGINT UNSIGNED`
+        assert_eq!(
+            <u64 as sqlx::Type<sqlx::MySql>>::type_info().name(),
+            "BIGINT UNSIGNED"
+        );
+
+        // the values must be the same after being serialized
+        let turing_machine_json = serde_json::to_string(&turing_machine).unwrap();
+        let turing_machine_deserialized: TuringMachine =
+            serde_json::from_str(&turing_machine_json).unwrap();
+
+        assert_eq!(turing_machine.score, 100_000);
+        assert_eq!(turing_machine_deserialized.score, 100_000);
+        assert_eq!(turing_machine_deserialized.steps, 47_176_870);
+    }
+
+    #[test]
+    fn execute_unfiltered_bit_tape() {
+        // the BB(4) champion
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA0LC_1RH1LD_1RD0RA").unwrap();
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function.clone());
+        let mut turing_machine_bit_tape: TuringMachine<BitTape> =
+            TuringMachine::with_tape(transition_function);
+
+        turing_machine.execute_unfiltered(1000);
+        turing_machine_bit_tape.execute_unfiltered(1000);
+
+        assert_eq!(turing_machine.halted, true);
+        assert_eq!(turing_machine.steps, 107);
+        assert_eq!(turing_machine.score, 13);
+        assert_eq!(turing_machine_bit_tape.halted, true);
+        assert_eq!(turing_machine_bit_tape.steps, turing_machine.steps);
+        assert_eq!(turing_machine_bit_tape.score, turing_machine.score);
+        assert_eq!(turing_machine_bit_tape.space, turing_machine.space);
+    }
+
+    #[test]
+    #[should_panic(expected = "can not hold the 3 symbols")]
+    fn with_tape_bit_tape_rejects_three_symbols() {
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB2LB---_1LA1RH---").unwrap();
+
+        let _: TuringMachine<BitTape> = TuringMachine::with_tape(transition_function);
+    }
+
+    #[test]
+    fn execute_macro_same_as_naive() {
+        // the BB(4) champion
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LB_1LA0LC_1RH1LD_1RD0RA").unwrap();
+
+        for block_size in 1..=4 {
+            let mut turing_machine: TuringMachine = TuringMachine::new(transition_function.clone());
+
+            assert_eq!(
+                turing_machine.execute_macro(block_size, 1000),
+                RunOutcome::Halted {
+                    steps: 107,
+                    score: 13
+                }
+            );
+        }
+
+        // the BB(5) champion, whose long sweeps are made in a single step
+        let transition_function =
+            TransitionFunction::from_standard_notation("1RB1LC_1RC1RB_1RD0LE_1LA1LD_1RH0LA")
+                .unwrap();
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function);
+
+        assert_eq!(
+            turing_machine.execute_macro(3, 100_000_000),
+            RunOutcome::Halted {
+                steps: 47_176_870,
+                score: 4098
+            }
+        );
+    }
+
+    #[test]
+    fn execute_macro_bouncer() {
+        // bouncer, with a region that grows linearly
+        let transition_function =
+            TransitionFunction::from_standard_notation("1LC1RC_0RA1RH_0LD1RA_1LB1LD").unwrap();
+
+        let mut turing_machine: TuringMachine = TuringMachine::new(transition_function.clone());
+        turing_machine.execute_unfiltered(10_000);
+
+        for block_size in 1..=4 {
+            let mut turing_machine_macro: TuringMachine =
+                TuringMachine::new(transition_function.clone());
+
+            assert_eq!(
+                turing_machine_macro.execute_macro(block_size, 10_000),
+                RunOutcome::StepLimit
+            );
+            assert_eq!(turing_machine_macro.halted, turing_machine.halted);
+            assert_eq!(turing_machine_macro.steps, turing_machine.steps);
+        }
+    }
+
+    #[test]
+    fn best_of_champion() {
+        let get_turing_machine = |encoded: &str, score: u64, steps: u64| {
+            let mut turing_machine =
+                TuringMachine::new(TransitionFunction::from_standard_notation(encoded).unwrap());
+            turing_machine.score = score;
+            turing_machine.steps = steps;
+            turing_machine
+        };
+
+        let turing_machines = vec![
+            get_turing_machine("1RB1LB_1LA1RH", 4, 7),
+            get_turing_machine("1RB0LB_1LA1RH", 3, 2),
+            // same score as the first one, but in fewer steps
+            get_turing_machine("1RB1LA_1LA1RH", 4, 6),
+        ];
+
+        let champion = TuringMachine::best_of(&turing_machines).unwrap();
+
+        assert_eq!(
+            champion.transition_function.to_standard_notation().unwrap(),
+            "1RB1LA_1LA1RH"
+        );
+        assert_eq!(turing_machines[2].better_than(&turing_machines[0]), true);
+        assert_eq!(turing_machines[0].better_than(&turing_machines[1]), true);
+        assert_eq!(turing_machines[0].better_than(&turing_machines[0]), false);
+
+        // with the same score and steps, the smaller encoding wins
+        let tied = vec![
+            get_turing_machine("1RB1LB_1LA1RH", 4, 6),
+            get_turing_machine("1RB1LA_1LA1RH", 4, 6),
+        ];
+        let champion = TuringMachine::best_of(&tied).unwrap();
+
+        assert!(
+            champion.transition_function.encode()
+                < tied
+                    .iter()
+                    .map(|turing_machine| turing_machine.transition_function.encode())
+                    .max()
+                    .unwrap()
+        );
+        assert!(TuringMachine::<VecDeque<u8>>::best_of(&[]).is_none());
+    }
 }
@@ -0,0 +1,104 @@
+use std::io::{self, Write};
+
+use crate::turing_machine::turing_machine::TuringMachine;
+
+/// Columns of the rows written by `TuringMachine::to_csv_row`.
+pub const CSV_HEADER: &str = "encoding,states,symbols,halted,steps,score,runtime";
+
+/// Escapes a field according to RFC 4180: if it contains a comma,
+/// a double quote or a line break, it is enclosed in double quotes
+/// and the double quotes inside it are doubled.
+///
+/// The encoding of a transition function always contains commas,
+/// so it is always quoted.
+pub fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) == false {
+        return field.to_string();
+    }
+
+    return format!("\"{}\"", field.replace('"', "\"\""));
+}
+
+/// Writes the header followed by one row for each Turing machine,
+/// so the results can be analysed in a spreadsheet.
+pub fn write_csv<W: Write>(writer: &mut W, turing_machines: &[TuringMachine]) -> io::Result<()> {
+    writeln!(writer, "{}", CSV_HEADER)?;
+
+    for turing_machine in turing_machines {
+        writeln!(writer, "{}", turing_machine.to_csv_row())?;
+    }
+
+    return writer.flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::delta::transition_function::TransitionFunction;
+
+    /// Splits a CSV row into its fields, removing the
+    /// quotes of the quoted ones.
+    fn parse_csv_row(row: &str) -> Vec<String> {
+        let mut fields: Vec<String> = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut characters = row.chars().peekable();
+
+        while let Some(character) = characters.next() {
+            match (character, quoted) {
+                ('"', true) if characters.peek() == Some(&'"') => {
+                    field.push('"');
+                    characters.next();
+                }
+                ('"', _) => quoted = !quoted,
+                (',', false) => fields.push(std::mem::take(&mut field)),
+                _ => field.push(character),
+            }
+        }
+        fields.push(field);
+
+        return fields;
+    }
+
+    #[test]
+    fn escape_csv_field() {
+        assert_eq!(super::escape_csv_field("true"), "true");
+        assert_eq!(super::escape_csv_field("0,0,1"), "\"0,0,1\"");
+        assert_eq!(super::escape_csv_field("a\"b"), "\"a\"\"b\"");
+    }
+
+    #[test]
+    fn write_csv_round_trip() {
+        let notations = ["1RB1LB_1LA1RH", "1RB1RH_1LB0RA"];
+        let mut turing_machines: Vec<TuringMachine> = notations
+            .iter()
+            .map(|notation| {
+                TuringMachine::new(TransitionFunction::from_standard_notation(notation).unwrap())
+            })
+            .collect();
+
+        for turing_machine in turing_machines.iter_mut() {
+            turing_machine.execute(100);
+        }
+
+        let mut buffer: Vec<u8> = Vec::new();
+        write_csv(&mut buffer, &turing_machines).unwrap();
+
+        let csv = String::from_utf8(buffer).unwrap();
+        let mut rows = csv.lines();
+
+        assert_eq!(rows.next(), Some(CSV_HEADER));
+
+        for turing_machine in turing_machines.iter() {
+            let fields = parse_csv_row(rows.next().unwrap());
+
+            assert_eq!(fields.len(), 7);
+            assert_eq!(fields[0], turing_machine.transition_function.encode());
+            assert_eq!(fields[1], "2");
+            assert_eq!(fields[3], turing_machine.halted.to_string());
+            assert_eq!(fields[5].parse::<u64>().unwrap(), turing_machine.score);
+        }
+
+        assert_eq!(rows.next(), None);
+    }
+}
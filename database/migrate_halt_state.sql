@@ -0,0 +1,9 @@
+-- Rewrites the encodings stored with the legacy halting state, 101,
+-- to the current one, 255, so they are matched by the upserts keyed
+-- on `transition_function`; runs on both MySQL and Postgres.
+--
+-- With at most 101 states and 101 symbols, `,101,` can
+-- only be the state a transition moves into.
+UPDATE turing_machines
+SET transition_function = REPLACE(transition_function, ',101,', ',255,')
+WHERE number_of_states <= 101 AND number_of_symbols <= 101;
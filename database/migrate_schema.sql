@@ -0,0 +1,38 @@
+-- Migrates a `turing_machines` table created by an earlier `schema.sql`
+-- to the current one, since `CREATE TABLE IF NOT EXISTS` leaves an
+-- existing table untouched; MySQL only, see `migrate_schema_postgres.sql`.
+--
+-- Run `migrate_halt_state.sql` first, so the encodings differing only by
+-- the legacy halting state are deduplicated as well.
+USE `busy-beaver`;
+
+-- the rows already stored have no recorded space, are assumed to use
+-- all of their states, and their input alphabet is the tape alphabet
+ALTER TABLE `turing_machines`
+    ADD COLUMN `space` bigint NOT NULL DEFAULT 0,
+    ADD COLUMN `filtered_at_step` bigint unsigned NULL,
+    ADD COLUMN `filter_type` tinyint NULL,
+    ADD COLUMN `states_used` tinyint NOT NULL DEFAULT 0,
+    ADD COLUMN `input_alphabet_size` tinyint NOT NULL DEFAULT 0;
+
+UPDATE `turing_machines`
+SET `states_used` = `number_of_states`, `input_alphabet_size` = `number_of_symbols`;
+
+ALTER TABLE `turing_machines`
+    ALTER COLUMN `space` DROP DEFAULT,
+    ALTER COLUMN `states_used` DROP DEFAULT,
+    ALTER COLUMN `input_alphabet_size` DROP DEFAULT,
+    MODIFY `steps` bigint unsigned NOT NULL,
+    MODIFY `score` bigint unsigned NOT NULL;
+
+-- keep only the first row inserted for every transition
+-- function, before they are required to be unique
+DELETE `duplicate`
+FROM `turing_machines` AS `duplicate`
+JOIN `turing_machines` AS `original`
+    ON `duplicate`.`transition_function` = `original`.`transition_function`
+    AND `duplicate`.`id` > `original`.`id`;
+
+ALTER TABLE `turing_machines`
+    MODIFY `transition_function` varchar(768) NOT NULL,
+    ADD UNIQUE KEY `transition_function_unique` (`transition_function`);
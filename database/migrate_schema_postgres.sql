@@ -0,0 +1,33 @@
+-- Postgres equivalent of `migrate_schema.sql`, migrating a `turing_machines`
+-- table created by an earlier `schema_postgres.sql` to the current one.
+--
+-- Run `migrate_halt_state.sql` first, so the encodings differing only by
+-- the legacy halting state are deduplicated as well.
+
+-- the rows already stored have no recorded space, are assumed to use
+-- all of their states, and their input alphabet is the tape alphabet
+ALTER TABLE turing_machines
+    ADD COLUMN IF NOT EXISTS space bigint NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS filtered_at_step bigint NULL,
+    ADD COLUMN IF NOT EXISTS filter_type smallint NULL,
+    ADD COLUMN IF NOT EXISTS states_used smallint NULL,
+    ADD COLUMN IF NOT EXISTS input_alphabet_size smallint NULL;
+
+UPDATE turing_machines SET states_used = number_of_states WHERE states_used IS NULL;
+UPDATE turing_machines SET input_alphabet_size = number_of_symbols WHERE input_alphabet_size IS NULL;
+
+ALTER TABLE turing_machines
+    ALTER COLUMN space DROP DEFAULT,
+    ALTER COLUMN states_used SET NOT NULL,
+    ALTER COLUMN input_alphabet_size SET NOT NULL;
+
+-- keep only the first row inserted for every transition
+-- function, before they are required to be unique
+DELETE FROM turing_machines AS duplicate
+USING turing_machines AS original
+WHERE duplicate.transition_function = original.transition_function
+    AND duplicate.id > original.id;
+
+ALTER TABLE turing_machines
+    DROP CONSTRAINT IF EXISTS turing_machines_transition_function_key,
+    ADD CONSTRAINT turing_machines_transition_function_key UNIQUE (transition_function);
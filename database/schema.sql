@@ -3,13 +3,19 @@ USE `busy-beaver`;
 
 CREATE TABLE IF NOT EXISTS `turing_machines` (
     `id` int NOT NULL AUTO_INCREMENT,
-    `transition_function` text NOT NULL,
+    `transition_function` varchar(768) NOT NULL,
     `number_of_states` tinyint NOT NULL,
     `number_of_symbols` tinyint NOT NULL,
     `halted` tinyint NOT NULL,
-    `steps` bigint NOT NULL,
-    `score` bigint NOT NULL,
+    `steps` bigint unsigned NOT NULL,
+    `score` bigint unsigned NOT NULL,
     `time_to_run` int NOT NULL,
+    `space` bigint NOT NULL,
+    `filtered_at_step` bigint unsigned NULL,
+    `filter_type` tinyint NULL,
+    `states_used` tinyint NOT NULL,
+    `input_alphabet_size` tinyint NOT NULL,
     
-    PRIMARY KEY (`id`)
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `transition_function_unique` (`transition_function`)
 );
\ No newline at end of file
@@ -0,0 +1,19 @@
+-- Postgres equivalent of `schema.sql`, to be run
+-- against the `busy-beaver` database.
+CREATE TABLE IF NOT EXISTS turing_machines (
+    id serial NOT NULL,
+    transition_function text NOT NULL UNIQUE,
+    number_of_states smallint NOT NULL,
+    number_of_symbols smallint NOT NULL,
+    halted boolean NOT NULL,
+    steps bigint NOT NULL,
+    score bigint NOT NULL,
+    time_to_run bigint NOT NULL,
+    space bigint NOT NULL,
+    filtered_at_step bigint NULL,
+    filter_type smallint NULL,
+    states_used smallint NOT NULL,
+    input_alphabet_size smallint NOT NULL,
+
+    PRIMARY KEY (id)
+);